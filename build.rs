@@ -0,0 +1,155 @@
+use regex::Regex;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// A business rule validator, the table generated from it and its rule lists with the profile they are checked for
+struct Validator {
+    path: &'static str,
+    table: &'static str,
+    /// The file listing the rule ids of each rule list, included by the validator
+    rule_ids: &'static str,
+    rule_lists: &'static [(&'static str, Option<&'static str>)],
+}
+
+const VALIDATORS: [Validator; 2] = [
+    Validator {
+        path: "src/ubl/ubl_business_rule_validator.rs",
+        table: "UBL_RULES",
+        rule_ids: "ubl_rule_ids.rs",
+        rule_lists: &[("BUSINESS_RULES", None)],
+    },
+    Validator {
+        path: "src/cii/cii_business_rule_validator.rs",
+        table: "CII_RULES",
+        rule_ids: "cii_rule_ids.rs",
+        rule_lists: &[
+            ("MINIMUM_BUSINESS_RULES", Some("ZugferdProfile::Minimum")),
+            ("BASIC_WL_BUSINESS_RULES", Some("ZugferdProfile::BasicWl")),
+            ("BASIC_BUSINESS_RULES", Some("ZugferdProfile::Basic")),
+            ("EN16931_BUSINESS_RULES", Some("ZugferdProfile::En16931")),
+            ("EXTENDED_BUSINESS_RULES", Some("ZugferdProfile::Extended")),
+        ],
+    },
+];
+
+/// Generate the rule catalogue from the rule functions of the business rule validators
+///
+/// Every rule is written as
+/// ```text
+/// // Context: <context>
+/// // Test: <test>
+/// fn validate_<rule id>(invoice: ...) -> Result<(), ValidationError> {
+///     ... ValidationError::<severity>(BusinessRuleViolation::new("<rule id>", "[<rule id>]-<message>")) ...
+/// }
+/// ```
+/// and the rule id is derived from the function name, e.g. `validate_br_co_25` is `BR-CO-25`.
+/// Rules that are not implemented yet ignore the invoice, their parameter is named `_invoice`.
+/// For every rule list, e.g. `BASIC_BUSINESS_RULES`, the ids of its rules are listed in the same order
+/// as `BASIC_BUSINESS_RULE_IDS`, so that disabled rules are skipped without running them.
+fn main() {
+    let rule =
+        Regex::new(r"(?m)^// Context: (.*)\n// Test: (.*)\nfn validate_(\w+)\((\w+)").unwrap();
+    let violation = Regex::new(
+        r#"ValidationError::(Fatal|Warning)\(\s*BusinessRuleViolation::new\(\s*"[^"]*",\s*"((?:[^"\\]|\\.)*)""#,
+    )
+    .unwrap();
+    let message_prefix = Regex::new(r"^\[[^\]]*\]-").unwrap();
+    let business_term = Regex::new(r"B[GT]-\d+").unwrap();
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let mut generated = String::new();
+    for validator in VALIDATORS {
+        println!("cargo:rerun-if-changed={}", validator.path);
+        let source = fs::read_to_string(validator.path).unwrap();
+        let rule_lists = validator
+            .rule_lists
+            .iter()
+            .map(|(name, profile)| {
+                let list = Regex::new(&format!(
+                    r"(?s)pub const {}: &\[BusinessRule\] = &\[(.*?)\];",
+                    name
+                ))
+                .unwrap();
+                let members = list.captures(&source).unwrap()[1]
+                    .split(',')
+                    .map(|member| member.trim().to_string())
+                    .collect::<Vec<_>>();
+                (members, *profile)
+            })
+            .collect::<Vec<_>>();
+
+        let mut rule_ids = String::new();
+        for ((name, _), (members, _)) in validator.rule_lists.iter().zip(&rule_lists) {
+            let ids = members
+                .iter()
+                .filter(|member| !member.is_empty())
+                .map(|member| rule_id(member.trim_start_matches("validate_")))
+                .collect::<Vec<_>>();
+            writeln!(
+                rule_ids,
+                "pub(crate) const {}: &[&str] = &{:?};",
+                name.replace("_RULES", "_RULE_IDS"),
+                ids
+            )
+            .unwrap();
+        }
+        fs::write(Path::new(&out_dir).join(validator.rule_ids), rule_ids).unwrap();
+
+        writeln!(
+            generated,
+            "pub(crate) const {}: &[CatalogueEntry] = &[",
+            validator.table
+        )
+        .unwrap();
+        let rules = rule.captures_iter(&source).collect::<Vec<_>>();
+        for (index, captures) in rules.iter().enumerate() {
+            let start = captures.get(0).unwrap().end();
+            let end = rules
+                .get(index + 1)
+                .map_or(source.len(), |next| next.get(0).unwrap().start());
+            let body = &source[start..end];
+            let function = format!("validate_{}", &captures[3]);
+            let violation = violation.captures(body).unwrap();
+            let message = message_prefix.replace(&violation[2], "");
+            let business_terms = business_term
+                .find_iter(&message)
+                .map(|term| term.as_str())
+                .fold(Vec::new(), |mut terms, term| {
+                    if !terms.contains(&term) {
+                        terms.push(term);
+                    }
+                    terms
+                });
+            let profiles = rule_lists
+                .iter()
+                .filter(|(members, _)| members.contains(&function))
+                .filter_map(|(_, profile)| *profile)
+                .collect::<Vec<_>>();
+            let checked = rule_lists
+                .iter()
+                .any(|(members, _)| members.contains(&function));
+            writeln!(
+                generated,
+                "    CatalogueEntry {{ rule_id: {:?}, severity: RuleSeverity::{}, message: \"{}\", context: {:?}, test: {:?}, business_terms: &{:?}, profiles: &[{}], implemented: {} }},",
+                rule_id(&captures[3]),
+                &violation[1],
+                message,
+                captures[1].trim(),
+                captures[2].trim(),
+                business_terms,
+                profiles.join(", "),
+                checked && !captures[4].starts_with('_'),
+            )
+            .unwrap();
+        }
+        writeln!(generated, "];").unwrap();
+    }
+    fs::write(Path::new(&out_dir).join("rule_catalogue.rs"), generated).unwrap();
+}
+
+/// The rule id of a rule function name without `validate_`, e.g. `br_co_25` is `BR-CO-25`
+fn rule_id(name: &str) -> String {
+    name.to_uppercase().replace('_', "-")
+}
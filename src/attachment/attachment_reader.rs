@@ -0,0 +1,129 @@
+use crate::InvoiceStandard;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// MIME codes EN 16931 allows for attached documents (BR-CL-24)
+pub const ALLOWED_MIME_CODES: [&str; 6] = [
+    "application/pdf",
+    "image/png",
+    "image/jpeg",
+    "text/csv",
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+    "application/vnd.oasis.opendocument.spreadsheet",
+];
+
+/// An additional supporting document (BG-24) of an invoice
+#[derive(uniffi::Record)]
+pub struct InvoiceAttachment {
+    /// Supporting document reference (BT-122)
+    pub id: Option<String>,
+    /// The document type code, e.g. `916` for a supporting document or `130` for an invoiced object
+    pub type_code: Option<String>,
+    /// Supporting document description (BT-123)
+    pub description: Option<String>,
+    /// External document location (BT-124)
+    pub uri: Option<String>,
+    /// Attached document file name (BT-125-2)
+    pub file_name: Option<String>,
+    /// Attached document MIME code (BT-125-1)
+    pub mime_code: Option<String>,
+    /// Whether the MIME code is one of the `ALLOWED_MIME_CODES`
+    pub mime_code_allowed: bool,
+    /// The base64 decoded attached document (BT-125), `None` if it is not embedded or not valid base64
+    pub content: Option<Vec<u8>>,
+    /// Why the embedded document cannot be decoded
+    pub error: Option<String>,
+}
+
+/// List the additional supporting documents of an invoice
+/// # Arguments
+/// * `invoice` - The invoice
+/// # Returns
+/// * `Vec<InvoiceAttachment>` - The supporting documents with their embedded content decoded
+///
+/// Documents referenced only by URI are returned without content.
+/// Documents that are not valid base64 are returned without content and with an `error`.
+pub fn invoice_attachments(invoice: &InvoiceStandard) -> Vec<InvoiceAttachment> {
+    match invoice {
+        InvoiceStandard::UBL(invoice) => invoice
+            .cac_additional_document_reference
+            .iter()
+            .map(|document| {
+                let attachment = document.cac_attachment.as_ref();
+                let object = attachment
+                    .and_then(|attachment| attachment.cbc_embedded_document_binary_object.as_ref());
+                invoice_attachment(
+                    document.id.as_ref().and_then(|id| id.id.clone()),
+                    document.cbc_document_type_code.clone(),
+                    (!document.cbc_document_description.is_empty())
+                        .then(|| document.cbc_document_description.join("\n")),
+                    attachment
+                        .and_then(|attachment| attachment.cac_external_reference.as_ref())
+                        .and_then(|reference| reference.cbc_uri.clone()),
+                    object.and_then(|object| object.filename.clone()),
+                    object.and_then(|object| object.mime_code.clone()),
+                    object.and_then(|object| object.text.as_deref()),
+                )
+            })
+            .collect(),
+        InvoiceStandard::CII(invoice) => invoice
+            .rsm_supply_chain_trade_transaction
+            .iter()
+            .filter_map(|transaction| transaction.ram_applicable_header_trade_agreement.as_ref())
+            .flat_map(|agreement| agreement.ram_additional_referenced_document.iter())
+            .map(|document| {
+                let object = document.ram_attachment_binary_object.as_ref();
+                invoice_attachment(
+                    document
+                        .ram_issuer_assigned_id
+                        .as_ref()
+                        .and_then(|id| id.id.clone()),
+                    document.ram_type_code.clone(),
+                    document.ram_name.clone(),
+                    document.ram_uriid.clone(),
+                    object.and_then(|object| object.filename.clone()),
+                    object.and_then(|object| object.mime_code.clone()),
+                    object.and_then(|object| object.text.as_deref()),
+                )
+            })
+            .collect(),
+    }
+}
+
+/// Decode a base64 attached document, ignoring the line breaks of MIME encoded content
+pub(crate) fn decode_attachment(content: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    let content = content
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect::<String>();
+    STANDARD.decode(content)
+}
+
+fn invoice_attachment(
+    id: Option<String>,
+    type_code: Option<String>,
+    description: Option<String>,
+    uri: Option<String>,
+    file_name: Option<String>,
+    mime_code: Option<String>,
+    content: Option<&str>,
+) -> InvoiceAttachment {
+    let (content, error) = match content.map(decode_attachment) {
+        Some(Ok(content)) => (Some(content), None),
+        Some(Err(e)) => (None, Some(format!("Not valid base64: {}", e))),
+        None => (None, None),
+    };
+    InvoiceAttachment {
+        mime_code_allowed: mime_code
+            .as_deref()
+            .is_some_and(|mime_code| ALLOWED_MIME_CODES.contains(&mime_code)),
+        id,
+        type_code,
+        description,
+        uri,
+        file_name,
+        mime_code,
+        content,
+        error,
+    }
+}
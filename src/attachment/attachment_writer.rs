@@ -0,0 +1,259 @@
+use crate::attachment::attachment_reader::ALLOWED_MIME_CODES;
+use crate::cii::cii_model::{
+    IdType, RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument,
+    RamAttachmentBinaryObject,
+};
+use crate::ubl::ubl_model::{
+    CacAttachment, CacDocumentReference, CbcEmbeddedDocumentBinaryObject, Identifier,
+};
+use crate::{CrossIndustryInvoice, InvoiceError, UblInvoice};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+/// Document type code of an additional supporting document (UNTDID 1001)
+pub const SUPPORTING_DOCUMENT_TYPE_CODE: &str = "916";
+
+impl UblInvoice {
+    /// Attach a document as an additional supporting document (BG-24)
+    /// # Arguments
+    /// * `id` - The supporting document reference (BT-122)
+    /// * `file_name` - The file name of the attached document, unique within the invoice
+    /// * `mime_code` - The MIME code, one of the `ALLOWED_MIME_CODES`
+    /// * `description` - The supporting document description (BT-123)
+    /// * `content` - The content of the attached document
+    /// # Errors
+    /// * `InvoiceError::ValidationError` - If the MIME code is not allowed or the file name is already attached
+    pub fn add_attachment(
+        &mut self,
+        id: &str,
+        file_name: &str,
+        mime_code: &str,
+        description: Option<&str>,
+        content: &[u8],
+    ) -> Result<(), InvoiceError> {
+        check_mime_code(mime_code)?;
+        if self.attachment_index(file_name).is_some() {
+            return Err(duplicate_file_name(file_name));
+        }
+        self.cac_additional_document_reference
+            .push(CacDocumentReference {
+                id: Some(Identifier {
+                    id: Some(id.to_string()),
+                    ..Default::default()
+                }),
+                cbc_document_type_code: Some(SUPPORTING_DOCUMENT_TYPE_CODE.to_string()),
+                cbc_document_description: description
+                    .map(ToString::to_string)
+                    .into_iter()
+                    .collect(),
+                cac_attachment: Some(CacAttachment {
+                    cbc_embedded_document_binary_object: Some(CbcEmbeddedDocumentBinaryObject {
+                        mime_code: Some(mime_code.to_string()),
+                        filename: Some(file_name.to_string()),
+                        text: Some(STANDARD.encode(content)),
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+        Ok(())
+    }
+
+    /// Replace the content of an attached document
+    /// # Arguments
+    /// * `file_name` - The file name of the attached document
+    /// * `mime_code` - The new MIME code, one of the `ALLOWED_MIME_CODES`
+    /// * `content` - The new content of the attached document
+    /// # Errors
+    /// * `InvoiceError::ValidationError` - If the MIME code is not allowed or no document with the file name is attached
+    pub fn replace_attachment(
+        &mut self,
+        file_name: &str,
+        mime_code: &str,
+        content: &[u8],
+    ) -> Result<(), InvoiceError> {
+        check_mime_code(mime_code)?;
+        let index = self
+            .attachment_index(file_name)
+            .ok_or_else(|| missing_file_name(file_name))?;
+        if let Some(object) = self.cac_additional_document_reference[index]
+            .cac_attachment
+            .as_mut()
+            .and_then(|attachment| attachment.cbc_embedded_document_binary_object.as_mut())
+        {
+            object.mime_code = Some(mime_code.to_string());
+            object.text = Some(STANDARD.encode(content));
+        }
+        Ok(())
+    }
+
+    /// Remove an attached document including its supporting document reference
+    /// # Arguments
+    /// * `file_name` - The file name of the attached document
+    /// # Returns
+    /// * `bool` - Whether a document with the file name was attached
+    pub fn remove_attachment(&mut self, file_name: &str) -> bool {
+        match self.attachment_index(file_name) {
+            Some(index) => {
+                self.cac_additional_document_reference.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn attachment_index(&self, file_name: &str) -> Option<usize> {
+        self.cac_additional_document_reference
+            .iter()
+            .position(|document| {
+                document
+                    .cac_attachment
+                    .as_ref()
+                    .and_then(|attachment| attachment.cbc_embedded_document_binary_object.as_ref())
+                    .and_then(|object| object.filename.as_deref())
+                    == Some(file_name)
+            })
+    }
+}
+
+impl CrossIndustryInvoice {
+    /// Attach a document as an additional supporting document (BG-24)
+    /// # Arguments
+    /// * `id` - The supporting document reference (BT-122)
+    /// * `file_name` - The file name of the attached document, unique within the invoice
+    /// * `mime_code` - The MIME code, one of the `ALLOWED_MIME_CODES`
+    /// * `description` - The supporting document description (BT-123)
+    /// * `content` - The content of the attached document
+    /// # Errors
+    /// * `InvoiceError::ValidationError` - If the MIME code is not allowed, the file name is already attached
+    ///   or the invoice has no header trade agreement
+    pub fn add_attachment(
+        &mut self,
+        id: &str,
+        file_name: &str,
+        mime_code: &str,
+        description: Option<&str>,
+        content: &[u8],
+    ) -> Result<(), InvoiceError> {
+        check_mime_code(mime_code)?;
+        if self.attachment_index(file_name).is_some() {
+            return Err(duplicate_file_name(file_name));
+        }
+        self.additional_referenced_documents()?.push(
+            RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument {
+                ram_issuer_assigned_id: Some(IdType {
+                    id: Some(id.to_string()),
+                    ..Default::default()
+                }),
+                ram_type_code: Some(SUPPORTING_DOCUMENT_TYPE_CODE.to_string()),
+                ram_name: description.map(ToString::to_string),
+                ram_attachment_binary_object: Some(RamAttachmentBinaryObject {
+                    mime_code: Some(mime_code.to_string()),
+                    filename: Some(file_name.to_string()),
+                    text: Some(STANDARD.encode(content)),
+                }),
+                ..Default::default()
+            },
+        );
+        Ok(())
+    }
+
+    /// Replace the content of an attached document
+    /// # Arguments
+    /// * `file_name` - The file name of the attached document
+    /// * `mime_code` - The new MIME code, one of the `ALLOWED_MIME_CODES`
+    /// * `content` - The new content of the attached document
+    /// # Errors
+    /// * `InvoiceError::ValidationError` - If the MIME code is not allowed or no document with the file name is attached
+    pub fn replace_attachment(
+        &mut self,
+        file_name: &str,
+        mime_code: &str,
+        content: &[u8],
+    ) -> Result<(), InvoiceError> {
+        check_mime_code(mime_code)?;
+        let index = self
+            .attachment_index(file_name)
+            .ok_or_else(|| missing_file_name(file_name))?;
+        if let Some(object) = self.additional_referenced_documents()?[index]
+            .ram_attachment_binary_object
+            .as_mut()
+        {
+            object.mime_code = Some(mime_code.to_string());
+            object.text = Some(STANDARD.encode(content));
+        }
+        Ok(())
+    }
+
+    /// Remove an attached document including its supporting document reference
+    /// # Arguments
+    /// * `file_name` - The file name of the attached document
+    /// # Returns
+    /// * `bool` - Whether a document with the file name was attached
+    pub fn remove_attachment(&mut self, file_name: &str) -> bool {
+        match self.attachment_index(file_name) {
+            Some(index) => self
+                .additional_referenced_documents()
+                .map(|documents| {
+                    documents.remove(index);
+                })
+                .is_ok(),
+            None => false,
+        }
+    }
+
+    fn attachment_index(&self, file_name: &str) -> Option<usize> {
+        self.rsm_supply_chain_trade_transaction
+            .as_ref()
+            .and_then(|transaction| transaction.ram_applicable_header_trade_agreement.as_ref())?
+            .ram_additional_referenced_document
+            .iter()
+            .position(|document| {
+                document
+                    .ram_attachment_binary_object
+                    .as_ref()
+                    .and_then(|object| object.filename.as_deref())
+                    == Some(file_name)
+            })
+    }
+
+    fn additional_referenced_documents(
+        &mut self,
+    ) -> Result<
+        &mut Vec<RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument>,
+        InvoiceError,
+    > {
+        self.rsm_supply_chain_trade_transaction
+            .as_mut()
+            .and_then(|transaction| transaction.ram_applicable_header_trade_agreement.as_mut())
+            .map(|agreement| &mut agreement.ram_additional_referenced_document)
+            .ok_or_else(|| {
+                InvoiceError::ValidationError(
+                    "The invoice has no ApplicableHeaderTradeAgreement".to_string(),
+                )
+            })
+    }
+}
+
+fn check_mime_code(mime_code: &str) -> Result<(), InvoiceError> {
+    if ALLOWED_MIME_CODES.contains(&mime_code) {
+        Ok(())
+    } else {
+        Err(InvoiceError::ValidationError(format!(
+            "[BR-CL-24]-The MIME code '{}' is not one of {}",
+            mime_code,
+            ALLOWED_MIME_CODES.join(", ")
+        )))
+    }
+}
+
+fn duplicate_file_name(file_name: &str) -> InvoiceError {
+    InvoiceError::ValidationError(format!(
+        "A document named '{}' is already attached",
+        file_name
+    ))
+}
+
+fn missing_file_name(file_name: &str) -> InvoiceError {
+    InvoiceError::ValidationError(format!("No document named '{}' is attached", file_name))
+}
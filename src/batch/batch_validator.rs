@@ -0,0 +1,254 @@
+use crate::{
+    decode_xml, invoice_report, zugferd_pdf_bytes_report, InvoiceStandard, ValidationConfig,
+    ValidationError, ValidationReport,
+};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+
+/// Profile label of an invoice whose specification identifier is missing
+const UNKNOWN_PROFILE: &str = "unknown";
+
+/// A document to validate in a batch, either an invoice XML or a ZUGFeRD / Factur-X PDF
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BatchInput {
+    /// A name identifying the document in the report, e.g. its path
+    pub name: String,
+    /// The content of the document, PDFs are recognised by their `%PDF` header
+    pub content: Vec<u8>,
+}
+
+impl BatchInput {
+    /// Read a document from a file, using its path as name
+    /// # Arguments
+    /// * `path` - The path to the XML or PDF file
+    /// # Returns
+    /// * `BatchInput` - The document
+    /// # Errors
+    /// * `std::io::Error` - If the file cannot be read
+    pub fn read(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        Ok(BatchInput {
+            name: path.display().to_string(),
+            content: std::fs::read(path)?,
+        })
+    }
+
+    /// Whether the document is a PDF
+    pub fn is_pdf(&self) -> bool {
+        self.content.starts_with(b"%PDF")
+    }
+}
+
+/// The validation result of a single document in a batch
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BatchDocumentReport {
+    /// The name of the input
+    pub name: String,
+    /// The profile of the invoice, see `BatchStatistics::profiles`
+    pub profile: Option<String>,
+    /// The violations of the document, `None` if it could not be validated
+    pub report: Option<ValidationReport>,
+    /// Why the document could not be validated, e.g. unparsable XML or a panic in a rule
+    pub error: Option<String>,
+}
+
+impl BatchDocumentReport {
+    /// Whether the document was validated without violations other than warnings
+    pub fn is_valid(&self) -> bool {
+        self.report.as_ref().is_some_and(ValidationReport::is_valid)
+    }
+}
+
+/// How often a business rule was violated in a batch
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct RuleCount {
+    pub rule_id: String,
+    /// The number of documents violating the rule
+    pub count: u64,
+}
+
+/// How many documents of a batch have a profile
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct ProfileCount {
+    pub profile: String,
+    pub count: u64,
+}
+
+/// Aggregated results of a batch
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BatchStatistics {
+    /// The number of documents in the batch
+    pub total: u64,
+    /// The number of documents without violations other than warnings
+    pub passed: u64,
+    /// The number of documents with violations other than warnings
+    pub failed: u64,
+    /// The number of documents that could not be validated
+    pub errors: u64,
+    /// The violated rules, most frequent first
+    pub rules: Vec<RuleCount>,
+    /// The profiles of the validated documents, most frequent first
+    ///
+    /// This is the ZUGFeRD / Factur-X conformance level of CII invoices and the specification identifier otherwise.
+    pub profiles: Vec<ProfileCount>,
+}
+
+/// The validation results of a batch
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct BatchReport {
+    /// The results in the order of the inputs
+    pub documents: Vec<BatchDocumentReport>,
+    pub statistics: BatchStatistics,
+}
+
+/// Validate many invoice XML and ZUGFeRD PDF documents in parallel
+/// # Arguments
+/// * `inputs` - The documents to validate
+/// # Returns
+/// * `BatchReport` - A report for every document and statistics over all of them
+///
+/// XML documents are validated like `validate_invoice_report`, PDFs like `validate_zugferd_pdf_report`.
+/// A document that cannot be validated, even one panicking during validation, does not abort the batch
+/// but is reported with an error.
+#[uniffi::export]
+pub fn validate_batch(inputs: Vec<BatchInput>) -> BatchReport {
+    validate_batch_with_config(inputs, &ValidationConfig::default())
+}
+
+/// Validate many invoice XML and ZUGFeRD PDF documents in parallel with a custom rule set
+/// # Arguments
+/// * `inputs` - The documents to validate
+/// * `config` - Which business rules are checked and with which severity
+/// # Returns
+/// * `BatchReport` - A report for every document and statistics over all of them
+#[uniffi::export]
+pub fn validate_batch_with_config(
+    inputs: Vec<BatchInput>,
+    config: &ValidationConfig,
+) -> BatchReport {
+    let documents = inputs
+        .par_iter()
+        .map(|input| validate_document(input, config))
+        .collect::<Vec<_>>();
+    let statistics = statistics(&documents);
+    BatchReport {
+        documents,
+        statistics,
+    }
+}
+
+fn validate_document(input: &BatchInput, config: &ValidationConfig) -> BatchDocumentReport {
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        if input.is_pdf() {
+            zugferd_pdf_bytes_report(&input.content, config)
+        } else {
+            decode_xml(&input.content).and_then(|xml| invoice_report(&xml, config))
+        }
+    }));
+    match result {
+        Ok(Ok((invoice_standard, report))) => BatchDocumentReport {
+            name: input.name.clone(),
+            profile: Some(profile(&invoice_standard, config)),
+            report: Some(report),
+            error: None,
+        },
+        Ok(Err(e)) => failed(input, e.to_string()),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown cause".to_string());
+            failed(input, format!("Validation panicked: {}", message))
+        }
+    }
+}
+
+fn failed(input: &BatchInput, error: String) -> BatchDocumentReport {
+    BatchDocumentReport {
+        name: input.name.clone(),
+        profile: None,
+        report: None,
+        error: Some(error),
+    }
+}
+
+fn profile(invoice_standard: &InvoiceStandard, config: &ValidationConfig) -> String {
+    invoice_standard
+        .zugferd_profile_with_config(config)
+        .map(|profile| profile.conformance_level().to_string())
+        .or_else(|| {
+            invoice_standard
+                .specification_identifier()
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| UNKNOWN_PROFILE.to_string())
+}
+
+fn statistics(documents: &[BatchDocumentReport]) -> BatchStatistics {
+    let mut rules = HashMap::<&str, u64>::new();
+    let mut profiles = HashMap::<&str, u64>::new();
+    let (mut passed, mut failed, mut errors) = (0, 0, 0);
+    for document in documents {
+        let Some(report) = &document.report else {
+            errors += 1;
+            continue;
+        };
+        if report.is_valid() {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
+        if let Some(profile) = &document.profile {
+            *profiles.entry(profile).or_default() += 1;
+        }
+        let mut rule_ids = report
+            .violations
+            .iter()
+            .filter_map(rule_id)
+            .collect::<Vec<_>>();
+        rule_ids.sort_unstable();
+        rule_ids.dedup();
+        for rule_id in rule_ids {
+            *rules.entry(rule_id).or_default() += 1;
+        }
+    }
+    BatchStatistics {
+        total: documents.len() as u64,
+        passed,
+        failed,
+        errors,
+        rules: most_frequent(rules)
+            .into_iter()
+            .map(|(rule_id, count)| RuleCount { rule_id, count })
+            .collect(),
+        profiles: most_frequent(profiles)
+            .into_iter()
+            .map(|(profile, count)| ProfileCount { profile, count })
+            .collect(),
+    }
+}
+
+fn rule_id(violation: &ValidationError) -> Option<&str> {
+    match violation {
+        ValidationError::Fatal(violation)
+        | ValidationError::Warning(violation)
+        | ValidationError::MetadataMismatch(violation)
+        | ValidationError::PdfViolation(violation) => Some(violation.rule_id.as_str()),
+        ValidationError::ConcurrencyError(_) => None,
+    }
+}
+
+/// Sort counted keys by descending count, ties by key to keep the order stable
+fn most_frequent(counts: HashMap<&str, u64>) -> Vec<(String, u64)> {
+    let mut counts = counts
+        .into_iter()
+        .map(|(key, count)| (key.to_string(), count))
+        .collect::<Vec<_>>();
+    counts.sort_by(|(a_key, a_count), (b_key, b_count)| {
+        b_count.cmp(a_count).then_with(|| a_key.cmp(b_key))
+    });
+    counts
+}
@@ -0,0 +1,51 @@
+//! Helpers shared by the command-line tool and the validation server
+
+use einvoice::{SourceLocation, ValidationError, ValidationReport};
+use serde_json::{json, Value};
+
+/// Whether a file is a PDF, judged by its header
+pub fn is_pdf(content: &[u8]) -> bool {
+    content.starts_with(b"%PDF")
+}
+
+/// The severity, rule id and text of a violation
+pub fn violation_parts(violation: &ValidationError) -> (&'static str, &str, &str) {
+    match violation {
+        ValidationError::Fatal(v) => ("fatal", &v.rule_id, &v.rule_text),
+        ValidationError::Warning(v) => ("warning", &v.rule_id, &v.rule_text),
+        ValidationError::MetadataMismatch(v) => ("metadata", &v.rule_id, &v.rule_text),
+        ValidationError::PdfViolation(v) => ("pdf", &v.rule_id, &v.rule_text),
+        ValidationError::ConcurrencyError(text) => ("error", "", text),
+    }
+}
+
+/// The element a violation was found at, if it was located in the XML source
+pub fn violation_location(violation: &ValidationError) -> Option<&SourceLocation> {
+    match violation {
+        ValidationError::Fatal(v)
+        | ValidationError::Warning(v)
+        | ValidationError::MetadataMismatch(v)
+        | ValidationError::PdfViolation(v) => v.location.as_ref(),
+        ValidationError::ConcurrencyError(_) => None,
+    }
+}
+
+/// A validation report as JSON object with the fields `valid`, `embedded_file` and `violations`
+pub fn report_json(report: &ValidationReport) -> Value {
+    let violations = report
+        .violations
+        .iter()
+        .map(|violation| {
+            let (severity, rule_id, text) = violation_parts(violation);
+            let location = violation_location(violation).map(|location| {
+                json!({ "path": location.path, "line": location.line, "column": location.column })
+            });
+            json!({ "severity": severity, "rule_id": rule_id, "text": text, "location": location })
+        })
+        .collect::<Vec<_>>();
+    json!({
+        "valid": report.is_valid(),
+        "embedded_file": report.file_name,
+        "violations": violations,
+    })
+}
@@ -0,0 +1,410 @@
+mod common;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use common::{is_pdf, report_json, violation_location, violation_parts};
+use einvoice::{
+    convert_invoice, decode_xml, extract_zugferd_xml_bytes, parse_invoice, render_html,
+    validate_invoice_bytes_report, validate_zugferd_pdf_bytes_report, EmbeddedInvoiceXml,
+    InvoiceSyntax, ValidationReport,
+};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Validate, convert and inspect EN 16931 e-invoices in UBL, CII and ZUGFeRD / Factur-X PDF format
+///
+/// Every command accepts several files and glob patterns, which are processed in parallel.
+/// Exit codes: 0 if all files are valid, 1 if an invoice has violations other than warnings, 2 if a file cannot be processed.
+#[derive(Parser)]
+#[command(name = "einvoice", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Validate invoices against the EN 16931 business rules
+    ///
+    /// PDF files are validated including their embedded invoice, XMP metadata and PDF/A-3 container.
+    Validate {
+        /// XML or PDF files or glob patterns
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+    /// Extract the invoice XML embedded in ZUGFeRD / Factur-X PDF files
+    Extract {
+        /// PDF files or glob patterns
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Output directory, `-` for standard output, defaults to the directory of each file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Convert invoices between UBL and CII, dropping elements outside the model such as `ext:UBLExtensions`
+    Convert {
+        /// XML or PDF files or glob patterns
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Target syntax
+        #[arg(short, long, value_enum)]
+        to: Syntax,
+        /// Output directory, `-` for standard output, defaults to the directory of each file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Render invoices as printable HTML
+    Render {
+        /// XML or PDF files or glob patterns
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Output directory, `-` for standard output, defaults to the directory of each file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the syntax, specification and profile of invoices
+    Info {
+        /// XML or PDF files or glob patterns
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Human)]
+        format: Format,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Syntax {
+    Ubl,
+    Cii,
+}
+
+impl From<Syntax> for InvoiceSyntax {
+    fn from(syntax: Syntax) -> Self {
+        match syntax {
+            Syntax::Ubl => InvoiceSyntax::Ubl,
+            Syntax::Cii => InvoiceSyntax::Cii,
+        }
+    }
+}
+
+/// Outcome of processing a single file, ordered by severity
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    Valid = 0,
+    Invalid = 1,
+    Failed = 2,
+}
+
+/// The result of processing a single file, printed once all files are done
+struct Outcome {
+    status: Status,
+    human: String,
+    json: Value,
+}
+
+impl Outcome {
+    fn failed(path: &Path, error: &str) -> Self {
+        Outcome {
+            status: Status::Failed,
+            human: format!("{}: error: {}", path.display(), error),
+            json: json!({ "file": path.display().to_string(), "error": error }),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let (files, format) = match &cli.command {
+        Command::Validate { files, format } | Command::Info { files, format } => (files, *format),
+        Command::Extract { files, .. }
+        | Command::Convert { files, .. }
+        | Command::Render { files, .. } => (files, Format::Human),
+    };
+
+    let (paths, mut outcomes) = expand(files);
+    let processed = paths
+        .par_iter()
+        .map(|path| {
+            let result = match &cli.command {
+                Command::Validate { .. } => validate(path),
+                Command::Extract { output, .. } => extract(path, output.as_deref()),
+                Command::Convert { to, output, .. } => convert(path, *to, output.as_deref()),
+                Command::Render { output, .. } => render(path, output.as_deref()),
+                Command::Info { .. } => info(path),
+            };
+            result.unwrap_or_else(|e| Outcome::failed(path, &e))
+        })
+        .collect::<Vec<_>>();
+    outcomes.extend(processed);
+
+    match format {
+        Format::Human => {
+            for outcome in &outcomes {
+                if !outcome.human.is_empty() {
+                    println!("{}", outcome.human);
+                }
+            }
+        }
+        Format::Json => {
+            let json = outcomes
+                .iter()
+                .map(|outcome| outcome.json.clone())
+                .collect::<Vec<_>>();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).unwrap_or_default()
+            );
+        }
+    }
+    let status = outcomes
+        .iter()
+        .map(|outcome| outcome.status)
+        .max()
+        .unwrap_or(Status::Valid);
+    ExitCode::from(status as u8)
+}
+
+/// Expand glob patterns, keeping plain paths as they are so a missing file is reported
+fn expand(patterns: &[String]) -> (Vec<PathBuf>, Vec<Outcome>) {
+    let mut paths = Vec::new();
+    let mut failures = Vec::new();
+    for pattern in patterns {
+        if Path::new(pattern).exists() {
+            paths.push(PathBuf::from(pattern));
+            continue;
+        }
+        match glob::glob(pattern) {
+            Ok(matches) => {
+                let before = paths.len();
+                for entry in matches {
+                    match entry {
+                        Ok(path) if path.is_file() => paths.push(path),
+                        Ok(_) => {}
+                        Err(e) => failures.push(Outcome::failed(e.path(), &e.to_string())),
+                    }
+                }
+                if paths.len() == before {
+                    failures.push(Outcome::failed(
+                        Path::new(pattern),
+                        "No such file or no file matches the pattern",
+                    ));
+                }
+            }
+            Err(e) => failures.push(Outcome::failed(Path::new(pattern), &e.to_string())),
+        }
+    }
+    (paths, failures)
+}
+
+/// The invoice XML of a file, extracted first if the file is a PDF
+struct Input {
+    xml: String,
+    embedded: Option<EmbeddedInvoiceXml>,
+}
+
+fn read_input(path: &Path) -> Result<Input, String> {
+    let content = std::fs::read(path).map_err(|e| e.to_string())?;
+    if is_pdf(&content) {
+        let embedded = extract_zugferd_xml_bytes(&content).map_err(|e| e.to_string())?;
+        Ok(Input {
+            xml: embedded.xml.clone(),
+            embedded: Some(embedded),
+        })
+    } else {
+        let xml = decode_xml(&content).map_err(|e| e.to_string())?;
+        Ok(Input {
+            xml,
+            embedded: None,
+        })
+    }
+}
+
+fn validate(path: &Path) -> Result<Outcome, String> {
+    let content = std::fs::read(path).map_err(|e| e.to_string())?;
+    let report = if is_pdf(&content) {
+        validate_zugferd_pdf_bytes_report(&content)
+    } else {
+        validate_invoice_bytes_report(&content)
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(report_outcome(path, &report))
+}
+
+fn report_outcome(path: &Path, report: &ValidationReport) -> Outcome {
+    let mut human = format!("{}: ", path.display());
+    if report.is_valid() {
+        human.push_str("valid");
+        if !report.violations.is_empty() {
+            human.push_str(&format!(", {} warning(s)", report.violations.len()));
+        }
+    } else {
+        human.push_str(&format!("{} violation(s)", report.violations.len()));
+    }
+    if let Some(file_name) = &report.file_name {
+        human.push_str(&format!(" ({})", file_name));
+    }
+    for violation in &report.violations {
+        let (severity, rule_id, text) = violation_parts(violation);
+        human.push_str(&format!("\n  {:<8} {:<12} {}", severity, rule_id, text));
+        if let Some(location) = violation_location(violation) {
+            human.push_str(&format!(
+                "\n  {:<21} at {} (line {}, column {})",
+                "", location.path, location.line, location.column
+            ));
+        }
+    }
+    let mut json = report_json(report);
+    json["file"] = json!(path.display().to_string());
+    Outcome {
+        status: if report.is_valid() {
+            Status::Valid
+        } else {
+            Status::Invalid
+        },
+        human,
+        json,
+    }
+}
+
+fn extract(path: &Path, output: Option<&Path>) -> Result<Outcome, String> {
+    let content = std::fs::read(path).map_err(|e| e.to_string())?;
+    if !is_pdf(&content) {
+        return Err("Not a PDF file".to_string());
+    }
+    let embedded = extract_zugferd_xml_bytes(&content).map_err(|e| e.to_string())?;
+    let target = write_output(path, output, "xml", &embedded.xml)?;
+    Ok(written(path, target, Some(&embedded.file_name)))
+}
+
+fn convert(path: &Path, to: Syntax, output: Option<&Path>) -> Result<Outcome, String> {
+    let input = read_input(path)?;
+    let xml = convert_invoice(&input.xml, to.into()).map_err(|e| e.to_string())?;
+    let extension = match to {
+        Syntax::Ubl => "ubl.xml",
+        Syntax::Cii => "cii.xml",
+    };
+    let target = write_output(path, output, extension, &xml)?;
+    Ok(written(path, target, None))
+}
+
+fn render(path: &Path, output: Option<&Path>) -> Result<Outcome, String> {
+    let input = read_input(path)?;
+    let invoice = parse_invoice(&input.xml).map_err(|e| e.to_string())?;
+    let target = write_output(path, output, "html", &render_html(&invoice))?;
+    Ok(written(path, target, None))
+}
+
+fn info(path: &Path) -> Result<Outcome, String> {
+    let input = read_input(path)?;
+    let invoice = parse_invoice(&input.xml).map_err(|e| e.to_string())?;
+    let syntax = match invoice.syntax() {
+        InvoiceSyntax::Ubl => "UBL",
+        InvoiceSyntax::Cii => "CII",
+    };
+    let profile = invoice
+        .zugferd_profile()
+        .map(|profile| profile.conformance_level());
+    let specification = invoice.specification();
+    let specification = specification
+        .as_ref()
+        .map(|specification| specification.name.as_str());
+    let metadata = input
+        .embedded
+        .as_ref()
+        .and_then(|embedded| embedded.metadata.as_ref());
+
+    let mut human = format!("{}\n  Syntax: {}", path.display(), syntax);
+    let mut line = |label: &str, value: Option<&str>| {
+        if let Some(value) = value {
+            human.push_str(&format!("\n  {}: {}", label, value));
+        }
+    };
+    line("Invoice number", invoice.invoice_number());
+    line(
+        "Specification identifier",
+        invoice.specification_identifier(),
+    );
+    line("Specification", specification);
+    line("Business process", invoice.business_process());
+    line("Profile", profile);
+    line(
+        "Embedded file",
+        input
+            .embedded
+            .as_ref()
+            .map(|embedded| embedded.file_name.as_str()),
+    );
+    line(
+        "Declared conformance level",
+        metadata.and_then(|metadata| metadata.conformance_level.as_deref()),
+    );
+    Ok(Outcome {
+        status: Status::Valid,
+        human,
+        json: json!({
+            "file": path.display().to_string(),
+            "syntax": syntax,
+            "invoice_number": invoice.invoice_number(),
+            "specification_identifier": invoice.specification_identifier(),
+            "specification": specification,
+            "business_process": invoice.business_process(),
+            "profile": profile,
+            "embedded_file": input.embedded.as_ref().map(|embedded| &embedded.file_name),
+            "declared_conformance_level": metadata
+                .and_then(|metadata| metadata.conformance_level.as_deref()),
+        }),
+    })
+}
+
+/// Write the output of a file, returning where it was written or `None` for standard output
+fn write_output(
+    path: &Path,
+    output: Option<&Path>,
+    extension: &str,
+    content: &str,
+) -> Result<Option<PathBuf>, String> {
+    if output == Some(Path::new("-")) {
+        println!("{}", content);
+        return Ok(None);
+    }
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "invoice".to_string());
+    let directory = output
+        .map(Path::to_path_buf)
+        .or_else(|| path.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+    let mut target = directory.join(format!("{}.{}", stem, extension));
+    if target == path {
+        target = directory.join(format!("{}.converted.{}", stem, extension));
+    }
+    std::fs::write(&target, content).map_err(|e| e.to_string())?;
+    Ok(Some(target))
+}
+
+fn written(path: &Path, target: Option<PathBuf>, source: Option<&str>) -> Outcome {
+    let target = target.map(|target| target.display().to_string());
+    Outcome {
+        status: Status::Valid,
+        human: target
+            .as_ref()
+            .map(|target| match source {
+                Some(source) => format!("{}: {} -> {}", path.display(), source, target),
+                None => format!("{} -> {}", path.display(), target),
+            })
+            .unwrap_or_default(),
+        json: json!({ "file": path.display().to_string(), "output": target }),
+    }
+}
@@ -0,0 +1,345 @@
+mod common;
+
+use clap::Parser;
+use common::{is_pdf, report_json};
+use einvoice::{
+    convert_invoice, decode_xml, extract_zugferd_xml_bytes, parse_invoice, render_html,
+    validate_invoice_bytes_report, validate_zugferd_pdf_bytes_report, validation_report_to_svrl,
+    InvoiceSyntax, ValidationReport,
+};
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// HTTP service validating, converting and rendering EN 16931 e-invoices
+///
+/// Invoices are posted as UBL or CII XML or as ZUGFeRD / Factur-X PDF.
+/// Like the KoSIT validator daemon, `POST /validate` answers 200 for valid invoices,
+/// 406 for invoices with violations other than warnings and 422 for documents that cannot be processed.
+/// `POST /convert` drops elements outside the model, like `convert_invoice`.
+#[derive(Parser)]
+#[command(name = "einvoice-server", version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+    /// Port to listen on, 0 for a port chosen by the operating system
+    #[arg(short, long, default_value_t = 8080)]
+    port: u16,
+    /// Number of requests handled in parallel, defaults to the number of CPUs
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Largest accepted request body in bytes
+    #[arg(long, default_value_t = 50 * 1024 * 1024)]
+    max_body_size: u64,
+}
+
+/// Counters exposed by `GET /metrics` in the Prometheus text format
+struct Metrics {
+    started: Instant,
+    requests: Mutex<BTreeMap<(String, u16), u64>>,
+    valid: AtomicU64,
+    invalid: AtomicU64,
+    failed: AtomicU64,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Metrics {
+            started: Instant::now(),
+            requests: Mutex::new(BTreeMap::new()),
+            valid: AtomicU64::new(0),
+            invalid: AtomicU64::new(0),
+            failed: AtomicU64::new(0),
+        }
+    }
+
+    fn count_request(&self, endpoint: &str, status: u16) {
+        if let Ok(mut requests) = self.requests.lock() {
+            *requests.entry((endpoint.to_string(), status)).or_default() += 1;
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut text = String::new();
+        text.push_str("# HELP einvoice_requests_total HTTP requests by endpoint and status\n");
+        text.push_str("# TYPE einvoice_requests_total counter\n");
+        if let Ok(requests) = self.requests.lock() {
+            for ((endpoint, status), count) in requests.iter() {
+                text.push_str(&format!(
+                    "einvoice_requests_total{{endpoint=\"{}\",status=\"{}\"}} {}\n",
+                    endpoint, status, count
+                ));
+            }
+        }
+        text.push_str("# HELP einvoice_validations_total Validated documents by result\n");
+        text.push_str("# TYPE einvoice_validations_total counter\n");
+        for (result, counter) in [
+            ("valid", &self.valid),
+            ("invalid", &self.invalid),
+            ("error", &self.failed),
+        ] {
+            text.push_str(&format!(
+                "einvoice_validations_total{{result=\"{}\"}} {}\n",
+                result,
+                counter.load(Ordering::Relaxed)
+            ));
+        }
+        text.push_str("# HELP einvoice_uptime_seconds Seconds since the server started\n");
+        text.push_str("# TYPE einvoice_uptime_seconds gauge\n");
+        text.push_str(&format!(
+            "einvoice_uptime_seconds {}\n",
+            self.started.elapsed().as_secs()
+        ));
+        text
+    }
+}
+
+/// A response before it is sent, kept simple so the status can be counted
+struct Reply {
+    status: u16,
+    content_type: &'static str,
+    body: Vec<u8>,
+    file_name: Option<String>,
+}
+
+impl Reply {
+    fn new(status: u16, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Reply {
+            status,
+            content_type,
+            body: body.into(),
+            file_name: None,
+        }
+    }
+
+    fn json(status: u16, value: serde_json::Value) -> Self {
+        Reply::new(status, "application/json", value.to_string())
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Reply::json(status, json!({ "error": message }))
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    let address = format!("{}:{}", args.host, args.port);
+    let server = match Server::http(&address) {
+        Ok(server) => Arc::new(server),
+        Err(e) => {
+            eprintln!("Failed to listen on {}: {}", address, e);
+            std::process::exit(1);
+        }
+    };
+    let metrics = Arc::new(Metrics::new());
+    let threads = args.threads.unwrap_or_else(|| {
+        std::thread::available_parallelism()
+            .map(usize::from)
+            .unwrap_or(4)
+    });
+    eprintln!(
+        "Listening on http://{} with {} threads",
+        server.server_addr(),
+        threads
+    );
+
+    let workers = (0..threads.max(1))
+        .map(|_| {
+            let server = Arc::clone(&server);
+            let metrics = Arc::clone(&metrics);
+            let max_body_size = args.max_body_size;
+            std::thread::spawn(move || {
+                while let Ok(request) = server.recv() {
+                    handle(request, &metrics, max_body_size);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for worker in workers {
+        let _ = worker.join();
+    }
+}
+
+fn handle(mut request: Request, metrics: &Metrics, max_body_size: u64) {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let endpoint = path.trim_matches('/').to_string();
+    let parameters = query
+        .split('&')
+        .filter_map(|parameter| parameter.split_once('='))
+        .collect::<BTreeMap<_, _>>();
+    let svrl = match parameters.get("format") {
+        Some(format) => *format == "svrl",
+        None => header(&request, "Accept").is_some_and(|accept| accept.contains("xml")),
+    };
+
+    let reply = match (request.method(), endpoint.as_str()) {
+        (Method::Get, "health") => Reply::json(
+            200,
+            json!({ "status": "UP", "version": env!("CARGO_PKG_VERSION") }),
+        ),
+        (Method::Get, "metrics") => Reply::new(200, "text/plain; version=0.0.4", metrics.render()),
+        (Method::Post, "validate" | "convert" | "extract" | "render") => {
+            match read_body(&mut request, max_body_size) {
+                Err(reply) => reply,
+                Ok(body) => match endpoint.as_str() {
+                    "validate" => validate(&body, svrl, metrics),
+                    "convert" => convert(&body, parameters.get("to").copied()),
+                    "extract" => extract(&body),
+                    _ => render(&body),
+                },
+            }
+        }
+        (_, "health" | "metrics" | "validate" | "convert" | "extract" | "render") => {
+            Reply::error(405, "Method not allowed")
+        }
+        _ => Reply::error(404, "Not found"),
+    };
+
+    // Unknown paths share one label to keep the number of metric series bounded
+    let label = if reply.status == 404 {
+        "unknown"
+    } else {
+        &endpoint
+    };
+    metrics.count_request(label, reply.status);
+    let mut response = Response::from_data(reply.body)
+        .with_status_code(reply.status)
+        .with_header(content_header("Content-Type", reply.content_type));
+    if let Some(file_name) = reply.file_name {
+        response = response.with_header(content_header(
+            "Content-Disposition",
+            &format!(
+                "inline; filename=\"{}\"",
+                file_name
+                    .chars()
+                    .filter(|c| c.is_ascii_graphic() && *c != '"')
+                    .collect::<String>()
+            ),
+        ));
+    }
+    if let Err(e) = request.respond(response) {
+        eprintln!("Failed to respond to {}: {}", url, e);
+    }
+}
+
+fn header<'a>(request: &'a Request, name: &'static str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str())
+}
+
+fn content_header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("header is valid ASCII")
+}
+
+fn read_body(request: &mut Request, max_body_size: u64) -> Result<Vec<u8>, Reply> {
+    if request
+        .body_length()
+        .is_some_and(|length| length as u64 > max_body_size)
+    {
+        return Err(Reply::error(413, "Request body too large"));
+    }
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max_body_size + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| Reply::error(400, &e.to_string()))?;
+    if body.len() as u64 > max_body_size {
+        return Err(Reply::error(413, "Request body too large"));
+    }
+    if body.is_empty() {
+        return Err(Reply::error(400, "Request body is empty"));
+    }
+    Ok(body)
+}
+
+/// The invoice XML of a request body, extracted first if the body is a PDF
+fn invoice_xml(body: &[u8]) -> Result<String, Reply> {
+    if is_pdf(body) {
+        extract_zugferd_xml_bytes(body)
+            .map(|embedded| embedded.xml)
+            .map_err(|e| Reply::error(422, &e.to_string()))
+    } else {
+        decode_xml(body).map_err(|e| Reply::error(422, &e.to_string()))
+    }
+}
+
+fn validate(body: &[u8], svrl: bool, metrics: &Metrics) -> Reply {
+    let result = if is_pdf(body) {
+        validate_zugferd_pdf_bytes_report(body).map_err(|e| e.to_string())
+    } else {
+        validate_invoice_bytes_report(body).map_err(|e| e.to_string())
+    };
+    let report = match result {
+        Ok(report) => report,
+        Err(message) => {
+            metrics.failed.fetch_add(1, Ordering::Relaxed);
+            return Reply::error(422, &message);
+        }
+    };
+    let status = if report.is_valid() {
+        metrics.valid.fetch_add(1, Ordering::Relaxed);
+        200
+    } else {
+        metrics.invalid.fetch_add(1, Ordering::Relaxed);
+        406
+    };
+    report_reply(status, &report, svrl)
+}
+
+fn report_reply(status: u16, report: &ValidationReport, svrl: bool) -> Reply {
+    if svrl {
+        Reply::new(status, "application/xml", validation_report_to_svrl(report))
+    } else {
+        Reply::json(status, report_json(report))
+    }
+}
+
+fn convert(body: &[u8], to: Option<&str>) -> Reply {
+    let syntax = match to.map(str::to_lowercase).as_deref() {
+        Some("ubl") => InvoiceSyntax::Ubl,
+        Some("cii") => InvoiceSyntax::Cii,
+        _ => return Reply::error(400, "Query parameter `to` must be `ubl` or `cii`"),
+    };
+    let xml = match invoice_xml(body) {
+        Ok(xml) => xml,
+        Err(reply) => return reply,
+    };
+    match convert_invoice(&xml, syntax) {
+        Ok(converted) => Reply::new(200, "application/xml", converted),
+        Err(e) => Reply::error(422, &e.to_string()),
+    }
+}
+
+fn extract(body: &[u8]) -> Reply {
+    if !is_pdf(body) {
+        return Reply::error(422, "Not a PDF file");
+    }
+    match extract_zugferd_xml_bytes(body) {
+        Ok(embedded) => Reply {
+            file_name: Some(embedded.file_name),
+            ..Reply::new(200, "application/xml", embedded.xml)
+        },
+        Err(e) => Reply::error(422, &e.to_string()),
+    }
+}
+
+fn render(body: &[u8]) -> Reply {
+    let xml = match invoice_xml(body) {
+        Ok(xml) => xml,
+        Err(reply) => return reply,
+    };
+    match parse_invoice(&xml) {
+        Ok(invoice) => Reply::new(200, "text/html; charset=utf-8", render_html(&invoice)),
+        Err(e) => Reply::error(422, &e.to_string()),
+    }
+}
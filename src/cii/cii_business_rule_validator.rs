@@ -1,23 +1,35 @@
-use crate::{BusinessRuleViolation, CrossIndustryInvoice, ValidationError, ZugferdProfile};
+use crate::attachment::attachment_reader::ALLOWED_MIME_CODES;
+use crate::lines::line_tree::cii_line_in_totals;
+use crate::{
+    BusinessRuleViolation, CrossIndustryInvoice, ValidationConfig, ValidationError, ZugferdProfile,
+};
 use rayon::iter::IntoParallelRefIterator;
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+use rust_decimal::{Decimal, RoundingStrategy};
+use std::str::FromStr;
 use std::sync::Arc;
 
 pub fn validate_invoice(
     zugferd_profile: ZugferdProfile,
     invoice: Arc<&CrossIndustryInvoice>,
+    config: &ValidationConfig,
 ) -> Result<(), Vec<ValidationError>> {
-    let validation_rules = match zugferd_profile {
-        ZugferdProfile::Basic => BASIC_BUSINESS_RULES,
-        ZugferdProfile::En16931 => EN16931_BUSINESS_RULES,
-        ZugferdProfile::Extended => &*vec![],
+    let (validation_rules, rule_ids) = match zugferd_profile {
+        ZugferdProfile::Minimum => (MINIMUM_BUSINESS_RULES, MINIMUM_BUSINESS_RULE_IDS),
+        ZugferdProfile::BasicWl => (BASIC_WL_BUSINESS_RULES, BASIC_WL_BUSINESS_RULE_IDS),
+        ZugferdProfile::Basic => (BASIC_BUSINESS_RULES, BASIC_BUSINESS_RULE_IDS),
+        ZugferdProfile::En16931 => (EN16931_BUSINESS_RULES, EN16931_BUSINESS_RULE_IDS),
+        ZugferdProfile::Extended => (EXTENDED_BUSINESS_RULES, EXTENDED_BUSINESS_RULE_IDS),
     };
 
     let results: Vec<_> = validation_rules
         .par_iter()
-        .map(|rule| rule(&invoice))
+        .zip(rule_ids)
+        .filter(|(_, rule_id)| config.is_enabled(rule_id))
+        .map(|(rule, _)| rule(&invoice))
         .filter_map(Result::err) // Collect only errors
         .collect();
+    let results = config.apply(results);
 
     if results.is_empty() {
         Ok(())
@@ -28,6 +40,189 @@ pub fn validate_invoice(
 
 type BusinessRule = fn(&CrossIndustryInvoice) -> Result<(), ValidationError>;
 
+include!(concat!(env!("OUT_DIR"), "/cii_rule_ids.rs"));
+
+/// The value of an amount, `None` if it is not a decimal
+fn decimal(amount: &str) -> Option<Decimal> {
+    Decimal::from_str(amount.trim()).ok()
+}
+
+/// The rules of the MINIMUM profile, which only carries the document totals and the parties
+pub const MINIMUM_BUSINESS_RULES: &[BusinessRule] = &[
+    validate_br_co_26,
+    validate_br_co_09,
+    validate_br_13,
+    validate_br_14,
+    validate_br_15,
+    validate_br_co_15,
+    validate_br_co_16,
+    validate_br_dec_12,
+    validate_br_dec_13,
+    validate_br_dec_14,
+    validate_br_dec_18,
+    validate_br_01,
+    validate_br_02,
+    validate_br_03,
+    validate_br_04,
+    validate_br_05,
+    validate_br_06,
+    validate_br_07,
+    validate_br_08,
+    validate_br_09,
+];
+
+/// The rules of the BASIC WL profile, which has no invoice lines
+pub const BASIC_WL_BUSINESS_RULES: &[BusinessRule] = &[
+    validate_br_45,
+    validate_br_46,
+    validate_br_47,
+    validate_br_48,
+    validate_br_co_03,
+    validate_br_co_17,
+    validate_br_dec_19,
+    validate_br_dec_20,
+    validate_br_z_09,
+    validate_br_z_10,
+    validate_br_s_09,
+    validate_br_s_10,
+    validate_br_29,
+    validate_br_co_19,
+    validate_br_31,
+    validate_br_32,
+    validate_br_33,
+    validate_br_co_05,
+    validate_br_co_21,
+    validate_br_dec_01,
+    validate_br_dec_02,
+    validate_br_36,
+    validate_br_37,
+    validate_br_38,
+    validate_br_co_06,
+    validate_br_co_22,
+    validate_br_dec_05,
+    validate_br_dec_06,
+    validate_br_17,
+    validate_br_18,
+    validate_br_19,
+    validate_br_20,
+    validate_br_56,
+    validate_br_co_26,
+    validate_br_co_09,
+    validate_br_66,
+    validate_br_ae_03,
+    validate_br_ae_06,
+    validate_br_e_03,
+    validate_br_e_06,
+    validate_br_g_03,
+    validate_br_g_06,
+    validate_br_ic_03,
+    validate_br_ic_06,
+    validate_br_af_03,
+    validate_br_af_06,
+    validate_br_ag_03,
+    validate_br_ag_06,
+    validate_br_o_03,
+    validate_br_o_06,
+    validate_br_s_03,
+    validate_br_s_06,
+    validate_br_z_03,
+    validate_br_z_06,
+    validate_br_ae_04,
+    validate_br_ae_07,
+    validate_br_e_04,
+    validate_br_e_07,
+    validate_br_g_04,
+    validate_br_g_07,
+    validate_br_ic_04,
+    validate_br_ic_07,
+    validate_br_af_04,
+    validate_br_af_07,
+    validate_br_ag_04,
+    validate_br_ag_07,
+    validate_br_o_04,
+    validate_br_o_07,
+    validate_br_s_04,
+    validate_br_s_07,
+    validate_br_z_04,
+    validate_br_z_07,
+    validate_br_12,
+    validate_br_13,
+    validate_br_14,
+    validate_br_15,
+    validate_br_co_11,
+    validate_br_co_12,
+    validate_br_co_13,
+    validate_br_co_15,
+    validate_br_co_16,
+    validate_br_dec_09,
+    validate_br_dec_10,
+    validate_br_dec_11,
+    validate_br_dec_12,
+    validate_br_dec_13,
+    validate_br_dec_14,
+    validate_br_dec_15,
+    validate_br_dec_16,
+    validate_br_dec_17,
+    validate_br_dec_18,
+    validate_br_53,
+    validate_br_co_14,
+    validate_br_49,
+    validate_br_co_27,
+    validate_br_61,
+    validate_br_50,
+    validate_br_co_18,
+    validate_br_ae_09,
+    validate_br_ae_10,
+    validate_br_e_09,
+    validate_br_e_10,
+    validate_br_g_09,
+    validate_br_g_10,
+    validate_br_ic_09,
+    validate_br_ic_10,
+    validate_br_ic_11,
+    validate_br_ic_12,
+    validate_br_af_09,
+    validate_br_af_10,
+    validate_br_ag_09,
+    validate_br_ag_10,
+    validate_br_o_09,
+    validate_br_o_10,
+    validate_br_o_11,
+    validate_br_o_12,
+    validate_br_o_13,
+    validate_br_o_14,
+    validate_br_co_25,
+    validate_br_01,
+    validate_br_02,
+    validate_br_03,
+    validate_br_04,
+    validate_br_05,
+    validate_br_06,
+    validate_br_07,
+    validate_br_08,
+    validate_br_09,
+    validate_br_10,
+    validate_br_11,
+    validate_br_62,
+    validate_br_63,
+    validate_br_s_01,
+    validate_br_z_01,
+    validate_br_e_01,
+    validate_br_ae_01,
+    validate_br_ic_01,
+    validate_br_g_01,
+    validate_br_o_01,
+    validate_br_af_01,
+    validate_br_ag_01,
+    validate_br_b_01,
+    validate_br_b_02,
+    validate_br_57,
+    validate_br_55,
+];
+
+/// The rules of the EXTENDED profile, which allows sub-invoice lines
+pub const EXTENDED_BUSINESS_RULES: &[BusinessRule] = &[validate_br_co_10];
+
 pub const BASIC_BUSINESS_RULES: &[BusinessRule] = &[
     validate_br_45,
     validate_br_46,
@@ -1324,8 +1519,45 @@ fn validate_br_53(_invoice: &CrossIndustryInvoice) -> Result<(), ValidationError
 
 // Context: //ram:SpecifiedTradeSettlementHeaderMonetarySummation
 // Test: xs:decimal(ram:LineTotalAmount) = round(xs:decimal(sum(../../ram:IncludedSupplyChainTradeLineItem/ram:SpecifiedLineTradeSettlement/ram:SpecifiedTradeSettlementLineMonetarySummation/ram:LineTotalAmount)) * xs:decimal(100)) div xs:decimal(100)
-fn validate_br_co_10(_invoice: &CrossIndustryInvoice) -> Result<(), ValidationError> {
-    if false {
+fn validate_br_co_10(invoice: &CrossIndustryInvoice) -> Result<(), ValidationError> {
+    // Sub-invoice lines and information lines are left out of the sum, see `cii_line_in_totals`
+    let Some(transaction) = invoice.rsm_supply_chain_trade_transaction.as_ref() else {
+        return Ok(());
+    };
+    let Some(line_total) = transaction
+        .ram_applicable_header_trade_settlement
+        .as_ref()
+        .and_then(|settlement| {
+            settlement
+                .ram_specified_trade_settlement_header_monetary_summation
+                .as_ref()
+        })
+        .and_then(|summation| summation.ram_line_total_amount.as_deref())
+    else {
+        return Ok(());
+    };
+    // Missing line net amounts are reported by BR-24, amounts that are not decimals break the sum
+    let sum = transaction
+        .ram_included_supply_chain_trade_line_item
+        .iter()
+        .filter(|line| cii_line_in_totals(line))
+        .filter_map(|line| {
+            line.ram_specified_line_trade_settlement
+                .as_ref()?
+                .ram_specified_trade_settlement_line_monetary_summation
+                .as_ref()?
+                .ram_line_total_amount
+                .as_deref()
+        })
+        .map(decimal)
+        .sum::<Option<Decimal>>();
+    let matches = match (decimal(line_total), sum) {
+        (Some(line_total), Some(sum)) => {
+            line_total == sum.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+        }
+        _ => false,
+    };
+    if !matches {
         return Err(ValidationError::Fatal(BusinessRuleViolation::new(
             "BR-CO-10",
             "[BR-CO-10]-Sum of Invoice line net amount (BT-106) = Σ Invoice line net amount (BT-131).",
@@ -10301,12 +10533,30 @@ fn validate_br_cl_23(_invoice: &CrossIndustryInvoice) -> Result<(), ValidationEr
 
 // Context: ram:AttachmentBinaryObject[@mimeCode]
 // Test: ((@mimeCode = 'application/pdf' or @mimeCode  = 'image/png' or @mimeCode  = 'image/jpeg' or @mimeCode  = 'text/csv' or @mimeCode  = 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet' or @mimeCode  = 'application/vnd.oasis.opendocument.spreadsheet'))
-fn validate_br_cl_24(_invoice: &CrossIndustryInvoice) -> Result<(), ValidationError> {
-    if false {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-CL-24",
-            "[BR-CL-24]-For Mime code in attribute use MIMEMediaType.",
-        )));
+fn validate_br_cl_24(invoice: &CrossIndustryInvoice) -> Result<(), ValidationError> {
+    if let Some(index) = invoice
+        .rsm_supply_chain_trade_transaction
+        .iter()
+        .filter_map(|transaction| transaction.ram_applicable_header_trade_agreement.as_ref())
+        .flat_map(|agreement| agreement.ram_additional_referenced_document.iter())
+        .position(|document| {
+            document
+                .ram_attachment_binary_object
+                .as_ref()
+                .and_then(|object| object.mime_code.as_deref())
+                .is_some_and(|mime_code| !ALLOWED_MIME_CODES.contains(&mime_code))
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-CL-24",
+                "[BR-CL-24]-For Mime code in attribute use MIMEMediaType.",
+            )
+            .with_element(format!(
+                "rsm:SupplyChainTradeTransaction/ram:ApplicableHeaderTradeAgreement/ram:AdditionalReferencedDocument[{}]/ram:AttachmentBinaryObject",
+                index + 1
+            )),
+        ));
     }
     Ok(())
 }
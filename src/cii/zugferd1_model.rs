@@ -0,0 +1,791 @@
+use einvoice_deps_yaserde_derive::{YaDeserialize, YaSerialize};
+
+/// The namespace of the root element of ZUGFeRD 1.0 invoices
+pub const ZUGFERD1_NAMESPACE: &str = "urn:ferd:CrossIndustryDocument:invoice:1p0";
+
+/// A ZUGFeRD 1.0 invoice, the UN/CEFACT Cross Industry Invoice D13B based predecessor of `CrossIndustryInvoice`
+///
+/// Only the elements of the BASIC, COMFORT and EXTENDED profiles that have an EN 16931 counterpart are modelled,
+/// use `zugferd1_to_cii` to validate or render the invoice.
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+default_namespace = "",
+prefix = "rsm",
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct CrossIndustryDocument {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "SpecifiedExchangedDocumentContext", prefix = "rsm")]
+    pub rsm_specified_exchanged_document_context: Option<Zugferd1ExchangedDocumentContext>,
+    #[yaserde(rename = "HeaderExchangedDocument", prefix = "rsm")]
+    pub rsm_header_exchanged_document: Option<Zugferd1HeaderExchangedDocument>,
+    #[yaserde(rename = "SpecifiedSupplyChainTradeTransaction", prefix = "rsm")]
+    pub rsm_specified_supply_chain_trade_transaction: Option<Zugferd1SupplyChainTradeTransaction>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1ExchangedDocumentContext {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "TestIndicator", prefix = "ram")]
+    pub ram_test_indicator: Option<Zugferd1Indicator>,
+    #[yaserde(
+        rename = "BusinessProcessSpecifiedDocumentContextParameter",
+        prefix = "ram"
+    )]
+    pub ram_business_process_specified_document_context_parameter:
+        Option<Zugferd1DocumentContextParameter>,
+    #[yaserde(rename = "GuidelineSpecifiedDocumentContextParameter", prefix = "ram")]
+    pub ram_guideline_specified_document_context_parameter:
+        Option<Zugferd1DocumentContextParameter>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1DocumentContextParameter {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1Indicator {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "Indicator", prefix = "udt")]
+    pub udt_indicator: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1HeaderExchangedDocument {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Option<String>,
+    #[yaserde(rename = "Name", prefix = "ram")]
+    pub ram_name: Vec<String>,
+    #[yaserde(rename = "TypeCode", prefix = "ram")]
+    pub ram_type_code: Option<String>,
+    #[yaserde(rename = "IssueDateTime", prefix = "ram")]
+    pub ram_issue_date_time: Option<Zugferd1DateTime>,
+    #[yaserde(rename = "IncludedNote", prefix = "ram")]
+    pub ram_included_note: Vec<Zugferd1Note>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1DateTime {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "DateTimeString", prefix = "udt")]
+    pub udt_date_time_string: Option<Zugferd1DateTimeString>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1DateTimeString {
+    #[yaserde(attribute = true)]
+    pub format: Option<String>,
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1Note {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "Content", prefix = "ram")]
+    pub ram_content: Vec<String>,
+    #[yaserde(rename = "SubjectCode", prefix = "ram")]
+    pub ram_subject_code: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1SupplyChainTradeTransaction {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ApplicableSupplyChainTradeAgreement", prefix = "ram")]
+    pub ram_applicable_supply_chain_trade_agreement: Option<Zugferd1TradeAgreement>,
+    #[yaserde(rename = "ApplicableSupplyChainTradeDelivery", prefix = "ram")]
+    pub ram_applicable_supply_chain_trade_delivery: Option<Zugferd1TradeDelivery>,
+    #[yaserde(rename = "ApplicableSupplyChainTradeSettlement", prefix = "ram")]
+    pub ram_applicable_supply_chain_trade_settlement: Option<Zugferd1TradeSettlement>,
+    #[yaserde(rename = "IncludedSupplyChainTradeLineItem", prefix = "ram")]
+    pub ram_included_supply_chain_trade_line_item: Vec<Zugferd1LineItem>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeAgreement {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "BuyerReference", prefix = "ram")]
+    pub ram_buyer_reference: Vec<String>,
+    #[yaserde(rename = "SellerTradeParty", prefix = "ram")]
+    pub ram_seller_trade_party: Option<Zugferd1TradeParty>,
+    #[yaserde(rename = "BuyerTradeParty", prefix = "ram")]
+    pub ram_buyer_trade_party: Option<Zugferd1TradeParty>,
+    #[yaserde(rename = "BuyerOrderReferencedDocument", prefix = "ram")]
+    pub ram_buyer_order_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "ContractReferencedDocument", prefix = "ram")]
+    pub ram_contract_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "AdditionalReferencedDocument", prefix = "ram")]
+    pub ram_additional_referenced_document: Vec<Zugferd1ReferencedDocument>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeParty {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Vec<String>,
+    #[yaserde(rename = "GlobalID", prefix = "ram")]
+    pub ram_global_id: Vec<Zugferd1SchemeId>,
+    #[yaserde(rename = "Name", prefix = "ram")]
+    pub ram_name: Option<String>,
+    #[yaserde(rename = "DefinedTradeContact", prefix = "ram")]
+    pub ram_defined_trade_contact: Option<Zugferd1TradeContact>,
+    #[yaserde(rename = "PostalTradeAddress", prefix = "ram")]
+    pub ram_postal_trade_address: Option<Zugferd1TradeAddress>,
+    #[yaserde(rename = "SpecifiedTaxRegistration", prefix = "ram")]
+    pub ram_specified_tax_registration: Vec<Zugferd1TaxRegistration>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1SchemeId {
+    #[yaserde(rename = "schemeID", attribute = true)]
+    pub scheme_id: Option<String>,
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeContact {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "PersonName", prefix = "ram")]
+    pub ram_person_name: Option<String>,
+    #[yaserde(rename = "DepartmentName", prefix = "ram")]
+    pub ram_department_name: Option<String>,
+    #[yaserde(rename = "TelephoneUniversalCommunication", prefix = "ram")]
+    pub ram_telephone_universal_communication: Option<Zugferd1UniversalCommunication>,
+    #[yaserde(rename = "FaxUniversalCommunication", prefix = "ram")]
+    pub ram_fax_universal_communication: Option<Zugferd1UniversalCommunication>,
+    #[yaserde(rename = "EmailURIUniversalCommunication", prefix = "ram")]
+    pub ram_email_uriuniversal_communication: Option<Zugferd1UniversalCommunication>,
+}
+
+/// A telephone number or an email address
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1UniversalCommunication {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "URIID", prefix = "ram")]
+    pub ram_uriid: Option<String>,
+    #[yaserde(rename = "CompleteNumber", prefix = "ram")]
+    pub ram_complete_number: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeAddress {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "PostcodeCode", prefix = "ram")]
+    pub ram_postcode_code: Option<String>,
+    #[yaserde(rename = "LineOne", prefix = "ram")]
+    pub ram_line_one: Option<String>,
+    #[yaserde(rename = "LineTwo", prefix = "ram")]
+    pub ram_line_two: Option<String>,
+    #[yaserde(rename = "CityName", prefix = "ram")]
+    pub ram_city_name: Option<String>,
+    #[yaserde(rename = "CountryID", prefix = "ram")]
+    pub ram_country_id: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TaxRegistration {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Option<Zugferd1SchemeId>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1ReferencedDocument {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "IssueDateTime", prefix = "ram")]
+    pub ram_issue_date_time: Option<String>,
+    #[yaserde(rename = "LineID", prefix = "ram")]
+    pub ram_line_id: Option<String>,
+    #[yaserde(rename = "TypeCode", prefix = "ram")]
+    pub ram_type_code: Option<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Vec<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeDelivery {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ShipToTradeParty", prefix = "ram")]
+    pub ram_ship_to_trade_party: Option<Zugferd1TradeParty>,
+    #[yaserde(rename = "ActualDeliverySupplyChainEvent", prefix = "ram")]
+    pub ram_actual_delivery_supply_chain_event: Option<Zugferd1SupplyChainEvent>,
+    #[yaserde(rename = "DespatchAdviceReferencedDocument", prefix = "ram")]
+    pub ram_despatch_advice_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "ReceivingAdviceReferencedDocument", prefix = "ram")]
+    pub ram_receiving_advice_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "DeliveryNoteReferencedDocument", prefix = "ram")]
+    pub ram_delivery_note_referenced_document: Option<Zugferd1ReferencedDocument>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1SupplyChainEvent {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "OccurrenceDateTime", prefix = "ram")]
+    pub ram_occurrence_date_time: Option<Zugferd1DateTime>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeSettlement {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "PaymentReference", prefix = "ram")]
+    pub ram_payment_reference: Vec<String>,
+    #[yaserde(rename = "InvoiceCurrencyCode", prefix = "ram")]
+    pub ram_invoice_currency_code: Option<String>,
+    #[yaserde(rename = "PayeeTradeParty", prefix = "ram")]
+    pub ram_payee_trade_party: Option<Zugferd1TradeParty>,
+    #[yaserde(rename = "SpecifiedTradeSettlementPaymentMeans", prefix = "ram")]
+    pub ram_specified_trade_settlement_payment_means: Vec<Zugferd1PaymentMeans>,
+    #[yaserde(rename = "ApplicableTradeTax", prefix = "ram")]
+    pub ram_applicable_trade_tax: Vec<Zugferd1TradeTax>,
+    #[yaserde(rename = "BillingSpecifiedPeriod", prefix = "ram")]
+    pub ram_billing_specified_period: Option<Zugferd1Period>,
+    #[yaserde(rename = "SpecifiedTradeAllowanceCharge", prefix = "ram")]
+    pub ram_specified_trade_allowance_charge: Vec<Zugferd1AllowanceCharge>,
+    #[yaserde(rename = "SpecifiedLogisticsServiceCharge", prefix = "ram")]
+    pub ram_specified_logistics_service_charge: Vec<Zugferd1LogisticsServiceCharge>,
+    #[yaserde(rename = "SpecifiedTradePaymentTerms", prefix = "ram")]
+    pub ram_specified_trade_payment_terms: Vec<Zugferd1PaymentTerms>,
+    #[yaserde(rename = "SpecifiedTradeSettlementMonetarySummation", prefix = "ram")]
+    pub ram_specified_trade_settlement_monetary_summation: Option<Zugferd1MonetarySummation>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1PaymentMeans {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "TypeCode", prefix = "ram")]
+    pub ram_type_code: Option<String>,
+    #[yaserde(rename = "Information", prefix = "ram")]
+    pub ram_information: Vec<String>,
+    #[yaserde(rename = "ID", prefix = "ram")]
+    pub ram_id: Vec<String>,
+    #[yaserde(rename = "PayerPartyDebtorFinancialAccount", prefix = "ram")]
+    pub ram_payer_party_debtor_financial_account: Option<Zugferd1FinancialAccount>,
+    #[yaserde(rename = "PayeePartyCreditorFinancialAccount", prefix = "ram")]
+    pub ram_payee_party_creditor_financial_account: Option<Zugferd1FinancialAccount>,
+    #[yaserde(rename = "PayerSpecifiedDebtorFinancialInstitution", prefix = "ram")]
+    pub ram_payer_specified_debtor_financial_institution: Option<Zugferd1FinancialInstitution>,
+    #[yaserde(rename = "PayeeSpecifiedCreditorFinancialInstitution", prefix = "ram")]
+    pub ram_payee_specified_creditor_financial_institution: Option<Zugferd1FinancialInstitution>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1FinancialAccount {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "IBANID", prefix = "ram")]
+    pub ram_ibanid: Option<String>,
+    #[yaserde(rename = "AccountName", prefix = "ram")]
+    pub ram_account_name: Option<String>,
+    #[yaserde(rename = "ProprietaryID", prefix = "ram")]
+    pub ram_proprietary_id: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1FinancialInstitution {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "BICID", prefix = "ram")]
+    pub ram_bicid: Option<String>,
+    #[yaserde(rename = "GermanBankleitzahlID", prefix = "ram")]
+    pub ram_german_bankleitzahl_id: Option<String>,
+    #[yaserde(rename = "Name", prefix = "ram")]
+    pub ram_name: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeTax {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "CalculatedAmount", prefix = "ram")]
+    pub ram_calculated_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "TypeCode", prefix = "ram")]
+    pub ram_type_code: Option<String>,
+    #[yaserde(rename = "ExemptionReason", prefix = "ram")]
+    pub ram_exemption_reason: Option<String>,
+    #[yaserde(rename = "BasisAmount", prefix = "ram")]
+    pub ram_basis_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "LineTotalBasisAmount", prefix = "ram")]
+    pub ram_line_total_basis_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "AllowanceChargeBasisAmount", prefix = "ram")]
+    pub ram_allowance_charge_basis_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "CategoryCode", prefix = "ram")]
+    pub ram_category_code: Option<String>,
+    #[yaserde(rename = "ApplicablePercent", prefix = "ram")]
+    pub ram_applicable_percent: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1Amount {
+    #[yaserde(rename = "currencyID", attribute = true)]
+    pub currency_id: Option<String>,
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1Quantity {
+    #[yaserde(rename = "unitCode", attribute = true)]
+    pub unit_code: Option<String>,
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1Period {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "StartDateTime", prefix = "ram")]
+    pub ram_start_date_time: Option<Zugferd1DateTime>,
+    #[yaserde(rename = "EndDateTime", prefix = "ram")]
+    pub ram_end_date_time: Option<Zugferd1DateTime>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1AllowanceCharge {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ChargeIndicator", prefix = "ram")]
+    pub ram_charge_indicator: Option<Zugferd1Indicator>,
+    #[yaserde(rename = "SequenceNumeric", prefix = "ram")]
+    pub ram_sequence_numeric: Option<String>,
+    #[yaserde(rename = "CalculationPercent", prefix = "ram")]
+    pub ram_calculation_percent: Option<String>,
+    #[yaserde(rename = "BasisAmount", prefix = "ram")]
+    pub ram_basis_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "BasisQuantity", prefix = "ram")]
+    pub ram_basis_quantity: Option<Zugferd1Quantity>,
+    #[yaserde(rename = "ActualAmount", prefix = "ram")]
+    pub ram_actual_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "ReasonCode", prefix = "ram")]
+    pub ram_reason_code: Option<String>,
+    #[yaserde(rename = "Reason", prefix = "ram")]
+    pub ram_reason: Option<String>,
+    #[yaserde(rename = "CategoryTradeTax", prefix = "ram")]
+    pub ram_category_trade_tax: Vec<Zugferd1TradeTax>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LogisticsServiceCharge {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "Description", prefix = "ram")]
+    pub ram_description: Vec<String>,
+    #[yaserde(rename = "AppliedAmount", prefix = "ram")]
+    pub ram_applied_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "AppliedTradeTax", prefix = "ram")]
+    pub ram_applied_trade_tax: Vec<Zugferd1TradeTax>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1PaymentTerms {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "Description", prefix = "ram")]
+    pub ram_description: Vec<String>,
+    #[yaserde(rename = "DueDateDateTime", prefix = "ram")]
+    pub ram_due_date_date_time: Option<Zugferd1DateTime>,
+}
+
+/// The totals of the invoice or, with the line total only, of an invoice line
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1MonetarySummation {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "LineTotalAmount", prefix = "ram")]
+    pub ram_line_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "ChargeTotalAmount", prefix = "ram")]
+    pub ram_charge_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "AllowanceTotalAmount", prefix = "ram")]
+    pub ram_allowance_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "TaxBasisTotalAmount", prefix = "ram")]
+    pub ram_tax_basis_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "TaxTotalAmount", prefix = "ram")]
+    pub ram_tax_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "GrandTotalAmount", prefix = "ram")]
+    pub ram_grand_total_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "TotalPrepaidAmount", prefix = "ram")]
+    pub ram_total_prepaid_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "TotalAllowanceChargeAmount", prefix = "ram")]
+    pub ram_total_allowance_charge_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "DuePayableAmount", prefix = "ram")]
+    pub ram_due_payable_amount: Option<Zugferd1Amount>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LineItem {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "AssociatedDocumentLineDocument", prefix = "ram")]
+    pub ram_associated_document_line_document: Option<Zugferd1LineDocument>,
+    #[yaserde(rename = "SpecifiedSupplyChainTradeAgreement", prefix = "ram")]
+    pub ram_specified_supply_chain_trade_agreement: Option<Zugferd1LineTradeAgreement>,
+    #[yaserde(rename = "SpecifiedSupplyChainTradeDelivery", prefix = "ram")]
+    pub ram_specified_supply_chain_trade_delivery: Option<Zugferd1LineTradeDelivery>,
+    #[yaserde(rename = "SpecifiedSupplyChainTradeSettlement", prefix = "ram")]
+    pub ram_specified_supply_chain_trade_settlement: Option<Zugferd1LineTradeSettlement>,
+    #[yaserde(rename = "SpecifiedTradeProduct", prefix = "ram")]
+    pub ram_specified_trade_product: Option<Zugferd1TradeProduct>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LineDocument {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "LineID", prefix = "ram")]
+    pub ram_line_id: Option<String>,
+    #[yaserde(rename = "IncludedNote", prefix = "ram")]
+    pub ram_included_note: Vec<Zugferd1Note>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LineTradeAgreement {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "BuyerOrderReferencedDocument", prefix = "ram")]
+    pub ram_buyer_order_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "ContractReferencedDocument", prefix = "ram")]
+    pub ram_contract_referenced_document: Option<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "AdditionalReferencedDocument", prefix = "ram")]
+    pub ram_additional_referenced_document: Vec<Zugferd1ReferencedDocument>,
+    #[yaserde(rename = "GrossPriceProductTradePrice", prefix = "ram")]
+    pub ram_gross_price_product_trade_price: Option<Zugferd1TradePrice>,
+    #[yaserde(rename = "NetPriceProductTradePrice", prefix = "ram")]
+    pub ram_net_price_product_trade_price: Option<Zugferd1TradePrice>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradePrice {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ChargeAmount", prefix = "ram")]
+    pub ram_charge_amount: Option<Zugferd1Amount>,
+    #[yaserde(rename = "BasisQuantity", prefix = "ram")]
+    pub ram_basis_quantity: Option<Zugferd1Quantity>,
+    #[yaserde(rename = "AppliedTradeAllowanceCharge", prefix = "ram")]
+    pub ram_applied_trade_allowance_charge: Vec<Zugferd1AllowanceCharge>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LineTradeDelivery {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "BilledQuantity", prefix = "ram")]
+    pub ram_billed_quantity: Option<Zugferd1Quantity>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1LineTradeSettlement {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "ApplicableTradeTax", prefix = "ram")]
+    pub ram_applicable_trade_tax: Vec<Zugferd1TradeTax>,
+    #[yaserde(rename = "BillingSpecifiedPeriod", prefix = "ram")]
+    pub ram_billing_specified_period: Option<Zugferd1Period>,
+    #[yaserde(rename = "SpecifiedTradeAllowanceCharge", prefix = "ram")]
+    pub ram_specified_trade_allowance_charge: Vec<Zugferd1AllowanceCharge>,
+    #[yaserde(rename = "SpecifiedTradeSettlementMonetarySummation", prefix = "ram")]
+    pub ram_specified_trade_settlement_monetary_summation: Option<Zugferd1MonetarySummation>,
+}
+
+#[derive(Clone, Default, YaSerialize, YaDeserialize)]
+#[yaserde(
+namespaces = {
+    "rsm" = "urn:ferd:CrossIndustryDocument:invoice:1p0",
+    "ram" = "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:12",
+    "udt" = "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:15",
+  }
+)]
+pub struct Zugferd1TradeProduct {
+    #[yaserde(text = true)]
+    pub text: Option<String>,
+    #[yaserde(rename = "GlobalID", prefix = "ram")]
+    pub ram_global_id: Vec<Zugferd1SchemeId>,
+    #[yaserde(rename = "SellerAssignedID", prefix = "ram")]
+    pub ram_seller_assigned_id: Option<String>,
+    #[yaserde(rename = "BuyerAssignedID", prefix = "ram")]
+    pub ram_buyer_assigned_id: Option<String>,
+    #[yaserde(rename = "Name", prefix = "ram")]
+    pub ram_name: Option<String>,
+    #[yaserde(rename = "Description", prefix = "ram")]
+    pub ram_description: Option<String>,
+}
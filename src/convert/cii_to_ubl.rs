@@ -0,0 +1,817 @@
+use crate::cii::cii_model::{
+    ApplicableTradeTax, DateTimeString, DefinedTradeContact, PostalTradeAddress,
+    RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument,
+    RamIncludedSupplyChainTradeLineItem, TradeParty,
+};
+use crate::convert::code_mapping::{
+    join_note, ubl_date_code, ADDITIONAL_DOCUMENT_TYPE_CODE, INVOICED_OBJECT_TYPE_CODE,
+    TENDER_OR_LOT_TYPE_CODE, VAT_SCHEME,
+};
+use crate::ubl::ubl_model::{
+    Address, Amount, CacAccountingCustomerParty, CacAccountingSupplierParty,
+    CacAdditionalItemProperty, CacAddressLine, CacAllowanceCharge, CacAttachment,
+    CacBillingReference, CacBuyersItemIdentification, CacCommodityClassification, CacCountry,
+    CacDelivery, CacDocumentReference, CacExternalReference, CacFinancialInstitutionBranch,
+    CacItem, CacOrderLineReference, CacOrderReference, CacPaymentTerms, CacPrice,
+    CacProjectReference, CacSellersItemIdentification, CacStandardItemIdentification,
+    CacTaxSubtotal, CacTaxTotal, CardAccount, CbcBaseQuantity, CbcEmbeddedDocumentBinaryObject,
+    CbcInvoicedQuantity, CbcItemClassificationCode, CbcPaymentMeansCode, CompanyId, Contact,
+    FinancialAccount, Identifier, InvoiceCacInvoiceLineCacItemCacStandardItemIdentificationCbcId,
+    InvoiceLine, Iso8601Date, LegalMonetaryTotal, Location, Party, PartyIdentification,
+    PartyLegalEntity, PartyName, PartyTaxScheme, PaymentMandate, PaymentMeans, Period, TaxCategory,
+    TaxScheme,
+};
+use crate::{CrossIndustryInvoice, UblInvoice};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Convert a UN/CEFACT Cross Industry Invoice to a UBL invoice
+/// # Arguments
+/// * `invoice` - The CII invoice
+/// # Returns
+/// * `UblInvoice` - The invoice in UBL syntax
+///
+/// The business terms of EN 16931 are mapped according to the syntax bindings of CEN/TS 16931-3.
+/// Elements without an EN 16931 business term, e.g. those of the EXTENDED profile, and unknown elements are not converted.
+pub fn cii_to_ubl(invoice: &CrossIndustryInvoice) -> UblInvoice {
+    let context = invoice.rsm_exchanged_document_context.as_ref();
+    let document = invoice.rsm_exchanged_document.as_ref();
+    let transaction = invoice.rsm_supply_chain_trade_transaction.as_ref();
+    let agreement = transaction.and_then(|t| t.ram_applicable_header_trade_agreement.as_ref());
+    let delivery = transaction.and_then(|t| t.ram_applicable_header_trade_delivery.as_ref());
+    let settlement = transaction.and_then(|t| t.ram_applicable_header_trade_settlement.as_ref());
+    let currency = settlement.and_then(|s| s.ram_invoice_currency_code.clone());
+    let tax_currency = settlement.and_then(|s| s.ram_tax_currency_code.clone());
+    let payment_terms = settlement.and_then(|s| s.ram_specified_trade_payment_terms.as_ref());
+    let trade_taxes = settlement
+        .map(|s| s.ram_applicable_trade_tax.as_slice())
+        .unwrap_or_default();
+    let additional_documents = agreement
+        .map(|a| a.ram_additional_referenced_document.as_slice())
+        .unwrap_or_default();
+    let amount = |value: Option<&String>| amount(value, currency.as_ref());
+
+    let mut seller = agreement
+        .and_then(|a| a.ram_seller_trade_party.as_ref())
+        .map(|party| trade_party(party, true));
+    if let (Some(seller), Some(creditor_reference_id)) = (
+        seller.as_mut(),
+        settlement.and_then(|s| s.ram_creditor_reference_id.as_ref()),
+    ) {
+        seller.cac_party_identification.push(PartyIdentification {
+            cbc_id: Some(identifier(creditor_reference_id, Some("SEPA"))),
+        });
+    }
+
+    let mut tax_totals = Vec::new();
+    let summation = settlement.and_then(|s| {
+        s.ram_specified_trade_settlement_header_monetary_summation
+            .as_ref()
+    });
+    for tax_total in summation
+        .map(|s| s.ram_tax_total_amount.as_slice())
+        .unwrap_or_default()
+    {
+        let accounting_currency = tax_total.currency_id.is_some()
+            && tax_total.currency_id != currency
+            && tax_total.currency_id == tax_currency;
+        tax_totals.push(CacTaxTotal {
+            text: None,
+            cbc_tax_amount: self::amount(
+                tax_total.text.as_ref(),
+                tax_total.currency_id.as_ref().or(currency.as_ref()),
+            ),
+            cac_tax_subtotal: if accounting_currency {
+                vec![]
+            } else {
+                trade_taxes
+                    .iter()
+                    .map(|tax| CacTaxSubtotal {
+                        text: None,
+                        cbc_taxable_amount: amount(tax.ram_basis_amount.as_ref()),
+                        cbc_tax_amount: amount(tax.ram_calculated_amount.as_ref()),
+                        cac_tax_category: Some(TaxCategory {
+                            cbc_tax_exemption_reason_code: tax.ram_exemption_reason_code.clone(),
+                            cbc_tax_exemption_reason: tax
+                                .ram_exemption_reason
+                                .clone()
+                                .into_iter()
+                                .collect(),
+                            ..tax_category(tax)
+                        }),
+                    })
+                    .collect()
+            },
+        });
+    }
+
+    let ship_to = delivery.and_then(|d| d.ram_ship_to_trade_party.as_ref());
+    let actual_delivery_date = delivery
+        .and_then(|d| d.ram_actual_delivery_supply_chain_event.as_ref())
+        .and_then(|event| event.ram_occurrence_date_time.as_ref())
+        .and_then(|date| date.udt_date_time_string.as_ref())
+        .and_then(date);
+    let billing_period = settlement.and_then(|s| s.ram_billing_specified_period.as_ref());
+    let period_start = billing_period
+        .and_then(|period| period.ram_start_date_time.as_ref())
+        .and_then(|date| date.udt_date_time_string.as_ref())
+        .and_then(date);
+    let period_end = billing_period
+        .and_then(|period| period.ram_end_date_time.as_ref())
+        .and_then(|date| date.udt_date_time_string.as_ref())
+        .and_then(date);
+    let date_code = trade_taxes
+        .iter()
+        .find_map(|tax| tax.ram_due_date_type_code.as_deref())
+        .map(ubl_date_code);
+    let mandate_id = payment_terms.and_then(|terms| terms.ram_direct_debit_mandate_id.clone());
+
+    UblInvoice {
+        cbc_customization_id: context
+            .and_then(|c| {
+                c.ram_guideline_specified_document_context_parameter
+                    .as_ref()
+            })
+            .and_then(|p| p.id.as_ref())
+            .and_then(|id| id.id.clone()),
+        cbc_profile_id: context
+            .and_then(|c| {
+                c.ram_business_process_specified_document_context_parameter
+                    .as_ref()
+            })
+            .and_then(|p| p.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+        cbc_id: document
+            .and_then(|d| d.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+        cbc_issue_date: document
+            .and_then(|d| d.ram_issue_date_time.as_ref())
+            .and_then(|d| d.udt_date_time_string.as_ref())
+            .and_then(date),
+        cbc_due_date: payment_terms
+            .and_then(|terms| terms.ram_due_date_date_time.as_ref())
+            .and_then(|d| d.udt_date_time_string.as_ref())
+            .and_then(date),
+        cbc_invoice_type_code: document.and_then(|d| d.ram_type_code.clone()),
+        cbc_note: document
+            .map(|d| d.ram_included_note.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|note| {
+                note.ram_content
+                    .as_deref()
+                    .map(|content| join_note(note.ram_subject_code.as_deref(), content))
+            })
+            .collect(),
+        cbc_tax_point_date: trade_taxes
+            .iter()
+            .filter_map(|tax| tax.ram_tax_point_date.as_ref())
+            .filter_map(|date| date.udt_date_string.as_ref())
+            .find_map(|date| {
+                date.text
+                    .as_deref()
+                    .and_then(|text| NaiveDate::parse_from_str(text.trim(), "%Y%m%d").ok())
+            })
+            .map(Iso8601Date::from),
+        cbc_document_currency_code: currency.clone(),
+        cbc_tax_currency_code: tax_currency.clone(),
+        cbc_accounting_cost: settlement
+            .and_then(|s| s.ram_receivable_specified_trade_accounting_account.as_ref())
+            .and_then(|account| account.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+        cbc_buyer_reference: agreement.and_then(|a| a.ram_buyer_reference.clone()),
+        cac_invoice_period: (period_start.is_some() || period_end.is_some() || date_code.is_some())
+            .then(|| Period {
+                cbc_start_date: period_start,
+                cbc_end_date: period_end,
+                cbc_description_code: date_code,
+                ..Default::default()
+            }),
+        cac_order_reference: {
+            let order_id = agreement
+                .and_then(|a| a.ram_buyer_order_referenced_document.as_ref())
+                .and_then(|d| d.ram_issuer_assigned_id.clone());
+            let sales_order_id = agreement
+                .and_then(|a| a.ram_seller_order_referenced_document.as_ref())
+                .and_then(|d| d.ram_issuer_assigned_id.clone());
+            (order_id.is_some() || sales_order_id.is_some()).then(|| CacOrderReference {
+                text: None,
+                // The order reference is mandatory in UBL, but the sales order reference may be given alone
+                cbc_id: order_id.or(Some("NA".to_string())),
+                cbc_sales_order_id: sales_order_id,
+            })
+        },
+        cac_billing_reference: settlement
+            .and_then(|s| s.ram_invoice_referenced_document.as_ref())
+            .map(|reference| CacBillingReference {
+                text: None,
+                cac_invoice_document_reference: Some(CacDocumentReference {
+                    id: reference
+                        .ram_issuer_assigned_id
+                        .as_deref()
+                        .map(|id| identifier(id, None)),
+                    cbc_issue_date: reference
+                        .ram_formatted_issue_date_time
+                        .as_ref()
+                        .and_then(|d| d.qdt_date_time_string.as_ref())
+                        .and_then(date),
+                    ..Default::default()
+                }),
+            })
+            .into_iter()
+            .collect(),
+        cac_despatch_document_reference: delivery
+            .and_then(|d| d.ram_despatch_advice_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.as_deref())
+            .map(document_reference),
+        cac_receipt_document_reference: delivery
+            .and_then(|d| d.ram_receiving_advice_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.as_deref())
+            .map(document_reference),
+        cac_originator_document_reference: additional_documents
+            .iter()
+            .find(|d| d.ram_type_code.as_deref() == Some(TENDER_OR_LOT_TYPE_CODE))
+            .and_then(|d| d.ram_issuer_assigned_id.as_ref())
+            .and_then(|id| id.id.as_deref())
+            .map(document_reference),
+        cac_contract_document_reference: agreement
+            .and_then(|a| a.ram_contract_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.as_deref())
+            .map(document_reference),
+        cac_additional_document_reference: additional_documents
+            .iter()
+            .filter(|d| d.ram_type_code.as_deref() != Some(TENDER_OR_LOT_TYPE_CODE))
+            .map(additional_document)
+            .collect(),
+        cac_project_reference: agreement
+            .and_then(|a| a.ram_specified_procuring_project.as_ref())
+            .and_then(|project| project.ram_id.as_ref())
+            .and_then(|id| id.id.clone())
+            .map(|id| CacProjectReference {
+                text: None,
+                cbc_id: Some(id),
+            }),
+        cac_accounting_supplier_party: seller.map(|party| CacAccountingSupplierParty {
+            text: None,
+            cac_party: Some(party),
+        }),
+        cac_accounting_customer_party: agreement
+            .and_then(|a| a.ram_buyer_trade_party.as_ref())
+            .map(|party| CacAccountingCustomerParty {
+                text: None,
+                cac_party: Some(trade_party(party, true)),
+            }),
+        cac_payee_party: settlement
+            .and_then(|s| s.ram_payee_trade_party.as_ref())
+            .map(|party| trade_party(party, false)),
+        cac_tax_representative_party: agreement
+            .and_then(|a| a.ram_seller_tax_representative_trade_party.as_ref())
+            .map(|party| trade_party(party, false)),
+        cac_delivery: (ship_to.is_some() || actual_delivery_date.is_some()).then(|| CacDelivery {
+            text: None,
+            cbc_actual_delivery_date: actual_delivery_date,
+            cac_delivery_location: ship_to
+                .filter(|party| {
+                    party.ram_global_id.is_some()
+                        || !party.ram_id.is_empty()
+                        || party.ram_postal_trade_address.is_some()
+                })
+                .map(|party| Location {
+                    cbc_id: party_ids(party).into_iter().next(),
+                    cac_address: party.ram_postal_trade_address.as_ref().map(address),
+                    ..Default::default()
+                }),
+            cac_delivery_party: ship_to
+                .and_then(|party| party.ram_name.clone())
+                .map(|name| Party {
+                    cac_party_name: vec![PartyName {
+                        cbc_name: Some(name),
+                    }],
+                    ..Default::default()
+                }),
+        }),
+        cac_payment_means: settlement
+            .map(|s| s.ram_specified_trade_settlement_payment_means.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|means| PaymentMeans {
+                cbc_payment_means_code: Some(CbcPaymentMeansCode {
+                    name: means.ram_information.clone(),
+                    text: means.ram_type_code.clone(),
+                }),
+                cbc_payment_id: settlement
+                    .and_then(|s| s.ram_payment_reference.as_deref())
+                    .map(|reference| identifier(reference, None)),
+                cac_card_account: means
+                    .ram_applicable_trade_settlement_financial_card
+                    .as_ref()
+                    .map(|card| CardAccount {
+                        primary_account_number_id: card
+                            .ram_id
+                            .as_deref()
+                            .map(|id| identifier(id, None)),
+                        cbc_network_id: Some(identifier("NA", None)),
+                        cbc_holder_name: card.ram_cardholder_name.clone(),
+                        ..Default::default()
+                    }),
+                cac_payee_financial_account: means
+                    .ram_payee_party_creditor_financial_account
+                    .as_ref()
+                    .map(|account| FinancialAccount {
+                        text: None,
+                        cbc_id: account
+                            .ram_ibanid
+                            .clone()
+                            .or(account.ram_proprietary_id.clone()),
+                        cbc_name: account.ram_account_name.clone(),
+                        cac_financial_institution_branch: means
+                            .ram_payee_specified_creditor_financial_institution
+                            .as_ref()
+                            .and_then(|institution| institution.ram_bicid.clone())
+                            .map(|bic| CacFinancialInstitutionBranch {
+                                text: None,
+                                cbc_id: Some(bic),
+                            }),
+                    }),
+                cac_payment_mandate: means
+                    .ram_payer_party_debitor_financial_account
+                    .as_ref()
+                    .map(|account| PaymentMandate {
+                        cbc_id: mandate_id.as_deref().map(|id| identifier(id, None)),
+                        cac_payer_financial_account: Some(FinancialAccount {
+                            cbc_id: account
+                                .ram_ibanid
+                                .clone()
+                                .or(account.ram_proprietary_id.clone()),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    }),
+                ..Default::default()
+            })
+            .collect(),
+        cac_payment_terms: payment_terms
+            .and_then(|terms| terms.ram_description.clone())
+            .map(|note| CacPaymentTerms {
+                cbc_note: Some(note),
+                ..Default::default()
+            }),
+        cac_allowance_charge: settlement
+            .map(|s| s.ram_specified_trade_allowance_charge.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|allowance_charge| CacAllowanceCharge {
+                charge_indicator: Some(
+                    allowance_charge
+                        .ram_charge_indicator
+                        .as_ref()
+                        .and_then(|indicator| indicator.udt_indicator.as_deref())
+                        .is_some_and(is_true),
+                ),
+                cbc_allowance_charge_reason_code: allowance_charge.ram_reason_code.clone(),
+                cbc_allowance_charge_reason: allowance_charge.ram_reason.clone(),
+                cbc_multiplier_factor_numeric: percent(
+                    allowance_charge.ram_calculation_percent.as_deref(),
+                ),
+                amount: amount(allowance_charge.ram_actual_amount.as_ref()),
+                cbc_base_amount: amount(allowance_charge.ram_basis_amount.as_ref()),
+                cac_tax_category: allowance_charge
+                    .ram_category_trade_tax
+                    .as_ref()
+                    .map(|tax| TaxCategory {
+                        cbc_id: tax.ram_category_code.clone(),
+                        cbc_percent: tax.ram_rate_applicable_percent.clone(),
+                        cac_tax_scheme: Some(vat_scheme()),
+                        ..Default::default()
+                    })
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            })
+            .collect(),
+        cac_tax_total: tax_totals,
+        cac_legal_monetary_total: summation.map(|summation| LegalMonetaryTotal {
+            value: None,
+            cbc_line_extension_amount: amount(summation.ram_line_total_amount.as_ref()),
+            cbc_tax_exclusive_amount: amount(summation.ram_tax_basis_total_amount.as_ref()),
+            cbc_tax_inclusive_amount: amount(summation.ram_grand_total_amount.as_ref()),
+            cbc_allowance_total_amount: amount(summation.ram_allowance_total_amount.as_ref()),
+            cbc_charge_total_amount: amount(summation.ram_charge_total_amount.as_ref()),
+            cbc_prepaid_amount: amount(summation.ram_total_prepaid_amount.as_ref()),
+            cbc_payable_rounding_amount: amount(summation.ram_rounding_amount.as_ref()),
+            payable_amount: amount(summation.ram_due_payable_amount.as_ref()),
+        }),
+        cac_invoice_line: transaction
+            .map(|t| t.ram_included_supply_chain_trade_line_item.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|line| invoice_line(line, currency.as_ref()))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn trade_party(party: &TradeParty, legal_name: bool) -> Party {
+    let legal_organization = party.ram_specified_legal_organization.as_ref();
+    let trading_name =
+        legal_organization.and_then(|organization| organization.ram_trading_business_name.clone());
+    let legal_id = legal_organization
+        .and_then(|organization| organization.ram_id.as_ref())
+        .map(|id| CompanyId {
+            scheme_id: id.scheme_id.clone(),
+            text: id.text.clone(),
+        });
+    let registration_name = party.ram_name.clone().filter(|_| legal_name);
+    let party_name = if legal_name {
+        trading_name
+    } else {
+        party.ram_name.clone()
+    };
+    Party {
+        cbc_endpoint_id: party
+            .ram_uriuniversal_communication
+            .as_ref()
+            .and_then(|communication| communication.ram_uriid.as_ref())
+            .and_then(|uriid| {
+                uriid
+                    .text
+                    .as_deref()
+                    .map(|text| identifier(text, uriid.scheme_id.as_deref()))
+            }),
+        cac_party_identification: party_ids(party)
+            .into_iter()
+            .map(|id| PartyIdentification { cbc_id: Some(id) })
+            .collect(),
+        cac_party_name: party_name
+            .map(|name| PartyName {
+                cbc_name: Some(name),
+            })
+            .into_iter()
+            .collect(),
+        cac_postal_address: party.ram_postal_trade_address.as_ref().map(address),
+        cac_party_tax_scheme: party
+            .ram_specified_tax_registration
+            .iter()
+            .filter_map(|registration| registration.ram_id.as_ref())
+            .map(|id| PartyTaxScheme {
+                cbc_company_id: id.text.clone(),
+                cac_tax_scheme: Some(if id.scheme_id.as_deref() == Some("VA") {
+                    vat_scheme()
+                } else {
+                    TaxScheme {
+                        id: Some(identifier("FC", None)),
+                        ..Default::default()
+                    }
+                }),
+                ..Default::default()
+            })
+            .collect(),
+        cac_party_legal_entity: (registration_name.is_some()
+            || legal_id.is_some()
+            || party.ram_description.is_some())
+        .then(|| PartyLegalEntity {
+            text: None,
+            cbc_registration_name: registration_name,
+            cbc_company_id: legal_id,
+            cbc_company_legal_form: party.ram_description.clone(),
+        })
+        .into_iter()
+        .collect(),
+        cac_contact: party.ram_defined_trade_contact.as_ref().map(contact),
+        ..Default::default()
+    }
+}
+
+fn party_ids(party: &TradeParty) -> Vec<Identifier> {
+    party
+        .ram_global_id
+        .iter()
+        .filter_map(|id| {
+            id.text
+                .as_deref()
+                .map(|text| identifier(text, id.scheme_id.as_deref()))
+        })
+        .chain(party.ram_id.iter().map(|id| identifier(id, None)))
+        .collect()
+}
+
+fn contact(contact: &DefinedTradeContact) -> Contact {
+    Contact {
+        text: None,
+        cbc_name: contact
+            .ram_person_name
+            .clone()
+            .or(contact.ram_department_name.clone()),
+        cbc_telephone: contact
+            .ram_telephone_universal_communication
+            .as_ref()
+            .and_then(|telephone| telephone.ram_complete_number.clone()),
+        cbc_electronic_mail: contact
+            .ram_email_uriuniversal_communication
+            .as_ref()
+            .and_then(|email| email.ram_uriid.clone()),
+    }
+}
+
+fn address(address: &PostalTradeAddress) -> Address {
+    Address {
+        cbc_street_name: address.ram_line_one.clone(),
+        cbc_additional_street_name: address.ram_line_two.clone(),
+        cbc_city_name: address.ram_city_name.clone(),
+        cbc_postal_zone: address.ram_postcode_code.clone(),
+        cbc_country_subentity: address.ram_country_sub_division_name.clone(),
+        cac_address_line: address
+            .ram_line_three
+            .clone()
+            .map(|line| CacAddressLine {
+                cbc_line: Some(line),
+            })
+            .into_iter()
+            .collect(),
+        cac_country: address.ram_country_id.clone().map(|code| CacCountry {
+            cbc_identification_code: Some(code),
+            cbc_name: None,
+        }),
+        ..Default::default()
+    }
+}
+
+fn additional_document(
+    document: &RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument,
+) -> CacDocumentReference {
+    let object = document.ram_attachment_binary_object.as_ref();
+    CacDocumentReference {
+        id: document.ram_issuer_assigned_id.as_ref().and_then(|id| {
+            id.id
+                .as_deref()
+                .map(|text| identifier(text, id.scheme_id.as_deref()))
+        }),
+        cbc_document_type_code: document
+            .ram_type_code
+            .clone()
+            .filter(|code| code != ADDITIONAL_DOCUMENT_TYPE_CODE),
+        cbc_document_description: document.ram_name.clone().into_iter().collect(),
+        cac_attachment: (object.is_some() || document.ram_uriid.is_some()).then(|| CacAttachment {
+            text: None,
+            cbc_embedded_document_binary_object: object.map(|object| {
+                CbcEmbeddedDocumentBinaryObject {
+                    mime_code: object.mime_code.clone(),
+                    filename: object.filename.clone(),
+                    text: object.text.clone(),
+                }
+            }),
+            cac_external_reference: document.ram_uriid.clone().map(|uri| CacExternalReference {
+                text: None,
+                cbc_uri: Some(uri),
+            }),
+        }),
+        ..Default::default()
+    }
+}
+
+fn invoice_line(
+    line: &RamIncludedSupplyChainTradeLineItem,
+    currency: Option<&String>,
+) -> InvoiceLine {
+    let document = line.ram_associated_document_line_document.as_ref();
+    let product = line.ram_specified_trade_product.as_ref();
+    let line_agreement = line.ram_specified_line_trade_agreement.as_ref();
+    let line_settlement = line.ram_specified_line_trade_settlement.as_ref();
+    let net_price = line_agreement.and_then(|a| a.ram_net_price_product_trade_price.as_ref());
+    let gross_price = line_agreement.and_then(|a| a.ram_gross_price_product_trade_price.as_ref());
+    let amount = |value: Option<&String>| amount(value, currency);
+    let period = line_settlement.and_then(|s| s.ram_billing_specified_period.as_ref());
+
+    InvoiceLine {
+        cbc_id: document
+            .and_then(|d| d.ram_line_id.as_deref())
+            .map(|id| identifier(id, None)),
+        cbc_note: document
+            .and_then(|d| d.ram_included_note.as_ref())
+            .and_then(|note| note.ram_content.clone()),
+        cbc_invoiced_quantity: line
+            .ram_specified_line_trade_delivery
+            .as_ref()
+            .and_then(|d| d.ram_billed_quantity.as_ref())
+            .map(|quantity| CbcInvoicedQuantity {
+                unit_code: quantity.unit_code.clone(),
+                text: quantity.text.clone(),
+            }),
+        cbc_line_extension_amount: amount(
+            line_settlement
+                .and_then(|s| {
+                    s.ram_specified_trade_settlement_line_monetary_summation
+                        .as_ref()
+                })
+                .and_then(|summation| summation.ram_line_total_amount.as_ref()),
+        ),
+        cbc_accounting_cost: line_settlement
+            .and_then(|s| s.ram_receivable_specified_trade_accounting_account.as_ref())
+            .and_then(|account| account.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+        cac_invoice_period: period.map(|period| Period {
+            cbc_start_date: period
+                .ram_start_date_time
+                .as_ref()
+                .and_then(|d| d.udt_date_time_string.as_ref())
+                .and_then(date),
+            cbc_end_date: period
+                .ram_end_date_time
+                .as_ref()
+                .and_then(|d| d.udt_date_time_string.as_ref())
+                .and_then(date),
+            ..Default::default()
+        }),
+        cac_order_line_reference: line_agreement
+            .and_then(|a| a.ram_buyer_order_referenced_document.as_ref())
+            .and_then(|d| d.ram_line_id.clone())
+            .map(|line_id| CacOrderLineReference {
+                text: None,
+                cbc_line_id: Some(line_id),
+            }),
+        cac_document_reference: line_settlement
+            .and_then(|s| s.ram_additional_referenced_document.as_ref())
+            .filter(|d| d.ram_type_code.as_deref() == Some(INVOICED_OBJECT_TYPE_CODE))
+            .and_then(|d| {
+                d.ram_issuer_assigned_id
+                    .as_deref()
+                    .map(|id| CacDocumentReference {
+                        id: Some(identifier(id, d.ram_reference_type_code.as_deref())),
+                        cbc_document_type_code: Some(INVOICED_OBJECT_TYPE_CODE.to_string()),
+                        ..Default::default()
+                    })
+            }),
+        cac_allowance_charge: line_settlement
+            .map(|s| s.ram_specified_trade_allowance_charge.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .map(|allowance_charge| CacAllowanceCharge {
+                charge_indicator: Some(
+                    allowance_charge
+                        .ram_charge_indicator
+                        .as_ref()
+                        .and_then(|indicator| indicator.udt_indicator.as_deref())
+                        .is_some_and(is_true),
+                ),
+                cbc_allowance_charge_reason_code: allowance_charge.ram_reason_code.clone(),
+                cbc_allowance_charge_reason: allowance_charge.ram_reason.clone(),
+                cbc_multiplier_factor_numeric: percent(
+                    allowance_charge.ram_calculation_percent.as_deref(),
+                ),
+                amount: amount(allowance_charge.ram_actual_amount.as_ref()),
+                cbc_base_amount: amount(allowance_charge.ram_basis_amount.as_ref()),
+                ..Default::default()
+            })
+            .collect(),
+        cac_item: product.map(|product| CacItem {
+            text: None,
+            cbc_description: product.ram_description.clone(),
+            cbc_name: product.ram_name.clone(),
+            cac_buyers_item_identification: product
+                .ram_buyer_assigned_id
+                .as_ref()
+                .and_then(|id| id.id.clone())
+                .map(|id| CacBuyersItemIdentification {
+                    text: None,
+                    cbc_id: Some(id),
+                }),
+            cac_sellers_item_identification: product
+                .ram_seller_assigned_id
+                .as_ref()
+                .and_then(|id| id.id.clone())
+                .map(|id| CacSellersItemIdentification {
+                    text: None,
+                    cbc_id: Some(id),
+                }),
+            cac_standard_item_identification: product.ram_global_id.as_ref().map(|id| {
+                CacStandardItemIdentification {
+                    text: None,
+                    cbc_id: Some(
+                        InvoiceCacInvoiceLineCacItemCacStandardItemIdentificationCbcId {
+                            scheme_id: id.scheme_id.clone(),
+                            text: id.id.clone(),
+                        },
+                    ),
+                }
+            }),
+            cac_origin_country: product
+                .ram_origin_trade_country
+                .as_ref()
+                .and_then(|country| country.ram_id.as_ref())
+                .and_then(|id| id.id.clone())
+                .map(|code| CacCountry {
+                    cbc_identification_code: Some(code),
+                    cbc_name: None,
+                }),
+            cac_commodity_classification: product
+                .ram_designated_product_classification
+                .iter()
+                .filter_map(|classification| classification.ram_class_code.as_ref())
+                .map(|code| CacCommodityClassification {
+                    text: None,
+                    cbc_item_classification_code: Some(CbcItemClassificationCode {
+                        list_id: code.list_id.clone(),
+                        list_version_id: code.list_version_id.clone(),
+                        text: code.text.clone(),
+                    }),
+                })
+                .collect(),
+            cac_classified_tax_category: line_settlement
+                .and_then(|s| s.ram_applicable_trade_tax.as_ref())
+                .map(tax_category),
+            cac_additional_item_property: product
+                .ram_applicable_product_characteristic
+                .iter()
+                .map(|characteristic| CacAdditionalItemProperty {
+                    text: None,
+                    cbc_name: characteristic.ram_description.clone(),
+                    cbc_value: characteristic.ram_value.clone(),
+                })
+                .collect(),
+        }),
+        cac_price: net_price.map(|price| CacPrice {
+            text: None,
+            cbc_price_amount: amount(price.ram_charge_amount.as_ref()),
+            cbc_base_quantity: price
+                .ram_basis_quantity
+                .as_ref()
+                .map(|quantity| CbcBaseQuantity {
+                    unit_code: quantity.unit_code.clone(),
+                    text: quantity.text.clone(),
+                }),
+            cac_allowance_charge: gross_price.map(|gross_price| CacAllowanceCharge {
+                charge_indicator: Some(false),
+                amount: amount(
+                    gross_price
+                        .ram_applied_trade_allowance_charge
+                        .as_ref()
+                        .and_then(|allowance| allowance.ram_actual_amount.as_ref()),
+                )
+                .or_else(|| {
+                    Some(Amount {
+                        value: Some(Decimal::ZERO),
+                        currency_id: currency.cloned(),
+                    })
+                }),
+                cbc_base_amount: amount(gross_price.ram_charge_amount.as_ref()),
+                ..Default::default()
+            }),
+        }),
+        cac_sub_invoice_line: vec![],
+    }
+}
+
+fn tax_category(tax: &ApplicableTradeTax) -> TaxCategory {
+    TaxCategory {
+        cbc_id: tax.ram_category_code.clone(),
+        cbc_percent: tax.ram_rate_applicable_percent.clone(),
+        cac_tax_scheme: Some(vat_scheme()),
+        ..Default::default()
+    }
+}
+
+fn vat_scheme() -> TaxScheme {
+    TaxScheme {
+        id: Some(identifier(VAT_SCHEME, None)),
+        ..Default::default()
+    }
+}
+
+fn document_reference(id: &str) -> CacDocumentReference {
+    CacDocumentReference {
+        id: Some(identifier(id, None)),
+        ..Default::default()
+    }
+}
+
+fn identifier(id: &str, scheme_id: Option<&str>) -> Identifier {
+    Identifier {
+        id: Some(id.to_string()),
+        scheme_id: scheme_id.map(ToString::to_string),
+        ..Default::default()
+    }
+}
+
+fn amount(value: Option<&String>, currency: Option<&String>) -> Option<Amount> {
+    value
+        .and_then(|value| Decimal::from_str(value.trim()).ok())
+        .map(|value| Amount {
+            value: Some(value),
+            currency_id: currency.cloned(),
+        })
+}
+
+fn percent(value: Option<&str>) -> Option<f32> {
+    value.and_then(|value| value.trim().parse().ok())
+}
+
+fn is_true(indicator: &str) -> bool {
+    indicator.trim() == "true"
+}
+
+fn date(date: &DateTimeString) -> Option<Iso8601Date> {
+    NaiveDate::parse_from_str(date.text.trim(), "%Y%m%d")
+        .ok()
+        .map(Iso8601Date::from)
+}
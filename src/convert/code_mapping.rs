@@ -0,0 +1,65 @@
+/// Tax scheme of the value added tax
+pub(crate) const VAT_SCHEME: &str = "VAT";
+
+/// Document type code of an invoiced object identifier (BT-18, BT-128)
+pub(crate) const INVOICED_OBJECT_TYPE_CODE: &str = "130";
+
+/// Document type code of a tender or lot reference (BT-17) in CII
+pub(crate) const TENDER_OR_LOT_TYPE_CODE: &str = "50";
+
+/// Document type code of an additional supporting document (BG-24) in CII
+pub(crate) const ADDITIONAL_DOCUMENT_TYPE_CODE: &str = "916";
+
+/// UBL codes of the value added tax point date (BT-8) with their CII counterparts
+///
+/// Both syntaxes use UNTDID 2005, but UBL refers to the event while CII refers to the date of the event.
+const DATE_CODES: [(&str, &str); 3] = [("3", "5"), ("35", "29"), ("432", "72")];
+
+/// The CII code of a UBL value added tax point date code
+pub(crate) fn cii_date_code(ubl_code: &str) -> String {
+    DATE_CODES
+        .iter()
+        .find(|(ubl, _)| *ubl == ubl_code)
+        .map_or(ubl_code, |(_, cii)| cii)
+        .to_string()
+}
+
+/// The UBL code of a CII value added tax point date code
+pub(crate) fn ubl_date_code(cii_code: &str) -> String {
+    DATE_CODES
+        .iter()
+        .find(|(_, cii)| *cii == cii_code)
+        .map_or(cii_code, |(ubl, _)| ubl)
+        .to_string()
+}
+
+/// Split a UBL note into its subject code (BT-21) and its content (BT-22)
+///
+/// UBL has no element for the subject code, so it is prefixed to the note like `#AAI#content`.
+pub(crate) fn split_note(note: &str) -> (Option<String>, String) {
+    if let Some(rest) = note.strip_prefix('#') {
+        if let Some((code, content)) = rest.split_once('#') {
+            if !code.is_empty() && code.len() <= 3 && code.chars().all(|c| c.is_ascii_uppercase()) {
+                return (Some(code.to_string()), content.to_string());
+            }
+        }
+    }
+    (None, note.to_string())
+}
+
+/// Join a subject code (BT-21) and a note (BT-22) to a UBL note
+pub(crate) fn join_note(subject_code: Option<&str>, content: &str) -> String {
+    match subject_code {
+        Some(code) => format!("#{}#{}", code, content),
+        None => content.to_string(),
+    }
+}
+
+/// Whether a payment account identifier (BT-84) looks like an IBAN
+pub(crate) fn is_iban(id: &str) -> bool {
+    let id = id.replace(' ', "");
+    id.len() > 4
+        && id[..2].chars().all(|c| c.is_ascii_uppercase())
+        && id[2..4].chars().all(|c| c.is_ascii_digit())
+        && id[4..].chars().all(|c| c.is_ascii_alphanumeric())
+}
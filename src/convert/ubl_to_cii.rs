@@ -0,0 +1,909 @@
+use crate::cii::cii_model::{
+    ApplicableTradeTax, DateString, DateTimeString, DefinedTradeContact, GlobalId, IdType,
+    PostalTradeAddress, RamActualDeliverySupplyChainEvent, RamApplicableHeaderTradeAgreement,
+    RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument,
+    RamApplicableHeaderTradeAgreementRamBuyerOrderReferencedDocument,
+    RamApplicableHeaderTradeAgreementRamSellerTradePartyRamUriuniversalCommunicationRamUriid,
+    RamApplicableHeaderTradeDelivery, RamApplicableHeaderTradeSettlement,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriod,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime,
+    RamApplicableHeaderTradeSettlementRamReceivableSpecifiedTradeAccountingAccount,
+    RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceCharge,
+    RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator,
+    RamApplicableProductCharacteristic, RamApplicableTradeSettlementFinancialCard,
+    RamAppliedTradeAllowanceCharge, RamAssociatedDocumentLineDocument,
+    RamAssociatedDocumentLineDocumentRamIncludedNote, RamAttachmentBinaryObject, RamBasisQuantity,
+    RamBilledQuantity, RamBusinessProcessSpecifiedDocumentContextParameter, RamCategoryTradeTax,
+    RamClassCode, RamContractReferencedDocument, RamDesignatedProductClassification,
+    RamDespatchAdviceReferencedDocument, RamDueDateDateTime, RamFormattedIssueDateTime,
+    RamGrossPriceProductTradePrice,
+    RamGrossPriceProductTradePriceRamAppliedTradeAllowanceChargeRamChargeIndicator,
+    RamGuidelineSpecifiedDocumentContextParameter, RamIncludedSupplyChainTradeLineItem,
+    RamInvoiceReferencedDocument, RamIssueDateTime, RamNetPriceProductTradePrice,
+    RamOccurrenceDateTime, RamOriginTradeCountry, RamPayeePartyCreditorFinancialAccount,
+    RamPayeeSpecifiedCreditorFinancialInstitution, RamReceivingAdviceReferencedDocument,
+    RamSellerOrderReferencedDocument,
+    RamSellerTradePartyRamDefinedTradeContactRamEmailUriuniversalCommunication,
+    RamSellerTradePartyRamDefinedTradeContactRamTelephoneUniversalCommunication,
+    RamSellerTradePartyRamSpecifiedLegalOrganizationRamId,
+    RamSellerTradePartyRamSpecifiedTaxRegistrationRamId, RamSpecifiedLineTradeAgreement,
+    RamSpecifiedLineTradeAgreementRamBuyerOrderReferencedDocument, RamSpecifiedLineTradeDelivery,
+    RamSpecifiedLineTradeSettlement,
+    RamSpecifiedLineTradeSettlementRamAdditionalReferencedDocument,
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriod,
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime,
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime,
+    RamSpecifiedLineTradeSettlementRamReceivableSpecifiedTradeAccountingAccount,
+    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceCharge,
+    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator,
+    RamSpecifiedProcuringProject, RamSpecifiedTradePaymentTerms, RamSpecifiedTradeProduct,
+    RamSpecifiedTradeSettlementHeaderMonetarySummation,
+    RamSpecifiedTradeSettlementLineMonetarySummation, RamSpecifiedTradeSettlementPaymentMeans,
+    RamTaxPointDate, RamTaxTotalAmount, RsmExchangedDocument, RsmExchangedDocumentContext,
+    RsmExchangedDocumentRamIncludedNote, RsmSupplyChainTradeTransaction,
+    SpecifiedLegalOrganization, SpecifiedTaxRegistration, TradeParty, UriUniversalCommunication,
+};
+use crate::convert::code_mapping::{
+    cii_date_code, is_iban, split_note, ADDITIONAL_DOCUMENT_TYPE_CODE, INVOICED_OBJECT_TYPE_CODE,
+    TENDER_OR_LOT_TYPE_CODE, VAT_SCHEME,
+};
+use crate::ubl::ubl_model::{
+    Address, Amount, CacAllowanceCharge, CacDocumentReference, Contact, Identifier, InvoiceLine,
+    Iso8601Date, Party, Period, TaxCategory,
+};
+use crate::{CrossIndustryInvoice, UblInvoice};
+
+/// Convert a UBL invoice to a UN/CEFACT Cross Industry Invoice
+/// # Arguments
+/// * `invoice` - The UBL invoice
+/// # Returns
+/// * `CrossIndustryInvoice` - The invoice in CII syntax
+///
+/// The business terms of EN 16931 are mapped according to the syntax bindings of CEN/TS 16931-3.
+/// Elements without an EN 16931 business term, UBL sub invoice lines and unknown elements are not converted.
+pub fn ubl_to_cii(invoice: &UblInvoice) -> CrossIndustryInvoice {
+    let currency = invoice.cbc_document_currency_code.clone();
+    CrossIndustryInvoice {
+        text: None,
+        rsm_exchanged_document_context: Some(RsmExchangedDocumentContext {
+            ram_business_process_specified_document_context_parameter: invoice
+                .cbc_profile_id
+                .as_ref()
+                .map(
+                    |profile_id| RamBusinessProcessSpecifiedDocumentContextParameter {
+                        text: None,
+                        ram_id: Some(id_type(profile_id, None)),
+                    },
+                ),
+            ram_guideline_specified_document_context_parameter: invoice
+                .cbc_customization_id
+                .as_ref()
+                .map(
+                    |customization_id| RamGuidelineSpecifiedDocumentContextParameter {
+                        text: None,
+                        id: Some(id_type(customization_id, None)),
+                    },
+                ),
+            ..Default::default()
+        }),
+        rsm_exchanged_document: Some(RsmExchangedDocument {
+            text: None,
+            ram_id: invoice.cbc_id.as_ref().map(|id| id_type(id, None)),
+            ram_type_code: invoice.cbc_invoice_type_code.clone(),
+            ram_issue_date_time: invoice
+                .cbc_issue_date
+                .as_ref()
+                .map(|date| RamIssueDateTime {
+                    text: None,
+                    udt_date_time_string: Some(date_time_string(date)),
+                }),
+            ram_included_note: invoice
+                .cbc_note
+                .iter()
+                .map(|note| {
+                    let (subject_code, content) = split_note(note);
+                    RsmExchangedDocumentRamIncludedNote {
+                        text: None,
+                        ram_content: Some(content),
+                        ram_subject_code: subject_code,
+                    }
+                })
+                .collect(),
+        }),
+        rsm_supply_chain_trade_transaction: Some(RsmSupplyChainTradeTransaction {
+            text: None,
+            ram_included_supply_chain_trade_line_item: invoice
+                .cac_invoice_line
+                .iter()
+                .map(line_item)
+                .collect(),
+            ram_applicable_header_trade_agreement: Some(agreement(invoice)),
+            ram_applicable_header_trade_delivery: Some(delivery(invoice)),
+            ram_applicable_header_trade_settlement: Some(settlement(invoice, currency)),
+        }),
+        unknown_elements: Vec::new(),
+    }
+}
+
+fn agreement(invoice: &UblInvoice) -> RamApplicableHeaderTradeAgreement {
+    let seller = invoice
+        .cac_accounting_supplier_party
+        .as_ref()
+        .and_then(|party| party.cac_party.as_ref());
+    let buyer = invoice
+        .cac_accounting_customer_party
+        .as_ref()
+        .and_then(|party| party.cac_party.as_ref());
+    let mut additional_documents = invoice
+        .cac_additional_document_reference
+        .iter()
+        .map(additional_document)
+        .collect::<Vec<_>>();
+    if let Some(id) = invoice
+        .cac_originator_document_reference
+        .as_ref()
+        .and_then(|reference| reference.id.as_ref())
+        .and_then(|id| id.id.as_ref())
+    {
+        additional_documents.push(
+            RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument {
+                ram_issuer_assigned_id: Some(id_type(id, None)),
+                ram_type_code: Some(TENDER_OR_LOT_TYPE_CODE.to_string()),
+                ..Default::default()
+            },
+        );
+    }
+    RamApplicableHeaderTradeAgreement {
+        text: None,
+        ram_buyer_reference: invoice.cbc_buyer_reference.clone(),
+        ram_seller_trade_party: seller.map(trade_party),
+        ram_buyer_trade_party: buyer.map(trade_party),
+        ram_seller_tax_representative_trade_party: invoice
+            .cac_tax_representative_party
+            .as_ref()
+            .map(trade_party),
+        ram_seller_order_referenced_document: invoice
+            .cac_order_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_sales_order_id.clone())
+            .map(|id| RamSellerOrderReferencedDocument {
+                text: None,
+                ram_issuer_assigned_id: Some(id),
+            }),
+        ram_buyer_order_referenced_document: invoice
+            .cac_order_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_id.clone())
+            .map(
+                |id| RamApplicableHeaderTradeAgreementRamBuyerOrderReferencedDocument {
+                    text: None,
+                    ram_issuer_assigned_id: Some(id),
+                },
+            ),
+        ram_contract_referenced_document: document_id(
+            invoice.cac_contract_document_reference.as_ref(),
+        )
+        .map(|id| RamContractReferencedDocument {
+            text: None,
+            ram_issuer_assigned_id: Some(id),
+        }),
+        ram_additional_referenced_document: additional_documents,
+        ram_specified_procuring_project: invoice
+            .cac_project_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_id.as_ref())
+            .map(|id| RamSpecifiedProcuringProject {
+                text: None,
+                ram_id: Some(id_type(id, None)),
+                ram_name: Some("Project reference".to_string()),
+            }),
+    }
+}
+
+fn delivery(invoice: &UblInvoice) -> RamApplicableHeaderTradeDelivery {
+    let delivery = invoice.cac_delivery.as_ref();
+    let location = delivery.and_then(|delivery| delivery.cac_delivery_location.as_ref());
+    let ship_to_name = delivery
+        .and_then(|delivery| delivery.cac_delivery_party.as_ref())
+        .and_then(|party| {
+            party
+                .cac_party_name
+                .iter()
+                .find_map(|name| name.cbc_name.clone())
+        });
+    let ship_to_location = location.and_then(|location| location.cbc_id.as_ref());
+    let ship_to_address = location.and_then(|location| location.cac_address.as_ref());
+    RamApplicableHeaderTradeDelivery {
+        text: None,
+        ram_ship_to_trade_party: (ship_to_name.is_some()
+            || ship_to_location.is_some()
+            || ship_to_address.is_some())
+        .then(|| {
+            let (ram_id, ram_global_id) = party_ids(ship_to_location.into_iter());
+            TradeParty {
+                ram_id,
+                ram_global_id,
+                ram_name: ship_to_name,
+                ram_postal_trade_address: ship_to_address.map(postal_address),
+                ..Default::default()
+            }
+        }),
+        ram_actual_delivery_supply_chain_event: delivery
+            .and_then(|delivery| delivery.cbc_actual_delivery_date.as_ref())
+            .map(|date| RamActualDeliverySupplyChainEvent {
+                text: None,
+                ram_occurrence_date_time: Some(RamOccurrenceDateTime {
+                    text: None,
+                    udt_date_time_string: Some(date_time_string(date)),
+                }),
+            }),
+        ram_despatch_advice_referenced_document: document_id(
+            invoice.cac_despatch_document_reference.as_ref(),
+        )
+        .map(|id| RamDespatchAdviceReferencedDocument {
+            text: None,
+            ram_issuer_assigned_id: Some(id),
+        }),
+        ram_receiving_advice_referenced_document: document_id(
+            invoice.cac_receipt_document_reference.as_ref(),
+        )
+        .map(|id| RamReceivingAdviceReferencedDocument {
+            text: None,
+            ram_issuer_assigned_id: Some(id),
+        }),
+    }
+}
+
+fn settlement(
+    invoice: &UblInvoice,
+    currency: Option<String>,
+) -> RamApplicableHeaderTradeSettlement {
+    let seller = invoice
+        .cac_accounting_supplier_party
+        .as_ref()
+        .and_then(|party| party.cac_party.as_ref());
+    let creditor_reference_id = seller
+        .into_iter()
+        .chain(invoice.cac_payee_party.as_ref())
+        .flat_map(|party| party.cac_party_identification.iter())
+        .filter_map(|identification| identification.cbc_id.as_ref())
+        .find(|id| id.scheme_id.as_deref() == Some("SEPA"))
+        .and_then(|id| id.id.clone());
+    let due_date_type_code = invoice
+        .cac_invoice_period
+        .as_ref()
+        .and_then(|period| period.cbc_description_code.as_deref())
+        .map(cii_date_code);
+    let tax_point_date = invoice.cbc_tax_point_date.as_ref();
+    let mandate_id = invoice
+        .cac_payment_means
+        .iter()
+        .filter_map(|means| means.cac_payment_mandate.as_ref())
+        .find_map(|mandate| mandate.cbc_id.as_ref().and_then(|id| id.id.clone()));
+
+    let mut tax_total_amounts = Vec::new();
+    for tax_total in &invoice.cac_tax_total {
+        if let Some(amount) = &tax_total.cbc_tax_amount {
+            tax_total_amounts.push(RamTaxTotalAmount {
+                currency_id: amount.currency_id.clone().or(currency.clone()),
+                text: amount_text(Some(amount)),
+            });
+        }
+    }
+    let total = invoice.cac_legal_monetary_total.as_ref();
+
+    RamApplicableHeaderTradeSettlement {
+        text: None,
+        ram_creditor_reference_id: creditor_reference_id,
+        ram_payment_reference: invoice
+            .cac_payment_means
+            .iter()
+            .find_map(|means| means.cbc_payment_id.as_ref().and_then(|id| id.id.clone())),
+        ram_tax_currency_code: invoice.cbc_tax_currency_code.clone(),
+        ram_invoice_currency_code: currency,
+        ram_payee_trade_party: invoice.cac_payee_party.as_ref().map(trade_party),
+        ram_specified_trade_settlement_payment_means: invoice
+            .cac_payment_means
+            .iter()
+            .map(|means| {
+                let payee_account = means.cac_payee_financial_account.as_ref();
+                RamSpecifiedTradeSettlementPaymentMeans {
+                    text: None,
+                    ram_type_code: means
+                        .cbc_payment_means_code
+                        .as_ref()
+                        .and_then(|code| code.text.clone()),
+                    ram_information: means
+                        .cbc_payment_means_code
+                        .as_ref()
+                        .and_then(|code| code.name.clone()),
+                    ram_payer_party_debitor_financial_account: means
+                        .cac_payment_mandate
+                        .as_ref()
+                        .and_then(|mandate| mandate.cac_payer_financial_account.as_ref())
+                        .and_then(|account| account.cbc_id.clone())
+                        .map(|id| RamPayeePartyCreditorFinancialAccount {
+                            ram_ibanid: Some(id),
+                            ..Default::default()
+                        }),
+                    ram_payee_party_creditor_financial_account: payee_account.map(|account| {
+                        let id = account.cbc_id.clone();
+                        let iban = id.as_deref().is_some_and(is_iban);
+                        RamPayeePartyCreditorFinancialAccount {
+                            text: None,
+                            ram_ibanid: id.clone().filter(|_| iban),
+                            ram_account_name: account.cbc_name.clone(),
+                            ram_proprietary_id: id.filter(|_| !iban),
+                        }
+                    }),
+                    ram_payee_specified_creditor_financial_institution: payee_account
+                        .and_then(|account| account.cac_financial_institution_branch.as_ref())
+                        .and_then(|branch| branch.cbc_id.clone())
+                        .map(|bic| RamPayeeSpecifiedCreditorFinancialInstitution {
+                            text: None,
+                            ram_bicid: Some(bic),
+                        }),
+                    ram_applicable_trade_settlement_financial_card: means
+                        .cac_card_account
+                        .as_ref()
+                        .map(|card| RamApplicableTradeSettlementFinancialCard {
+                            ram_id: card
+                                .primary_account_number_id
+                                .as_ref()
+                                .and_then(|id| id.id.clone()),
+                            ram_cardholder_name: card.cbc_holder_name.clone(),
+                        }),
+                }
+            })
+            .collect(),
+        ram_applicable_trade_tax: invoice
+            .cac_tax_total
+            .iter()
+            .flat_map(|tax_total| tax_total.cac_tax_subtotal.iter())
+            .map(|subtotal| {
+                let category = subtotal.cac_tax_category.as_ref();
+                ApplicableTradeTax {
+                    text: None,
+                    ram_calculated_amount: amount_text(subtotal.cbc_tax_amount.as_ref()),
+                    ram_type_code: Some(VAT_SCHEME.to_string()),
+                    ram_exemption_reason: category
+                        .filter(|category| !category.cbc_tax_exemption_reason.is_empty())
+                        .map(|category| category.cbc_tax_exemption_reason.join("\n")),
+                    ram_basis_amount: amount_text(subtotal.cbc_taxable_amount.as_ref()),
+                    ram_category_code: category.and_then(|category| category.cbc_id.clone()),
+                    ram_due_date_type_code: due_date_type_code.clone(),
+                    ram_exemption_reason_code: category
+                        .and_then(|category| category.cbc_tax_exemption_reason_code.clone()),
+                    ram_tax_point_date: tax_point_date.map(|date| RamTaxPointDate {
+                        text: None,
+                        udt_date_string: Some(DateString {
+                            format: Some("102".to_string()),
+                            text: Some(date.date().format("%Y%m%d").to_string()),
+                        }),
+                    }),
+                    ram_rate_applicable_percent: category
+                        .and_then(|category| category.cbc_percent.clone()),
+                }
+            })
+            .collect(),
+        ram_billing_specified_period: invoice.cac_invoice_period.as_ref().and_then(|period| {
+            (period.cbc_start_date.is_some() || period.cbc_end_date.is_some()).then(|| {
+                RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriod {
+                    text: None,
+                    ram_start_date_time: period.cbc_start_date.as_ref().map(|date| {
+                        RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime {
+                            text: None,
+                            udt_date_time_string: Some(date_time_string(date)),
+                        }
+                    }),
+                    ram_end_date_time: period.cbc_end_date.as_ref().map(|date| {
+                        RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime {
+                            text: None,
+                            udt_date_time_string: Some(date_time_string(date)),
+                        }
+                    }),
+                }
+            })
+        }),
+        ram_specified_trade_allowance_charge: invoice
+            .cac_allowance_charge
+            .iter()
+            .map(|allowance_charge| {
+                let category = allowance_charge.cac_tax_category.first();
+                RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceCharge {
+                    text: None,
+                    ram_charge_indicator: Some(
+                        RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator {
+                            text: None,
+                            udt_indicator: Some(charge_indicator(allowance_charge)),
+                        },
+                    ),
+                    ram_calculation_percent: allowance_charge
+                        .cbc_multiplier_factor_numeric
+                        .map(|factor| factor.to_string()),
+                    ram_basis_amount: amount_text(allowance_charge.cbc_base_amount.as_ref()),
+                    ram_actual_amount: amount_text(allowance_charge.amount.as_ref()),
+                    ram_reason_code: allowance_charge.cbc_allowance_charge_reason_code.clone(),
+                    ram_reason: allowance_charge.cbc_allowance_charge_reason.clone(),
+                    ram_category_trade_tax: category.map(|category| RamCategoryTradeTax {
+                        text: None,
+                        ram_type_code: Some(VAT_SCHEME.to_string()),
+                        ram_category_code: category.cbc_id.clone(),
+                        ram_rate_applicable_percent: category.cbc_percent.clone(),
+                    }),
+                }
+            })
+            .collect(),
+        ram_specified_trade_payment_terms: (invoice.cbc_due_date.is_some()
+            || mandate_id.is_some()
+            || invoice
+                .cac_payment_terms
+                .as_ref()
+                .is_some_and(|terms| terms.cbc_note.is_some()))
+        .then(|| RamSpecifiedTradePaymentTerms {
+            text: None,
+            ram_description: invoice
+                .cac_payment_terms
+                .as_ref()
+                .and_then(|terms| terms.cbc_note.clone()),
+            ram_due_date_date_time: invoice.cbc_due_date.as_ref().map(|date| {
+                RamDueDateDateTime {
+                    text: None,
+                    udt_date_time_string: Some(date_time_string(date)),
+                }
+            }),
+            ram_direct_debit_mandate_id: mandate_id,
+        }),
+        ram_specified_trade_settlement_header_monetary_summation: total.map(|total| {
+            RamSpecifiedTradeSettlementHeaderMonetarySummation {
+                text: None,
+                ram_line_total_amount: amount_text(total.cbc_line_extension_amount.as_ref()),
+                ram_charge_total_amount: amount_text(total.cbc_charge_total_amount.as_ref()),
+                ram_allowance_total_amount: amount_text(
+                    total.cbc_allowance_total_amount.as_ref(),
+                ),
+                ram_tax_basis_total_amount: amount_text(total.cbc_tax_exclusive_amount.as_ref()),
+                ram_tax_total_amount: tax_total_amounts,
+                ram_rounding_amount: amount_text(total.cbc_payable_rounding_amount.as_ref()),
+                ram_grand_total_amount: amount_text(total.cbc_tax_inclusive_amount.as_ref()),
+                ram_total_prepaid_amount: amount_text(total.cbc_prepaid_amount.as_ref()),
+                ram_due_payable_amount: amount_text(total.payable_amount.as_ref()),
+            }
+        }),
+        ram_invoice_referenced_document: invoice
+            .cac_billing_reference
+            .iter()
+            .find_map(|reference| reference.cac_invoice_document_reference.as_ref())
+            .map(|reference| RamInvoiceReferencedDocument {
+                text: None,
+                ram_issuer_assigned_id: reference.id.as_ref().and_then(|id| id.id.clone()),
+                ram_formatted_issue_date_time: reference.cbc_issue_date.as_ref().map(|date| {
+                    RamFormattedIssueDateTime {
+                        text: None,
+                        qdt_date_time_string: Some(date_time_string(date)),
+                    }
+                }),
+            }),
+        ram_receivable_specified_trade_accounting_account: invoice
+            .cbc_accounting_cost
+            .as_ref()
+            .map(|accounting_cost| {
+                RamApplicableHeaderTradeSettlementRamReceivableSpecifiedTradeAccountingAccount {
+                    text: None,
+                    ram_id: Some(id_type(accounting_cost, None)),
+                }
+            }),
+    }
+}
+
+fn trade_party(party: &Party) -> TradeParty {
+    let legal_entity = party.cac_party_legal_entity.first();
+    let trading_name = party
+        .cac_party_name
+        .iter()
+        .find_map(|name| name.cbc_name.clone());
+    let registration_name = legal_entity.and_then(|entity| entity.cbc_registration_name.clone());
+    let legal_id = legal_entity.and_then(|entity| entity.cbc_company_id.as_ref());
+    let (ram_id, ram_global_id) = party_ids(
+        party
+            .cac_party_identification
+            .iter()
+            .filter_map(|identification| identification.cbc_id.as_ref())
+            .filter(|id| id.scheme_id.as_deref() != Some("SEPA")),
+    );
+    TradeParty {
+        text: None,
+        ram_id,
+        ram_global_id,
+        // Payees and tax representatives only carry a party name
+        ram_name: registration_name.clone().or(trading_name.clone()),
+        ram_description: legal_entity.and_then(|entity| entity.cbc_company_legal_form.clone()),
+        ram_specified_legal_organization: (legal_id.is_some()
+            || (registration_name.is_some() && trading_name.is_some()))
+        .then(|| SpecifiedLegalOrganization {
+            text: None,
+            ram_id: legal_id.map(|id| RamSellerTradePartyRamSpecifiedLegalOrganizationRamId {
+                scheme_id: id.scheme_id.clone(),
+                text: id.text.clone(),
+            }),
+            ram_trading_business_name: trading_name.filter(|_| registration_name.is_some()),
+        }),
+        ram_defined_trade_contact: party.cac_contact.as_ref().map(trade_contact),
+        ram_postal_trade_address: party.cac_postal_address.as_ref().map(postal_address),
+        ram_uriuniversal_communication: party.cbc_endpoint_id.as_ref().map(|endpoint| {
+            UriUniversalCommunication {
+                text: None,
+                ram_uriid: Some(
+                    RamApplicableHeaderTradeAgreementRamSellerTradePartyRamUriuniversalCommunicationRamUriid {
+                        scheme_id: endpoint.scheme_id.clone(),
+                        text: endpoint.id.clone(),
+                    },
+                ),
+            }
+        }),
+        ram_specified_tax_registration: party
+            .cac_party_tax_scheme
+            .iter()
+            .filter_map(|scheme| {
+                let vat = scheme
+                    .cac_tax_scheme
+                    .as_ref()
+                    .and_then(|tax_scheme| tax_scheme.id.as_ref())
+                    .and_then(|id| id.id.as_deref())
+                    == Some(VAT_SCHEME);
+                scheme
+                    .cbc_company_id
+                    .clone()
+                    .map(|company_id| SpecifiedTaxRegistration {
+                        text: None,
+                        ram_id: Some(RamSellerTradePartyRamSpecifiedTaxRegistrationRamId {
+                            scheme_id: Some(if vat { "VA" } else { "FC" }.to_string()),
+                            text: Some(company_id),
+                        }),
+                    })
+            })
+            .collect(),
+    }
+}
+
+/// Identifiers with a scheme are global identifiers in CII, which allows only one of them
+fn party_ids<'a>(ids: impl Iterator<Item = &'a Identifier>) -> (Vec<String>, Option<GlobalId>) {
+    let mut ram_ids = Vec::new();
+    let mut global_id = None;
+    for id in ids {
+        match (&id.scheme_id, &global_id) {
+            (Some(scheme_id), None) => {
+                global_id = Some(GlobalId {
+                    scheme_id: Some(scheme_id.clone()),
+                    text: id.id.clone(),
+                })
+            }
+            _ => ram_ids.extend(id.id.clone()),
+        }
+    }
+    (ram_ids, global_id)
+}
+
+fn trade_contact(contact: &Contact) -> DefinedTradeContact {
+    DefinedTradeContact {
+        text: None,
+        ram_person_name: contact.cbc_name.clone(),
+        ram_department_name: None,
+        ram_telephone_universal_communication: contact.cbc_telephone.clone().map(|number| {
+            RamSellerTradePartyRamDefinedTradeContactRamTelephoneUniversalCommunication {
+                text: None,
+                ram_complete_number: Some(number),
+            }
+        }),
+        ram_email_uriuniversal_communication: contact.cbc_electronic_mail.clone().map(|mail| {
+            RamSellerTradePartyRamDefinedTradeContactRamEmailUriuniversalCommunication {
+                text: None,
+                ram_uriid: Some(mail),
+            }
+        }),
+    }
+}
+
+fn postal_address(address: &Address) -> PostalTradeAddress {
+    PostalTradeAddress {
+        text: None,
+        ram_postcode_code: address.cbc_postal_zone.clone(),
+        ram_line_one: address.cbc_street_name.clone(),
+        ram_line_two: address.cbc_additional_street_name.clone(),
+        ram_line_three: address
+            .cac_address_line
+            .iter()
+            .find_map(|line| line.cbc_line.clone()),
+        ram_city_name: address.cbc_city_name.clone(),
+        ram_country_id: address
+            .cac_country
+            .as_ref()
+            .and_then(|country| country.cbc_identification_code.clone()),
+        ram_country_sub_division_name: address.cbc_country_subentity.clone(),
+    }
+}
+
+fn additional_document(
+    document: &CacDocumentReference,
+) -> RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument {
+    let attachment = document.cac_attachment.as_ref();
+    RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument {
+        ram_issuer_assigned_id: document
+            .id
+            .as_ref()
+            .and_then(|id| id.id.as_ref())
+            .map(|id| {
+                id_type(
+                    id,
+                    document.id.as_ref().and_then(|id| id.scheme_id.as_deref()),
+                )
+            }),
+        ram_uriid: attachment
+            .and_then(|attachment| attachment.cac_external_reference.as_ref())
+            .and_then(|reference| reference.cbc_uri.clone()),
+        ram_type_code: Some(
+            document
+                .cbc_document_type_code
+                .clone()
+                .unwrap_or_else(|| ADDITIONAL_DOCUMENT_TYPE_CODE.to_string()),
+        ),
+        ram_name: (!document.cbc_document_description.is_empty())
+            .then(|| document.cbc_document_description.join("\n")),
+        ram_attachment_binary_object: attachment
+            .and_then(|attachment| attachment.cbc_embedded_document_binary_object.as_ref())
+            .map(|object| RamAttachmentBinaryObject {
+                mime_code: object.mime_code.clone(),
+                filename: object.filename.clone(),
+                text: object.text.clone(),
+            }),
+    }
+}
+
+fn line_item(line: &InvoiceLine) -> RamIncludedSupplyChainTradeLineItem {
+    let item = line.cac_item.as_ref();
+    let price = line.cac_price.as_ref();
+    let price_discount = price.and_then(|price| price.cac_allowance_charge.as_ref());
+    let base_quantity = price
+        .and_then(|price| price.cbc_base_quantity.as_ref())
+        .map(|quantity| RamBasisQuantity {
+            unit_code: quantity.unit_code.clone(),
+            text: quantity.text.clone(),
+        });
+    RamIncludedSupplyChainTradeLineItem {
+        text: None,
+        ram_associated_document_line_document: Some(RamAssociatedDocumentLineDocument {
+            text: None,
+            ram_line_id: line.cbc_id.as_ref().and_then(|id| id.id.clone()),
+            ram_parent_line_id: None,
+            ram_line_status_code: None,
+            ram_line_status_reason_code: None,
+            ram_included_note: line.cbc_note.clone().map(|note| {
+                RamAssociatedDocumentLineDocumentRamIncludedNote {
+                    text: None,
+                    ram_content: Some(note),
+                }
+            }),
+        }),
+        ram_specified_trade_product: item.map(|item| RamSpecifiedTradeProduct {
+            ram_global_id: item
+                .cac_standard_item_identification
+                .as_ref()
+                .and_then(|identification| identification.cbc_id.as_ref())
+                .and_then(|id| {
+                    id.text
+                        .as_ref()
+                        .map(|text| id_type(text, id.scheme_id.as_deref()))
+                }),
+            ram_seller_assigned_id: item
+                .cac_sellers_item_identification
+                .as_ref()
+                .and_then(|identification| identification.cbc_id.as_ref())
+                .map(|id| id_type(id, None)),
+            ram_buyer_assigned_id: item
+                .cac_buyers_item_identification
+                .as_ref()
+                .and_then(|identification| identification.cbc_id.as_ref())
+                .map(|id| id_type(id, None)),
+            text: None,
+            ram_name: item.cbc_name.clone(),
+            ram_description: item.cbc_description.clone(),
+            ram_applicable_product_characteristic: item
+                .cac_additional_item_property
+                .iter()
+                .map(|property| RamApplicableProductCharacteristic {
+                    text: None,
+                    ram_description: property.cbc_name.clone(),
+                    ram_value: property.cbc_value.clone(),
+                })
+                .collect(),
+            ram_designated_product_classification: item
+                .cac_commodity_classification
+                .iter()
+                .filter_map(|classification| classification.cbc_item_classification_code.as_ref())
+                .map(|code| RamDesignatedProductClassification {
+                    text: None,
+                    ram_class_code: Some(RamClassCode {
+                        list_id: code.list_id.clone(),
+                        list_version_id: code.list_version_id.clone(),
+                        text: code.text.clone(),
+                    }),
+                })
+                .collect(),
+            ram_origin_trade_country: item
+                .cac_origin_country
+                .as_ref()
+                .and_then(|country| country.cbc_identification_code.as_ref())
+                .map(|code| RamOriginTradeCountry {
+                    text: None,
+                    ram_id: Some(id_type(code, None)),
+                }),
+        }),
+        ram_specified_line_trade_agreement: Some(RamSpecifiedLineTradeAgreement {
+            ram_buyer_order_referenced_document: line
+                .cac_order_line_reference
+                .as_ref()
+                .and_then(|reference| reference.cbc_line_id.clone())
+                .map(
+                    |line_id| RamSpecifiedLineTradeAgreementRamBuyerOrderReferencedDocument {
+                        text: None,
+                        ram_line_id: Some(line_id),
+                    },
+                ),
+            ram_gross_price_product_trade_price: price_discount
+                .filter(|discount| discount.cbc_base_amount.is_some())
+                .map(|discount| RamGrossPriceProductTradePrice {
+                    text: None,
+                    ram_charge_amount: amount_text(discount.cbc_base_amount.as_ref()),
+                    ram_basis_quantity: price
+                        .and_then(|price| price.cbc_base_quantity.as_ref())
+                        .map(|quantity| RamBasisQuantity {
+                            unit_code: quantity.unit_code.clone(),
+                            text: quantity.text.clone(),
+                        }),
+                    ram_applied_trade_allowance_charge: discount.amount.as_ref().map(|amount| {
+                        RamAppliedTradeAllowanceCharge {
+                            text: None,
+                            ram_charge_indicator: Some(
+                                RamGrossPriceProductTradePriceRamAppliedTradeAllowanceChargeRamChargeIndicator {
+                                    text: None,
+                                    udt_indicator: Some("false".to_string()),
+                                },
+                            ),
+                            ram_actual_amount: amount_text(Some(amount)),
+                        }
+                    }),
+                }),
+            ram_net_price_product_trade_price: price.map(|price| RamNetPriceProductTradePrice {
+                text: None,
+                ram_charge_amount: amount_text(price.cbc_price_amount.as_ref()),
+                ram_basis_quantity: base_quantity,
+            }),
+        }),
+        ram_specified_line_trade_delivery: Some(RamSpecifiedLineTradeDelivery {
+            text: None,
+            ram_billed_quantity: line.cbc_invoiced_quantity.as_ref().map(|quantity| {
+                RamBilledQuantity {
+                    unit_code: quantity.unit_code.clone(),
+                    text: quantity.text.clone(),
+                }
+            }),
+        }),
+        ram_specified_line_trade_settlement: Some(RamSpecifiedLineTradeSettlement {
+            text: None,
+            ram_applicable_trade_tax: item
+                .and_then(|item| item.cac_classified_tax_category.as_ref())
+                .map(line_trade_tax),
+            ram_billing_specified_period: line.cac_invoice_period.as_ref().map(line_period),
+            ram_specified_trade_allowance_charge: line
+                .cac_allowance_charge
+                .iter()
+                .map(|allowance_charge| {
+                    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceCharge {
+                        text: None,
+                        ram_charge_indicator: Some(
+                            RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator {
+                                text: None,
+                                udt_indicator: Some(charge_indicator(allowance_charge)),
+                            },
+                        ),
+                        ram_calculation_percent: allowance_charge
+                            .cbc_multiplier_factor_numeric
+                            .map(|factor| factor.to_string()),
+                        ram_basis_amount: amount_text(allowance_charge.cbc_base_amount.as_ref()),
+                        ram_actual_amount: amount_text(allowance_charge.amount.as_ref()),
+                        ram_reason_code: allowance_charge.cbc_allowance_charge_reason_code.clone(),
+                        ram_reason: allowance_charge.cbc_allowance_charge_reason.clone(),
+                    }
+                })
+                .collect(),
+            ram_specified_trade_settlement_line_monetary_summation: Some(
+                RamSpecifiedTradeSettlementLineMonetarySummation {
+                    text: None,
+                    ram_line_total_amount: amount_text(line.cbc_line_extension_amount.as_ref()),
+                },
+            ),
+            ram_additional_referenced_document: line
+                .cac_document_reference
+                .as_ref()
+                .and_then(|reference| reference.id.as_ref())
+                .map(
+                    |id| RamSpecifiedLineTradeSettlementRamAdditionalReferencedDocument {
+                        text: None,
+                        ram_issuer_assigned_id: id.id.clone(),
+                        ram_type_code: Some(INVOICED_OBJECT_TYPE_CODE.to_string()),
+                        ram_reference_type_code: id.scheme_id.clone(),
+                    },
+                ),
+            ram_receivable_specified_trade_accounting_account: line
+                .cbc_accounting_cost
+                .as_ref()
+                .map(|accounting_cost| {
+                    RamSpecifiedLineTradeSettlementRamReceivableSpecifiedTradeAccountingAccount {
+                        text: None,
+                        ram_id: Some(id_type(accounting_cost, None)),
+                    }
+                }),
+        }),
+    }
+}
+
+fn line_trade_tax(category: &TaxCategory) -> ApplicableTradeTax {
+    ApplicableTradeTax {
+        ram_type_code: Some(VAT_SCHEME.to_string()),
+        ram_category_code: category.cbc_id.clone(),
+        ram_rate_applicable_percent: category.cbc_percent.clone(),
+        ..Default::default()
+    }
+}
+
+fn line_period(period: &Period) -> RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriod {
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriod {
+        text: None,
+        ram_start_date_time: period.cbc_start_date.as_ref().map(|date| {
+            RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime {
+                text: None,
+                udt_date_time_string: Some(date_time_string(date)),
+            }
+        }),
+        ram_end_date_time: period.cbc_end_date.as_ref().map(|date| {
+            RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime {
+                text: None,
+                udt_date_time_string: Some(date_time_string(date)),
+            }
+        }),
+    }
+}
+
+fn charge_indicator(allowance_charge: &CacAllowanceCharge) -> String {
+    allowance_charge
+        .charge_indicator
+        .unwrap_or(false)
+        .to_string()
+}
+
+fn document_id(reference: Option<&CacDocumentReference>) -> Option<String> {
+    reference
+        .and_then(|reference| reference.id.as_ref())
+        .and_then(|id| id.id.clone())
+}
+
+fn id_type(id: &str, scheme_id: Option<&str>) -> IdType {
+    IdType {
+        scheme_id: scheme_id.map(ToString::to_string),
+        id: Some(id.to_string()),
+    }
+}
+
+fn amount_text(amount: Option<&Amount>) -> Option<String> {
+    amount
+        .and_then(|amount| amount.value)
+        .map(|value| value.to_string())
+}
+
+fn date_time_string(date: &Iso8601Date) -> DateTimeString {
+    DateTimeString {
+        format: "102".to_string(),
+        text: date.date().format("%Y%m%d").to_string(),
+    }
+}
@@ -0,0 +1,719 @@
+use crate::cii::cii_model::{
+    ApplicableTradeTax, DateTimeString, DefinedTradeContact, GlobalId, IdType, PostalTradeAddress,
+    RamActualDeliverySupplyChainEvent, RamApplicableHeaderTradeAgreement,
+    RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument,
+    RamApplicableHeaderTradeAgreementRamBuyerOrderReferencedDocument,
+    RamApplicableHeaderTradeDelivery, RamApplicableHeaderTradeSettlement,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriod,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime,
+    RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime,
+    RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceCharge,
+    RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator,
+    RamAppliedTradeAllowanceCharge, RamAssociatedDocumentLineDocument,
+    RamAssociatedDocumentLineDocumentRamIncludedNote, RamBasisQuantity, RamBilledQuantity,
+    RamBusinessProcessSpecifiedDocumentContextParameter, RamCategoryTradeTax,
+    RamContractReferencedDocument, RamDespatchAdviceReferencedDocument, RamDueDateDateTime,
+    RamGrossPriceProductTradePrice,
+    RamGrossPriceProductTradePriceRamAppliedTradeAllowanceChargeRamChargeIndicator,
+    RamGuidelineSpecifiedDocumentContextParameter, RamIncludedSupplyChainTradeLineItem,
+    RamIssueDateTime, RamNetPriceProductTradePrice, RamOccurrenceDateTime,
+    RamPayeePartyCreditorFinancialAccount, RamPayeeSpecifiedCreditorFinancialInstitution,
+    RamReceivingAdviceReferencedDocument,
+    RamSellerTradePartyRamDefinedTradeContactRamEmailUriuniversalCommunication,
+    RamSellerTradePartyRamDefinedTradeContactRamTelephoneUniversalCommunication,
+    RamSellerTradePartyRamSpecifiedTaxRegistrationRamId, RamSpecifiedLineTradeAgreement,
+    RamSpecifiedLineTradeAgreementRamBuyerOrderReferencedDocument, RamSpecifiedLineTradeDelivery,
+    RamSpecifiedLineTradeSettlement, RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriod,
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime,
+    RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime,
+    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceCharge,
+    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator,
+    RamSpecifiedTradePaymentTerms, RamSpecifiedTradeProduct,
+    RamSpecifiedTradeSettlementHeaderMonetarySummation,
+    RamSpecifiedTradeSettlementLineMonetarySummation, RamSpecifiedTradeSettlementPaymentMeans,
+    RamTaxTotalAmount, RsmExchangedDocument, RsmExchangedDocumentContext,
+    RsmExchangedDocumentRamIncludedNote, RsmSupplyChainTradeTransaction, SpecifiedTaxRegistration,
+    TradeParty,
+};
+use crate::cii::zugferd1_model::{
+    CrossIndustryDocument, Zugferd1AllowanceCharge, Zugferd1Amount, Zugferd1DateTime,
+    Zugferd1FinancialAccount, Zugferd1Indicator, Zugferd1LineItem, Zugferd1Note, Zugferd1Period,
+    Zugferd1Quantity, Zugferd1TradeAgreement, Zugferd1TradeDelivery, Zugferd1TradeParty,
+    Zugferd1TradeSettlement, Zugferd1TradeTax,
+};
+use crate::convert::code_mapping::ADDITIONAL_DOCUMENT_TYPE_CODE;
+use crate::CrossIndustryInvoice;
+
+/// Convert a ZUGFeRD 1.0 invoice to a UN/CEFACT Cross Industry Invoice D16B
+/// # Arguments
+/// * `invoice` - The ZUGFeRD 1.0 invoice
+/// # Returns
+/// * `CrossIndustryInvoice` - The invoice in the current CII version
+///
+/// The ZUGFeRD 1.0 guideline ID is kept, so the invoice is validated with the rules of its own profile.
+/// Converting it with `InvoiceStandard::convert` or embedding it into a PDF declares the succeeding Factur-X profile,
+/// see `with_facturx_guideline_id`.
+/// Logistics service charges become document level charges, repeated texts are joined by line breaks.
+/// Elements without a counterpart in `CrossIndustryInvoice`, e.g. the test indicator or the delivery note, are not converted.
+pub fn zugferd1_to_cii(invoice: &CrossIndustryDocument) -> CrossIndustryInvoice {
+    let context = invoice.rsm_specified_exchanged_document_context.as_ref();
+    let document = invoice.rsm_header_exchanged_document.as_ref();
+    let transaction = invoice
+        .rsm_specified_supply_chain_trade_transaction
+        .as_ref();
+    let guideline_id = context
+        .and_then(|context| {
+            context
+                .ram_guideline_specified_document_context_parameter
+                .as_ref()
+        })
+        .and_then(|parameter| parameter.ram_id.as_ref());
+    CrossIndustryInvoice {
+        text: None,
+        rsm_exchanged_document_context: context.map(|context| RsmExchangedDocumentContext {
+            text: None,
+            ram_business_process_specified_document_context_parameter: context
+                .ram_business_process_specified_document_context_parameter
+                .as_ref()
+                .and_then(|parameter| parameter.ram_id.as_ref())
+                .map(|id| RamBusinessProcessSpecifiedDocumentContextParameter {
+                    text: None,
+                    ram_id: Some(id_type(id, None)),
+                }),
+            ram_guideline_specified_document_context_parameter: guideline_id.map(|id| {
+                RamGuidelineSpecifiedDocumentContextParameter {
+                    text: None,
+                    id: Some(id_type(id, None)),
+                }
+            }),
+        }),
+        rsm_exchanged_document: document.map(|document| RsmExchangedDocument {
+            text: None,
+            ram_id: document.ram_id.as_ref().map(|id| id_type(id, None)),
+            ram_type_code: document.ram_type_code.clone(),
+            ram_issue_date_time: date_time_string(document.ram_issue_date_time.as_ref()).map(
+                |date_time| RamIssueDateTime {
+                    text: None,
+                    udt_date_time_string: Some(date_time),
+                },
+            ),
+            ram_included_note: document
+                .ram_included_note
+                .iter()
+                .map(|note| RsmExchangedDocumentRamIncludedNote {
+                    text: None,
+                    ram_content: note_content(note),
+                    ram_subject_code: note.ram_subject_code.clone(),
+                })
+                .collect(),
+        }),
+        rsm_supply_chain_trade_transaction: transaction.map(|transaction| {
+            RsmSupplyChainTradeTransaction {
+                text: None,
+                ram_included_supply_chain_trade_line_item: transaction
+                    .ram_included_supply_chain_trade_line_item
+                    .iter()
+                    .map(line_item)
+                    .collect(),
+                ram_applicable_header_trade_agreement: transaction
+                    .ram_applicable_supply_chain_trade_agreement
+                    .as_ref()
+                    .map(agreement),
+                ram_applicable_header_trade_delivery: transaction
+                    .ram_applicable_supply_chain_trade_delivery
+                    .as_ref()
+                    .map(delivery),
+                ram_applicable_header_trade_settlement: transaction
+                    .ram_applicable_supply_chain_trade_settlement
+                    .as_ref()
+                    .map(settlement),
+            }
+        }),
+        unknown_elements: Vec::new(),
+    }
+}
+
+/// Replace the guideline ID of an invoice converted from ZUGFeRD 1.0 by that of the succeeding Factur-X profile
+/// # Arguments
+/// * `invoice` - The invoice
+/// # Returns
+/// * `CrossIndustryInvoice` - The invoice declaring the Factur-X BASIC, EN 16931 or EXTENDED profile
+///   instead of the ZUGFeRD 1.0 BASIC, COMFORT or EXTENDED profile, other guideline IDs are kept
+///
+/// Used when an invoice is written in another syntax or as CII D16B into a PDF,
+/// where the ZUGFeRD 1.0 guideline IDs are not valid.
+pub(crate) fn with_facturx_guideline_id(mut invoice: CrossIndustryInvoice) -> CrossIndustryInvoice {
+    if let Some(id) = invoice
+        .rsm_exchanged_document_context
+        .as_mut()
+        .and_then(|context| {
+            context
+                .ram_guideline_specified_document_context_parameter
+                .as_mut()
+        })
+        .and_then(|parameter| parameter.id.as_mut())
+        .and_then(|id| id.id.as_mut())
+    {
+        *id = facturx_guideline_id(id).to_string();
+    }
+    invoice
+}
+
+/// The Factur-X guideline ID succeeding a ZUGFeRD 1.0 one, other IDs are kept
+fn facturx_guideline_id(id: &str) -> &str {
+    match id.trim() {
+        "urn:ferd:CrossIndustryDocument:invoice:1p0:basic" => {
+            "urn:cen.eu:en16931:2017#compliant#urn:factur-x.eu:1p0:basic"
+        }
+        "urn:ferd:CrossIndustryDocument:invoice:1p0:comfort" => "urn:cen.eu:en16931:2017",
+        "urn:ferd:CrossIndustryDocument:invoice:1p0:extended" => {
+            "urn:cen.eu:en16931:2017#conformant#urn:factur-x.eu:1p0:extended"
+        }
+        _ => id,
+    }
+}
+
+fn agreement(agreement: &Zugferd1TradeAgreement) -> RamApplicableHeaderTradeAgreement {
+    RamApplicableHeaderTradeAgreement {
+        ram_buyer_reference: joined(&agreement.ram_buyer_reference),
+        ram_seller_trade_party: agreement.ram_seller_trade_party.as_ref().map(trade_party),
+        ram_buyer_trade_party: agreement.ram_buyer_trade_party.as_ref().map(trade_party),
+        ram_buyer_order_referenced_document: agreement
+            .ram_buyer_order_referenced_document
+            .as_ref()
+            .map(
+                |document| RamApplicableHeaderTradeAgreementRamBuyerOrderReferencedDocument {
+                    text: None,
+                    ram_issuer_assigned_id: document.ram_id.first().cloned(),
+                },
+            ),
+        ram_contract_referenced_document: agreement.ram_contract_referenced_document.as_ref().map(
+            |document| RamContractReferencedDocument {
+                text: None,
+                ram_issuer_assigned_id: document.ram_id.first().cloned(),
+            },
+        ),
+        ram_additional_referenced_document: agreement
+            .ram_additional_referenced_document
+            .iter()
+            .map(
+                |document| RamApplicableHeaderTradeAgreementRamAdditionalReferencedDocument {
+                    ram_issuer_assigned_id: document.ram_id.first().map(|id| id_type(id, None)),
+                    ram_type_code: Some(
+                        document
+                            .ram_type_code
+                            .clone()
+                            .unwrap_or_else(|| ADDITIONAL_DOCUMENT_TYPE_CODE.to_string()),
+                    ),
+                    ..Default::default()
+                },
+            )
+            .collect(),
+        ..Default::default()
+    }
+}
+
+fn trade_party(party: &Zugferd1TradeParty) -> TradeParty {
+    TradeParty {
+        text: None,
+        ram_id: party.ram_id.clone(),
+        ram_global_id: party.ram_global_id.first().map(|id| GlobalId {
+            scheme_id: id.scheme_id.clone(),
+            text: id.text.clone(),
+        }),
+        ram_name: party.ram_name.clone(),
+        ram_description: None,
+        ram_specified_legal_organization: None,
+        ram_defined_trade_contact: party.ram_defined_trade_contact.as_ref().map(|contact| {
+            DefinedTradeContact {
+                text: None,
+                ram_person_name: contact.ram_person_name.clone(),
+                ram_department_name: contact.ram_department_name.clone(),
+                ram_telephone_universal_communication: contact
+                    .ram_telephone_universal_communication
+                    .as_ref()
+                    .map(|telephone| {
+                        RamSellerTradePartyRamDefinedTradeContactRamTelephoneUniversalCommunication {
+                            text: None,
+                            ram_complete_number: telephone.ram_complete_number.clone(),
+                        }
+                    }),
+                ram_email_uriuniversal_communication: contact
+                    .ram_email_uriuniversal_communication
+                    .as_ref()
+                    .map(|email| {
+                        RamSellerTradePartyRamDefinedTradeContactRamEmailUriuniversalCommunication {
+                            text: None,
+                            ram_uriid: email.ram_uriid.clone(),
+                        }
+                    }),
+            }
+        }),
+        ram_postal_trade_address: party.ram_postal_trade_address.as_ref().map(|address| {
+            PostalTradeAddress {
+                text: None,
+                ram_postcode_code: address.ram_postcode_code.clone(),
+                ram_line_one: address.ram_line_one.clone(),
+                ram_line_two: address.ram_line_two.clone(),
+                ram_line_three: None,
+                ram_city_name: address.ram_city_name.clone(),
+                ram_country_id: address.ram_country_id.clone(),
+                ram_country_sub_division_name: None,
+            }
+        }),
+        ram_uriuniversal_communication: None,
+        ram_specified_tax_registration: party
+            .ram_specified_tax_registration
+            .iter()
+            .filter_map(|registration| registration.ram_id.as_ref())
+            .map(|id| SpecifiedTaxRegistration {
+                text: None,
+                ram_id: Some(RamSellerTradePartyRamSpecifiedTaxRegistrationRamId {
+                    scheme_id: id.scheme_id.clone(),
+                    text: id.text.clone(),
+                }),
+            })
+            .collect(),
+    }
+}
+
+fn delivery(delivery: &Zugferd1TradeDelivery) -> RamApplicableHeaderTradeDelivery {
+    RamApplicableHeaderTradeDelivery {
+        text: None,
+        ram_ship_to_trade_party: delivery.ram_ship_to_trade_party.as_ref().map(trade_party),
+        ram_actual_delivery_supply_chain_event: date_time_string(
+            delivery
+                .ram_actual_delivery_supply_chain_event
+                .as_ref()
+                .and_then(|event| event.ram_occurrence_date_time.as_ref()),
+        )
+        .map(|date_time| RamActualDeliverySupplyChainEvent {
+            text: None,
+            ram_occurrence_date_time: Some(RamOccurrenceDateTime {
+                text: None,
+                udt_date_time_string: Some(date_time),
+            }),
+        }),
+        ram_despatch_advice_referenced_document: delivery
+            .ram_despatch_advice_referenced_document
+            .as_ref()
+            .map(|document| RamDespatchAdviceReferencedDocument {
+                text: None,
+                ram_issuer_assigned_id: document.ram_id.first().cloned(),
+            }),
+        ram_receiving_advice_referenced_document: delivery
+            .ram_receiving_advice_referenced_document
+            .as_ref()
+            .map(|document| RamReceivingAdviceReferencedDocument {
+                text: None,
+                ram_issuer_assigned_id: document.ram_id.first().cloned(),
+            }),
+    }
+}
+
+fn settlement(settlement: &Zugferd1TradeSettlement) -> RamApplicableHeaderTradeSettlement {
+    let mut allowance_charges = settlement
+        .ram_specified_trade_allowance_charge
+        .iter()
+        .map(document_allowance_charge)
+        .collect::<Vec<_>>();
+    allowance_charges.extend(
+        settlement
+            .ram_specified_logistics_service_charge
+            .iter()
+            .map(|charge| {
+                document_allowance_charge(&Zugferd1AllowanceCharge {
+                    ram_charge_indicator: Some(Zugferd1Indicator {
+                        text: None,
+                        udt_indicator: Some("true".to_string()),
+                    }),
+                    ram_actual_amount: charge.ram_applied_amount.clone(),
+                    ram_reason: joined(&charge.ram_description),
+                    ram_category_trade_tax: charge.ram_applied_trade_tax.clone(),
+                    ..Default::default()
+                })
+            }),
+    );
+    let terms = &settlement.ram_specified_trade_payment_terms;
+    let summation = settlement
+        .ram_specified_trade_settlement_monetary_summation
+        .as_ref();
+    RamApplicableHeaderTradeSettlement {
+        text: None,
+        ram_creditor_reference_id: None,
+        ram_payment_reference: joined(&settlement.ram_payment_reference),
+        ram_tax_currency_code: None,
+        ram_invoice_currency_code: settlement.ram_invoice_currency_code.clone(),
+        ram_payee_trade_party: settlement.ram_payee_trade_party.as_ref().map(trade_party),
+        ram_specified_trade_settlement_payment_means: settlement
+            .ram_specified_trade_settlement_payment_means
+            .iter()
+            .map(|means| RamSpecifiedTradeSettlementPaymentMeans {
+                text: None,
+                ram_type_code: means.ram_type_code.clone(),
+                ram_information: joined(&means.ram_information),
+                ram_payer_party_debitor_financial_account: means
+                    .ram_payer_party_debtor_financial_account
+                    .as_ref()
+                    .map(financial_account),
+                ram_payee_party_creditor_financial_account: means
+                    .ram_payee_party_creditor_financial_account
+                    .as_ref()
+                    .map(financial_account),
+                ram_payee_specified_creditor_financial_institution: means
+                    .ram_payee_specified_creditor_financial_institution
+                    .as_ref()
+                    .and_then(|institution| institution.ram_bicid.clone())
+                    .map(|bic| RamPayeeSpecifiedCreditorFinancialInstitution {
+                        text: None,
+                        ram_bicid: Some(bic),
+                    }),
+                ram_applicable_trade_settlement_financial_card: None,
+            })
+            .collect(),
+        ram_applicable_trade_tax: settlement
+            .ram_applicable_trade_tax
+            .iter()
+            .map(trade_tax)
+            .collect(),
+        ram_billing_specified_period: settlement.ram_billing_specified_period.as_ref().map(
+            |period| {
+                let (start, end) = period_date_times(period);
+                RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriod {
+                    text: None,
+                    ram_start_date_time: start.map(|date_time| {
+                        RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime {
+                            text: None,
+                            udt_date_time_string: Some(date_time),
+                        }
+                    }),
+                    ram_end_date_time: end.map(|date_time| {
+                        RamApplicableHeaderTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime {
+                            text: None,
+                            udt_date_time_string: Some(date_time),
+                        }
+                    }),
+                }
+            },
+        ),
+        ram_specified_trade_allowance_charge: allowance_charges,
+        ram_specified_trade_payment_terms: (!terms.is_empty()).then(|| {
+            RamSpecifiedTradePaymentTerms {
+                text: None,
+                ram_description: joined(
+                    &terms
+                        .iter()
+                        .flat_map(|terms| terms.ram_description.iter().cloned())
+                        .collect::<Vec<_>>(),
+                ),
+                ram_due_date_date_time: date_time_string(
+                    terms
+                        .iter()
+                        .find_map(|terms| terms.ram_due_date_date_time.as_ref()),
+                )
+                .map(|date_time| RamDueDateDateTime {
+                    text: None,
+                    udt_date_time_string: Some(date_time),
+                }),
+                ram_direct_debit_mandate_id: None,
+            }
+        }),
+        ram_specified_trade_settlement_header_monetary_summation: summation.map(|summation| {
+            RamSpecifiedTradeSettlementHeaderMonetarySummation {
+                text: None,
+                ram_line_total_amount: amount(&summation.ram_line_total_amount),
+                ram_charge_total_amount: amount(&summation.ram_charge_total_amount),
+                ram_allowance_total_amount: amount(&summation.ram_allowance_total_amount),
+                ram_tax_basis_total_amount: amount(&summation.ram_tax_basis_total_amount),
+                ram_tax_total_amount: summation
+                    .ram_tax_total_amount
+                    .iter()
+                    .map(|total| RamTaxTotalAmount {
+                        // BR-CO-15 needs the currency of the tax total, which ZUGFeRD 1.0 may omit
+                        currency_id: total
+                            .currency_id
+                            .clone()
+                            .or_else(|| settlement.ram_invoice_currency_code.clone()),
+                        text: total.text.clone(),
+                    })
+                    .collect(),
+                ram_rounding_amount: None,
+                ram_grand_total_amount: amount(&summation.ram_grand_total_amount),
+                ram_total_prepaid_amount: amount(&summation.ram_total_prepaid_amount),
+                ram_due_payable_amount: amount(&summation.ram_due_payable_amount),
+            }
+        }),
+        ram_invoice_referenced_document: None,
+        ram_receivable_specified_trade_accounting_account: None,
+    }
+}
+
+fn document_allowance_charge(
+    allowance_charge: &Zugferd1AllowanceCharge,
+) -> RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceCharge {
+    RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceCharge {
+        text: None,
+        ram_charge_indicator: charge_indicator(allowance_charge).map(|indicator| {
+            RamApplicableHeaderTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator {
+                text: None,
+                udt_indicator: Some(indicator),
+            }
+        }),
+        ram_calculation_percent: allowance_charge.ram_calculation_percent.clone(),
+        ram_basis_amount: amount(&allowance_charge.ram_basis_amount),
+        ram_actual_amount: amount(&allowance_charge.ram_actual_amount),
+        ram_reason_code: allowance_charge.ram_reason_code.clone(),
+        ram_reason: allowance_charge.ram_reason.clone(),
+        ram_category_trade_tax: allowance_charge.ram_category_trade_tax.first().map(|tax| {
+            RamCategoryTradeTax {
+                text: None,
+                ram_type_code: tax.ram_type_code.clone(),
+                ram_category_code: tax.ram_category_code.clone(),
+                ram_rate_applicable_percent: tax.ram_applicable_percent.clone(),
+            }
+        }),
+    }
+}
+
+fn line_item(item: &Zugferd1LineItem) -> RamIncludedSupplyChainTradeLineItem {
+    let agreement = item.ram_specified_supply_chain_trade_agreement.as_ref();
+    let settlement = item.ram_specified_supply_chain_trade_settlement.as_ref();
+    RamIncludedSupplyChainTradeLineItem {
+        text: None,
+        ram_associated_document_line_document: item
+            .ram_associated_document_line_document
+            .as_ref()
+            .map(|document| RamAssociatedDocumentLineDocument {
+                text: None,
+                ram_line_id: document.ram_line_id.clone(),
+                ram_parent_line_id: None,
+                ram_line_status_code: None,
+                ram_line_status_reason_code: None,
+                ram_included_note: joined(
+                    &document
+                        .ram_included_note
+                        .iter()
+                        .filter_map(note_content)
+                        .collect::<Vec<_>>(),
+                )
+                .map(|content| RamAssociatedDocumentLineDocumentRamIncludedNote {
+                    text: None,
+                    ram_content: Some(content),
+                }),
+            }),
+        ram_specified_trade_product: item.ram_specified_trade_product.as_ref().map(|product| {
+            RamSpecifiedTradeProduct {
+                ram_global_id: product
+                    .ram_global_id
+                    .first()
+                    .and_then(|id| id.text.as_ref().map(|text| (text, id)))
+                    .map(|(text, id)| id_type(text, id.scheme_id.clone())),
+                ram_seller_assigned_id: product
+                    .ram_seller_assigned_id
+                    .as_ref()
+                    .map(|id| id_type(id, None)),
+                ram_buyer_assigned_id: product
+                    .ram_buyer_assigned_id
+                    .as_ref()
+                    .map(|id| id_type(id, None)),
+                ram_name: product.ram_name.clone(),
+                ram_description: product.ram_description.clone(),
+                ..Default::default()
+            }
+        }),
+        ram_specified_line_trade_agreement: agreement.map(|agreement| {
+            RamSpecifiedLineTradeAgreement {
+                ram_buyer_order_referenced_document: agreement
+                    .ram_buyer_order_referenced_document
+                    .as_ref()
+                    .and_then(|document| document.ram_line_id.clone())
+                    .map(
+                        |line_id| RamSpecifiedLineTradeAgreementRamBuyerOrderReferencedDocument {
+                            text: None,
+                            ram_line_id: Some(line_id),
+                        },
+                    ),
+                ram_gross_price_product_trade_price: agreement
+                    .ram_gross_price_product_trade_price
+                    .as_ref()
+                    .map(|price| RamGrossPriceProductTradePrice {
+                        text: None,
+                        ram_charge_amount: amount(&price.ram_charge_amount),
+                        ram_basis_quantity: basis_quantity(&price.ram_basis_quantity),
+                        ram_applied_trade_allowance_charge: price
+                            .ram_applied_trade_allowance_charge
+                            .first()
+                            .map(|allowance_charge| RamAppliedTradeAllowanceCharge {
+                                text: None,
+                                ram_charge_indicator: charge_indicator(allowance_charge).map(
+                                    |indicator| {
+                                        RamGrossPriceProductTradePriceRamAppliedTradeAllowanceChargeRamChargeIndicator {
+                                            text: None,
+                                            udt_indicator: Some(indicator),
+                                        }
+                                    },
+                                ),
+                                ram_actual_amount: amount(&allowance_charge.ram_actual_amount),
+                            }),
+                    }),
+                ram_net_price_product_trade_price: agreement
+                    .ram_net_price_product_trade_price
+                    .as_ref()
+                    .map(|price| RamNetPriceProductTradePrice {
+                        text: None,
+                        ram_charge_amount: amount(&price.ram_charge_amount),
+                        ram_basis_quantity: basis_quantity(&price.ram_basis_quantity),
+                    }),
+            }
+        }),
+        ram_specified_line_trade_delivery: item
+            .ram_specified_supply_chain_trade_delivery
+            .as_ref()
+            .map(|delivery| RamSpecifiedLineTradeDelivery {
+                text: None,
+                ram_billed_quantity: delivery.ram_billed_quantity.as_ref().map(|quantity| {
+                    RamBilledQuantity {
+                        unit_code: quantity.unit_code.clone(),
+                        text: quantity.text.clone(),
+                    }
+                }),
+            }),
+        ram_specified_line_trade_settlement: settlement.map(|settlement| {
+            RamSpecifiedLineTradeSettlement {
+                text: None,
+                ram_applicable_trade_tax: settlement.ram_applicable_trade_tax.first().map(trade_tax),
+                ram_billing_specified_period: settlement.ram_billing_specified_period.as_ref().map(
+                    |period| {
+                        let (start, end) = period_date_times(period);
+                        RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriod {
+                            text: None,
+                            ram_start_date_time: start.map(|date_time| {
+                                RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamStartDateTime {
+                                    text: None,
+                                    udt_date_time_string: Some(date_time),
+                                }
+                            }),
+                            ram_end_date_time: end.map(|date_time| {
+                                RamSpecifiedLineTradeSettlementRamBillingSpecifiedPeriodRamEndDateTime {
+                                    text: None,
+                                    udt_date_time_string: Some(date_time),
+                                }
+                            }),
+                        }
+                    },
+                ),
+                ram_specified_trade_allowance_charge: settlement
+                    .ram_specified_trade_allowance_charge
+                    .iter()
+                    .map(|allowance_charge| {
+                        RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceCharge {
+                            text: None,
+                            ram_charge_indicator: charge_indicator(allowance_charge).map(
+                                |indicator| {
+                                    RamSpecifiedLineTradeSettlementRamSpecifiedTradeAllowanceChargeRamChargeIndicator {
+                                        text: None,
+                                        udt_indicator: Some(indicator),
+                                    }
+                                },
+                            ),
+                            ram_calculation_percent: allowance_charge
+                                .ram_calculation_percent
+                                .clone(),
+                            ram_basis_amount: amount(&allowance_charge.ram_basis_amount),
+                            ram_actual_amount: amount(&allowance_charge.ram_actual_amount),
+                            ram_reason_code: allowance_charge.ram_reason_code.clone(),
+                            ram_reason: allowance_charge.ram_reason.clone(),
+                        }
+                    })
+                    .collect(),
+                ram_specified_trade_settlement_line_monetary_summation: settlement
+                    .ram_specified_trade_settlement_monetary_summation
+                    .as_ref()
+                    .map(|summation| RamSpecifiedTradeSettlementLineMonetarySummation {
+                        text: None,
+                        ram_line_total_amount: amount(&summation.ram_line_total_amount),
+                    }),
+                ram_additional_referenced_document: None,
+                ram_receivable_specified_trade_accounting_account: None,
+            }
+        }),
+    }
+}
+
+fn trade_tax(tax: &Zugferd1TradeTax) -> ApplicableTradeTax {
+    ApplicableTradeTax {
+        text: None,
+        ram_calculated_amount: amount(&tax.ram_calculated_amount),
+        ram_type_code: tax.ram_type_code.clone(),
+        ram_exemption_reason: tax.ram_exemption_reason.clone(),
+        ram_basis_amount: amount(&tax.ram_basis_amount),
+        ram_category_code: tax.ram_category_code.clone(),
+        ram_due_date_type_code: None,
+        ram_exemption_reason_code: None,
+        ram_tax_point_date: None,
+        ram_rate_applicable_percent: tax.ram_applicable_percent.clone(),
+    }
+}
+
+fn financial_account(account: &Zugferd1FinancialAccount) -> RamPayeePartyCreditorFinancialAccount {
+    RamPayeePartyCreditorFinancialAccount {
+        text: None,
+        ram_ibanid: account.ram_ibanid.clone(),
+        ram_account_name: account.ram_account_name.clone(),
+        ram_proprietary_id: account.ram_proprietary_id.clone(),
+    }
+}
+
+fn id_type(id: &str, scheme_id: Option<String>) -> IdType {
+    IdType {
+        scheme_id,
+        id: Some(id.to_string()),
+    }
+}
+
+fn amount(amount: &Option<Zugferd1Amount>) -> Option<String> {
+    amount.as_ref().and_then(|amount| amount.text.clone())
+}
+
+fn basis_quantity(quantity: &Option<Zugferd1Quantity>) -> Option<RamBasisQuantity> {
+    quantity.as_ref().map(|quantity| RamBasisQuantity {
+        unit_code: quantity.unit_code.clone(),
+        text: quantity.text.clone(),
+    })
+}
+
+fn charge_indicator(allowance_charge: &Zugferd1AllowanceCharge) -> Option<String> {
+    allowance_charge
+        .ram_charge_indicator
+        .as_ref()
+        .and_then(|indicator| indicator.udt_indicator.clone())
+}
+
+/// The date of a ZUGFeRD 1.0 date time, whose format is optional but always `102` in practice
+fn date_time_string(date_time: Option<&Zugferd1DateTime>) -> Option<DateTimeString> {
+    let date_time = date_time?.udt_date_time_string.as_ref()?;
+    Some(DateTimeString {
+        format: date_time
+            .format
+            .clone()
+            .unwrap_or_else(|| "102".to_string()),
+        text: date_time.text.clone()?,
+    })
+}
+
+fn period_date_times(period: &Zugferd1Period) -> (Option<DateTimeString>, Option<DateTimeString>) {
+    (
+        date_time_string(period.ram_start_date_time.as_ref()),
+        date_time_string(period.ram_end_date_time.as_ref()),
+    )
+}
+
+/// The content of a note, ZUGFeRD 1.0 allows several contents where CII D16B allows one
+fn note_content(note: &Zugferd1Note) -> Option<String> {
+    joined(&note.ram_content)
+}
+
+/// Texts joined by line breaks, `None` if there are none
+fn joined(texts: &[String]) -> Option<String> {
+    (!texts.is_empty()).then(|| texts.join("\n"))
+}
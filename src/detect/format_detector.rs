@@ -0,0 +1,245 @@
+use crate::cii::zugferd1_model::ZUGFERD1_NAMESPACE;
+use crate::{InvoiceError, InvoiceSyntax, ValidationConfig, ZugferdProfile};
+use einvoice_deps_yaserde::__xml::name::OwnedName;
+use einvoice_deps_yaserde::__xml::reader::{EventReader, XmlEvent};
+
+/// Namespaces of UBL 2.1, CII D16B and ZUGFeRD 1.0 invoices and the prefix used for them in reports
+const NAMESPACES: [(&str, &str); 9] = [
+    (
+        "ubl",
+        "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
+    ),
+    (
+        "cn",
+        "urn:oasis:names:specification:ubl:schema:xsd:CreditNote-2",
+    ),
+    (
+        "cac",
+        "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2",
+    ),
+    (
+        "cbc",
+        "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2",
+    ),
+    (
+        "ext",
+        "urn:oasis:names:specification:ubl:schema:xsd:CommonExtensionComponents-2",
+    ),
+    (
+        "rsm",
+        "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100",
+    ),
+    (
+        "ram",
+        "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100",
+    ),
+    (
+        "udt",
+        "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100",
+    ),
+    (
+        "qdt",
+        "urn:un:unece:uncefact:data:standard:QualifiedDataType:100",
+    ),
+];
+
+/// The local names on the path of the specification identifier (BT-24) of a UBL invoice
+const UBL_SPECIFICATION_IDENTIFIER: [&str; 2] = ["Invoice", "CustomizationID"];
+/// The local names on the path of the business process (BT-23) of a UBL invoice
+const UBL_BUSINESS_PROCESS: [&str; 2] = ["Invoice", "ProfileID"];
+/// The local names on the path of the specification identifier (BT-24) of a CII invoice
+const CII_SPECIFICATION_IDENTIFIER: [&str; 4] = [
+    "CrossIndustryInvoice",
+    "ExchangedDocumentContext",
+    "GuidelineSpecifiedDocumentContextParameter",
+    "ID",
+];
+/// The local names on the path of the business process (BT-23) of a CII invoice
+const CII_BUSINESS_PROCESS: [&str; 4] = [
+    "CrossIndustryInvoice",
+    "ExchangedDocumentContext",
+    "BusinessProcessSpecifiedDocumentContextParameter",
+    "ID",
+];
+/// The local names on the path of the guideline ID of a ZUGFeRD 1.0 invoice
+const ZUGFERD1_SPECIFICATION_IDENTIFIER: [&str; 4] = [
+    "CrossIndustryDocument",
+    "SpecifiedExchangedDocumentContext",
+    "GuidelineSpecifiedDocumentContextParameter",
+    "ID",
+];
+/// The local names on the path of the business process of a ZUGFeRD 1.0 invoice
+const ZUGFERD1_BUSINESS_PROCESS: [&str; 4] = [
+    "CrossIndustryDocument",
+    "SpecifiedExchangedDocumentContext",
+    "BusinessProcessSpecifiedDocumentContextParameter",
+    "ID",
+];
+
+/// What an invoice document is, as told by its root element and document context
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct DocumentInfo {
+    /// The syntax of the invoice
+    pub syntax: InvoiceSyntax,
+    /// The local name of the root element, e.g. `Invoice`, `CrossIndustryInvoice` or the ZUGFeRD 1.0 `CrossIndustryDocument`
+    pub root_element: String,
+    /// The namespace of the root element
+    pub namespace: String,
+    /// The specification identifier (BT-24), the `CustomizationID` of UBL and the guideline ID of CII and ZUGFeRD 1.0
+    pub specification_identifier: Option<String>,
+    /// The business process type (BT-23), the `ProfileID` of UBL and the business process ID of CII
+    pub business_process: Option<String>,
+    /// The profile of the specification identifier if it is known, see `ValidationConfig::find_specification`
+    ///
+    /// For UBL invoices this is the profile selecting the rules they are checked with.
+    pub profile: Option<ZugferdProfile>,
+}
+
+/// The syntax of an invoice with a root element, `None` if it is not the root of a UBL or CII invoice
+///
+/// ZUGFeRD 1.0 invoices are CII invoices, see `is_zugferd1_root`.
+pub(crate) fn root_syntax(name: &OwnedName) -> Option<InvoiceSyntax> {
+    match qualified_name(name).as_str() {
+        "ubl:Invoice" => Some(InvoiceSyntax::Ubl),
+        "rsm:CrossIndustryInvoice" => Some(InvoiceSyntax::Cii),
+        _ if is_zugferd1_root(name) => Some(InvoiceSyntax::Cii),
+        _ => None,
+    }
+}
+
+/// Whether an element is the root of a ZUGFeRD 1.0 invoice, which is converted to a `CrossIndustryInvoice` when parsed
+pub(crate) fn is_zugferd1_root(name: &OwnedName) -> bool {
+    name.local_name == "CrossIndustryDocument"
+        && name.namespace.as_deref() == Some(ZUGFERD1_NAMESPACE)
+}
+
+/// Detect the syntax and profile of an invoice without parsing it fully
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `DocumentInfo` - The syntax, root element and document context of the invoice
+/// # Errors
+/// * `InvoiceError::ParseError` - If the XML is not well-formed before the document context ends
+///   or the root element is not that of a UBL or CII invoice
+///
+/// Reading stops after the document context, the invoice lines are never read,
+/// so documents can be routed cheaply before they are validated.
+#[uniffi::export]
+pub fn detect_format(xml: &str) -> Result<DocumentInfo, InvoiceError> {
+    detect_format_with_config(xml, &ValidationConfig::default())
+}
+
+/// Detect the syntax and profile of an invoice with the specifications of a configuration
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// * `config` - The specifications known in addition to the built-in ones
+/// # Returns
+/// * `DocumentInfo` - The syntax, root element and document context of the invoice
+/// # Errors
+/// * `InvoiceError::ParseError` - If the XML is not well-formed before the document context ends
+///   or the root element is not that of a UBL or CII invoice
+#[uniffi::export]
+pub fn detect_format_with_config(
+    xml: &str,
+    config: &ValidationConfig,
+) -> Result<DocumentInfo, InvoiceError> {
+    let mut reader = EventReader::new(xml.as_bytes());
+    let mut info: Option<DocumentInfo> = None;
+    let mut stack: Vec<String> = Vec::new();
+    let mut text = String::new();
+    loop {
+        match reader
+            .next()
+            .map_err(|e| InvoiceError::ParseError(e.to_string()))?
+        {
+            XmlEvent::StartElement { name, .. } => {
+                let qualified = qualified_name(&name);
+                match &info {
+                    None => {
+                        let syntax = root_syntax(&name).ok_or_else(|| {
+                            InvoiceError::ParseError(format!(
+                                "Unknown invoice root element: {}",
+                                qualified
+                            ))
+                        })?;
+                        info = Some(DocumentInfo {
+                            syntax,
+                            root_element: name.local_name.clone(),
+                            namespace: name.namespace.clone().unwrap_or_default(),
+                            specification_identifier: None,
+                            business_process: None,
+                            profile: None,
+                        });
+                    }
+                    // The UBL header ends with the first aggregate child of the root
+                    Some(info)
+                        if info.syntax == InvoiceSyntax::Ubl
+                            && stack.len() == 1
+                            && qualified.starts_with("cac:") =>
+                    {
+                        break
+                    }
+                    Some(_) => {}
+                }
+                stack.push(name.local_name);
+                text.clear();
+            }
+            XmlEvent::Characters(characters) | XmlEvent::CData(characters) => {
+                text.push_str(&characters)
+            }
+            XmlEvent::EndElement { .. } => {
+                let Some(info) = info.as_mut() else {
+                    break;
+                };
+                let (specification_identifier, business_process): (&[&str], &[&str]) =
+                    match (info.syntax, info.root_element.as_str()) {
+                        (InvoiceSyntax::Ubl, _) => {
+                            (&UBL_SPECIFICATION_IDENTIFIER, &UBL_BUSINESS_PROCESS)
+                        }
+                        (InvoiceSyntax::Cii, "CrossIndustryDocument") => (
+                            &ZUGFERD1_SPECIFICATION_IDENTIFIER,
+                            &ZUGFERD1_BUSINESS_PROCESS,
+                        ),
+                        (InvoiceSyntax::Cii, _) => {
+                            (&CII_SPECIFICATION_IDENTIFIER, &CII_BUSINESS_PROCESS)
+                        }
+                    };
+                if stack == specification_identifier {
+                    info.specification_identifier = Some(text.trim().to_string());
+                } else if stack == business_process {
+                    info.business_process = Some(text.trim().to_string());
+                }
+                text.clear();
+                stack.pop();
+                // The CII and ZUGFeRD 1.0 document context precedes the header and the lines
+                if info.syntax == InvoiceSyntax::Cii && stack.len() == 1 {
+                    break;
+                }
+            }
+            XmlEvent::EndDocument => break,
+            _ => {}
+        }
+    }
+
+    let mut info = info.ok_or_else(|| {
+        InvoiceError::ParseError("Document does not contain a root element".to_string())
+    })?;
+    info.profile = info
+        .specification_identifier
+        .as_deref()
+        .and_then(|id| config.find_specification(id, info.syntax))
+        .map(|specification| specification.profile);
+    Ok(info)
+}
+
+/// The name of an element with the prefix of its namespace as used in reports, e.g. `cbc:IssueDate`
+pub(crate) fn qualified_name(name: &OwnedName) -> String {
+    let namespace = name.namespace.as_deref().unwrap_or_default();
+    match NAMESPACES.iter().find(|(_, uri)| *uri == namespace) {
+        Some((prefix, _)) => format!("{}:{}", prefix, name.local_name),
+        None => match &name.prefix {
+            Some(prefix) => format!("{}:{}", prefix, name.local_name),
+            None => name.local_name.clone(),
+        },
+    }
+}
@@ -0,0 +1,89 @@
+use crate::InvoiceError;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::ops::Range;
+
+/// Decode the bytes of an XML document to text
+/// # Arguments
+/// * `xml` - The XML document as read from a file, a PDF attachment or a request
+/// # Returns
+/// * `String` - The document without byte order mark, its XML declaration naming UTF-8
+/// # Errors
+/// * `InvoiceError::ParseError` - If the declared encoding is not supported or the bytes are not valid in the encoding
+///
+/// The encoding is taken from the byte order mark, else from the `<?xml` of UTF-16 documents without one,
+/// else from the `encoding` of the XML declaration, and defaults to UTF-8.
+/// Encoding names follow the WHATWG Encoding Standard, so `ISO-8859-1` is decoded as its superset windows-1252.
+pub fn decode_xml(xml: &[u8]) -> Result<String, InvoiceError> {
+    let (encoding, content) = match Encoding::for_bom(xml) {
+        Some((encoding, bom_length)) => (encoding, &xml[bom_length..]),
+        None => match xml {
+            [0x3c, 0x00, 0x3f, 0x00, ..] => (UTF_16LE, xml),
+            [0x00, 0x3c, 0x00, 0x3f, ..] => (UTF_16BE, xml),
+            _ => (declared_encoding(xml)?, xml),
+        },
+    };
+    let text = encoding
+        .decode_without_bom_handling_and_without_replacement(content)
+        .ok_or_else(|| {
+            InvoiceError::ParseError(format!("Document is not valid {}", encoding.name()))
+        })?;
+    Ok(with_utf8_declaration(&text))
+}
+
+#[uniffi::export(name = "decode_xml")]
+fn ffi_decode_xml(xml: Vec<u8>) -> Result<String, InvoiceError> {
+    decode_xml(&xml)
+}
+
+/// The encoding named by the XML declaration of a document in an ASCII compatible encoding
+fn declared_encoding(xml: &[u8]) -> Result<&'static Encoding, InvoiceError> {
+    let Some(declaration) = xml_declaration(xml) else {
+        return Ok(UTF_8);
+    };
+    let Some(range) = encoding_range(declaration) else {
+        return Ok(UTF_8);
+    };
+    let label = &declaration[range];
+    match Encoding::for_label(label.as_bytes()) {
+        // Without byte order mark and `<?xml` in UTF-16 the document cannot be UTF-16
+        Some(encoding) if encoding == UTF_16LE || encoding == UTF_16BE => {
+            Err(InvoiceError::ParseError(format!(
+                "Document declares {} but is not encoded in it",
+                label
+            )))
+        }
+        Some(encoding) => Ok(encoding),
+        None => Err(InvoiceError::ParseError(format!(
+            "Unsupported encoding: {}",
+            label
+        ))),
+    }
+}
+
+/// The XML declaration at the start of a document, which consists of ASCII characters only
+fn xml_declaration(xml: &[u8]) -> Option<&str> {
+    if !xml.starts_with(b"<?xml") {
+        return None;
+    }
+    let end = xml.windows(2).position(|window| window == b"?>")?;
+    std::str::from_utf8(&xml[..end]).ok()
+}
+
+/// The range of the value of the `encoding` pseudo-attribute in an XML declaration
+fn encoding_range(declaration: &str) -> Option<Range<usize>> {
+    let start = declaration.find("encoding")? + "encoding".len();
+    let rest = declaration[start..].trim_start();
+    let rest = rest.strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value_start = declaration.len() - rest.len() + 1;
+    let value_length = rest[1..].find(quote)?;
+    Some(value_start..value_start + value_length)
+}
+
+/// Declare the encoding of a decoded document as UTF-8, so the parser does not decode it a second time
+fn with_utf8_declaration(text: &str) -> String {
+    match xml_declaration(text.as_bytes()).and_then(encoding_range) {
+        Some(range) => format!("{}UTF-8{}", &text[..range.start], &text[range.end..]),
+        None => text.to_string(),
+    }
+}
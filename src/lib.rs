@@ -1,6 +1,11 @@
+pub mod batch {
+    pub mod batch_validator;
+}
+
 pub mod cii {
     pub mod cii_business_rule_validator;
     pub mod cii_model;
+    pub mod zugferd1_model;
 }
 
 pub mod ubl {
@@ -8,17 +13,103 @@ pub mod ubl {
     pub mod ubl_model;
 }
 
+pub mod detect {
+    pub mod format_detector;
+}
+
+pub mod encoding {
+    pub mod xml_decoder;
+}
+
+pub mod lines {
+    pub mod line_tree;
+}
+
+pub mod attachment {
+    pub mod attachment_reader;
+    pub mod attachment_writer;
+}
+
+pub mod convert {
+    pub mod cii_to_ubl;
+    mod code_mapping;
+    pub mod ubl_to_cii;
+    pub mod zugferd1_to_cii;
+}
+
+pub mod preserve {
+    pub mod unknown_elements;
+}
+
+pub mod pdf {
+    mod pdf_font;
+    pub mod pdf_metadata;
+    pub mod pdf_reader;
+    pub mod pdf_renderer;
+    pub mod pdf_validator;
+    pub mod pdf_writer;
+}
+
+pub mod render {
+    pub mod html_renderer;
+}
+
+pub mod report {
+    pub mod source_location;
+    pub mod svrl_writer;
+}
+
+pub mod rules {
+    pub mod custom_rule;
+    pub mod rule_catalogue;
+    pub mod specification_registry;
+    pub mod validation_config;
+}
+
 use crate::cii::cii_business_rule_validator;
 use crate::ubl::ubl_business_rule_validator;
+pub use attachment::attachment_reader::{invoice_attachments, InvoiceAttachment};
+pub use batch::batch_validator::{
+    validate_batch, validate_batch_with_config, BatchDocumentReport, BatchInput, BatchReport,
+    BatchStatistics, ProfileCount, RuleCount,
+};
 pub use cii::cii_model::CrossIndustryInvoice;
+pub use cii::zugferd1_model::CrossIndustryDocument;
+pub use convert::cii_to_ubl::cii_to_ubl;
+pub use convert::ubl_to_cii::ubl_to_cii;
+use convert::zugferd1_to_cii::with_facturx_guideline_id;
+pub use convert::zugferd1_to_cii::zugferd1_to_cii;
+pub use detect::format_detector::{detect_format, detect_format_with_config, DocumentInfo};
+use detect::format_detector::{is_zugferd1_root, qualified_name, root_syntax};
 use einvoice_deps_yaserde::__xml::attribute::OwnedAttribute;
 use einvoice_deps_yaserde::__xml::namespace::Namespace;
+use einvoice_deps_yaserde::__xml::reader::XmlEvent;
 use einvoice_deps_yaserde::de::Deserializer;
 use einvoice_deps_yaserde::ser::Serializer;
 use einvoice_deps_yaserde::{YaDeserialize, YaSerialize};
+pub use encoding::xml_decoder::decode_xml;
+pub use lines::line_tree::{invoice_line_tree, InvoiceLineKind, InvoiceLineNode};
 use lopdf::Document;
-use lopdf::Error;
-use lopdf::Object;
+pub use pdf::pdf_metadata::{read_facturx_metadata, FacturXMetadata};
+pub use pdf::pdf_reader::{
+    find_embedded_xml_files, find_pdf_attachments, EmbeddedXmlCandidate, PdfAttachment,
+};
+pub use pdf::pdf_renderer::{render_zugferd_pdf, render_zugferd_pdf_with_config};
+pub use pdf::pdf_validator::validate_pdf_container;
+pub use pdf::pdf_writer::{embed_invoice_into_pdf, embed_invoice_into_pdf_with_config};
+pub use preserve::unknown_elements::XmlFragment;
+use preserve::unknown_elements::{serialize_with_unknown_elements, unknown_elements};
+pub use render::html_renderer::render_html;
+pub use report::source_location::SourceLocation;
+use report::source_location::{locate_violations, syntax_error_position};
+pub use report::svrl_writer::validation_report_to_svrl;
+use rules::custom_rule::validate_custom_rules;
+pub use rules::custom_rule::{CheckedInvoice, FnRule, ForeignRule, Rule};
+pub use rules::rule_catalogue::{
+    find_rule, rule_catalogue, rules_for, RuleDefinition, RuleSeverity,
+};
+pub use rules::specification_registry::{find_specification, known_specifications, Specification};
+pub use rules::validation_config::{SeverityOverride, ValidationConfig};
 use std::fmt::Debug;
 use std::io::{Read, Write};
 use std::sync::Arc;
@@ -28,26 +119,45 @@ pub use ubl::ubl_model::UblInvoice;
 uniffi::setup_scaffolding!();
 #[derive(Debug, uniffi::Error, Error)]
 pub enum InvoiceError {
-    #[error("Failed to parse XML")]
+    #[error("Failed to parse XML: {0}")]
     ParseError(String),
     #[error("Failed to validate XML: {0}")]
     ValidationError(String),
     #[error("Failed to unwrap Arc")]
     ArcError,
+    #[error("Failed to process PDF: {0}")]
+    PdfError(String),
 }
 
 #[derive(Debug, Clone, uniffi::Record)]
 pub struct BusinessRuleViolation {
     pub rule_id: String,
     pub rule_text: String,
+    /// The element the rule failed for, as a path below the root element numbering repeated siblings from 1,
+    /// e.g. `cac:AllowanceCharge[2]`, if the rule reports it
+    pub element: Option<String>,
+    /// The element the violation was found at, if it can be told from the XML source
+    pub location: Option<SourceLocation>,
 }
 impl BusinessRuleViolation {
     pub fn new(rule_id: &str, rule_text: &str) -> Self {
         Self {
             rule_id: rule_id.to_string(),
             rule_text: rule_text.to_string(),
+            element: None,
+            location: None,
         }
     }
+
+    /// Report the element the rule failed for
+    /// # Arguments
+    /// * `element` - The path of the element below the root element, e.g. `cac:AllowanceCharge[2]`
+    /// # Returns
+    /// * `BusinessRuleViolation` - The violation with the element
+    pub fn with_element(mut self, element: String) -> Self {
+        self.element = Some(element);
+        self
+    }
 }
 
 #[derive(Error, Debug, Clone, uniffi::Enum)]
@@ -56,19 +166,67 @@ pub enum ValidationError {
     Fatal(BusinessRuleViolation),
     #[error("Warning: {0:?}")]
     Warning(BusinessRuleViolation),
+    #[error("Metadata mismatch: {0:?}")]
+    MetadataMismatch(BusinessRuleViolation),
+    #[error("PDF violation: {0:?}")]
+    PdfViolation(BusinessRuleViolation),
     #[error("Failed to concurrently execute: {0:?}")]
     ConcurrencyError(String),
 }
+
+impl ValidationError {
+    /// Whether the violation is a warning, which does not make the invoice invalid
+    pub fn is_warning(&self) -> bool {
+        matches!(self, ValidationError::Warning(_))
+    }
+}
+
+/// All violations found while validating an invoice
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct ValidationReport {
+    /// The syntax of the validated invoice
+    pub syntax: InvoiceSyntax,
+    /// The name of the validated attachment if the invoice was embedded in a PDF
+    pub file_name: Option<String>,
+    /// The Factur-X properties declared in the XMP metadata if the invoice was embedded in a PDF
+    pub metadata: Option<FacturXMetadata>,
+    /// The violations of the invoice and its container
+    pub violations: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Whether no violations other than warnings were found
+    pub fn is_valid(&self) -> bool {
+        self.violations.iter().all(ValidationError::is_warning)
+    }
+}
+
 impl YaDeserialize for InvoiceStandard {
     fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
-        UblInvoice::deserialize(reader)
-            .map(InvoiceStandard::UBL)
-            .or_else(|_| CrossIndustryInvoice::deserialize(reader).map(InvoiceStandard::CII))
+        let (syntax, zugferd1) = match reader.peek()? {
+            XmlEvent::StartElement { name, .. } => (
+                root_syntax(name).ok_or_else(|| {
+                    format!("Unknown invoice root element: {}", qualified_name(name))
+                })?,
+                is_zugferd1_root(name),
+            ),
+            event => return Err(format!("Expected invoice root element, found {:?}", event)),
+        };
+        if zugferd1 {
+            return CrossIndustryDocument::deserialize(reader)
+                .map(|document| InvoiceStandard::CII(zugferd1_to_cii(&document)));
+        }
+        match syntax {
+            InvoiceSyntax::Ubl => UblInvoice::deserialize(reader).map(InvoiceStandard::UBL),
+            InvoiceSyntax::Cii => {
+                CrossIndustryInvoice::deserialize(reader).map(InvoiceStandard::CII)
+            }
+        }
     }
 }
 
 #[allow(clippy::large_enum_variant)]
-#[derive(uniffi::Enum)]
+#[derive(Clone, uniffi::Enum)]
 pub enum InvoiceStandard {
     UBL(UblInvoice),
     CII(CrossIndustryInvoice),
@@ -77,8 +235,12 @@ pub enum InvoiceStandard {
 impl YaSerialize for InvoiceStandard {
     fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
         match self {
-            InvoiceStandard::UBL(invoice) => invoice.serialize(writer),
-            InvoiceStandard::CII(invoice) => invoice.serialize(writer),
+            InvoiceStandard::UBL(invoice) => {
+                serialize_with_unknown_elements(invoice, &invoice.unknown_elements, writer)
+            }
+            InvoiceStandard::CII(invoice) => {
+                serialize_with_unknown_elements(invoice, &invoice.unknown_elements, writer)
+            }
         }
     }
 
@@ -99,40 +261,198 @@ impl YaSerialize for InvoiceStandard {
 /// # Returns
 /// * `InvoiceStandard` - The parsed invoice
 /// # Errors
-/// * `InvoiceError` - If the invoice is invalid
+/// * `InvoiceError` - If the invoice is invalid, warnings are not reported
 #[uniffi::export]
 pub fn validate_invoice(xml: &str) -> Result<InvoiceStandard, InvoiceError> {
-    let invoice_standard: InvoiceStandard =
-        einvoice_deps_yaserde::de::from_str(xml).map_err(InvoiceError::ParseError)?;
+    validate_invoice_with_config(xml, &ValidationConfig::default())
+}
+
+/// Validate an invoice XML file given as bytes in any encoding
+/// # Arguments
+/// * `xml` - The XML content of the invoice, e.g. UTF-16 with byte order mark or ISO-8859-1 declared in the XML declaration
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice
+/// # Errors
+/// * `InvoiceError` - If the content cannot be decoded or the invoice is invalid
+pub fn validate_invoice_bytes(xml: &[u8]) -> Result<InvoiceStandard, InvoiceError> {
+    validate_invoice(&decode_xml(xml)?)
+}
+
+#[uniffi::export(name = "validate_invoice_bytes")]
+fn ffi_validate_invoice_bytes(xml: Vec<u8>) -> Result<InvoiceStandard, InvoiceError> {
+    validate_invoice_bytes(&xml)
+}
+
+/// Validate an invoice XML file with a custom rule set
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// * `config` - Which rules are checked and with which severity
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice
+/// # Errors
+/// * `InvoiceError` - If the invoice violates an enabled rule reported as fatal, warnings are not reported
+#[uniffi::export]
+pub fn validate_invoice_with_config(
+    xml: &str,
+    config: &ValidationConfig,
+) -> Result<InvoiceStandard, InvoiceError> {
+    let (invoice_standard, report) = invoice_report(xml, config)?;
+    into_result(report.violations)?;
+    Ok(invoice_standard)
+}
+
+/// Parse an invoice XML file without validating it
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice
+/// # Errors
+/// * `InvoiceError::ParseError` - If the XML is neither a UBL nor a CII invoice,
+///   with the line and column of the error if the XML is not well-formed
+///
+/// Parsing is lossy: elements and attributes outside the model, e.g. `ext:UBLExtensions`, are dropped and
+/// missing when the invoice is written again. Use `parse_invoice_lossless` to read, modify and write an invoice.
+#[uniffi::export]
+pub fn parse_invoice(xml: &str) -> Result<InvoiceStandard, InvoiceError> {
+    einvoice_deps_yaserde::de::from_str(xml).map_err(|e| match syntax_error_position(xml) {
+        Some(position) => InvoiceError::ParseError(format!(
+            "{} (line {}, column {})",
+            e,
+            position.row + 1,
+            position.column + 1
+        )),
+        None => InvoiceError::ParseError(e),
+    })
+}
+
+/// Parse an invoice XML file keeping the elements outside the model, to write it back without losing them
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice with its `unknown_elements`
+/// # Errors
+/// * `InvoiceError::ParseError` - If the XML is neither a UBL nor a CII invoice,
+///   with the line and column of the error if the XML is not well-formed
+///
+/// Finding the unknown elements writes the parsed invoice and compares it with the XML,
+/// use `parse_invoice` if the invoice is only read.
+/// Unknown elements are kept with their attributes, but attributes outside the model on modelled elements
+/// are out of scope and dropped as by `parse_invoice`.
+#[uniffi::export]
+pub fn parse_invoice_lossless(xml: &str) -> Result<InvoiceStandard, InvoiceError> {
+    let mut invoice_standard = parse_invoice(xml)?;
+    let fragments = unknown_elements(xml, &invoice_standard);
+    match &mut invoice_standard {
+        InvoiceStandard::UBL(invoice) => invoice.unknown_elements = fragments,
+        InvoiceStandard::CII(invoice) => invoice.unknown_elements = fragments,
+    }
+    Ok(invoice_standard)
+}
+
+/// Validate an invoice XML file and report all business rule violations
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `ValidationReport` - The violations of the invoice, none or only warnings if the invoice is valid
+/// # Errors
+/// * `InvoiceError` - If the invoice cannot be parsed or its profile cannot be determined
+///
+/// Unlike `validate_invoice` this does not fail on violations, so they can be inspected one by one.
+#[uniffi::export]
+pub fn validate_invoice_report(xml: &str) -> Result<ValidationReport, InvoiceError> {
+    validate_invoice_report_with_config(xml, &ValidationConfig::default())
+}
+
+/// Validate an invoice XML file given as bytes in any encoding and report all business rule violations
+/// # Arguments
+/// * `xml` - The XML content of the invoice, decoded like `decode_xml`
+/// # Returns
+/// * `ValidationReport` - The violations of the invoice, none or only warnings if the invoice is valid
+/// # Errors
+/// * `InvoiceError` - If the content cannot be decoded, the invoice cannot be parsed or its profile cannot be determined
+pub fn validate_invoice_bytes_report(xml: &[u8]) -> Result<ValidationReport, InvoiceError> {
+    validate_invoice_report(&decode_xml(xml)?)
+}
+
+#[uniffi::export(name = "validate_invoice_bytes_report")]
+fn ffi_validate_invoice_bytes_report(xml: Vec<u8>) -> Result<ValidationReport, InvoiceError> {
+    validate_invoice_bytes_report(&xml)
+}
+
+/// Validate an invoice XML file with a custom rule set and report all violations of enabled rules
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// * `config` - Which rules are checked and with which severity
+/// # Returns
+/// * `ValidationReport` - The violations of the invoice, none or only warnings if the invoice is valid
+/// # Errors
+/// * `InvoiceError` - If the invoice cannot be parsed or its profile cannot be determined
+#[uniffi::export]
+pub fn validate_invoice_report_with_config(
+    xml: &str,
+    config: &ValidationConfig,
+) -> Result<ValidationReport, InvoiceError> {
+    invoice_report(xml, config).map(|(_, report)| report)
+}
+
+/// Parse an invoice XML file and collect its business rule violations
+pub(crate) fn invoice_report(
+    xml: &str,
+    config: &ValidationConfig,
+) -> Result<(InvoiceStandard, ValidationReport), InvoiceError> {
+    let invoice_standard = parse_invoice(xml)?;
+    let mut violations = business_rule_violations(&invoice_standard, config)?;
+    locate_violations(xml, invoice_standard.syntax(), &mut violations);
+    let report = ValidationReport {
+        syntax: invoice_standard.syntax(),
+        file_name: None,
+        metadata: None,
+        violations,
+    };
+    Ok((invoice_standard, report))
+}
 
-    match &invoice_standard {
+fn business_rule_violations(
+    invoice_standard: &InvoiceStandard,
+    config: &ValidationConfig,
+) -> Result<Vec<ValidationError>, InvoiceError> {
+    let result = match invoice_standard {
         InvoiceStandard::UBL(invoice) => {
+            let profile = ubl_profile(invoice, config);
             let arc = Arc::new(invoice);
-            ubl_business_rule_validator::validate_invoice(arc).map_err(|e| {
-                InvoiceError::ValidationError(
-                    e.iter()
-                        .map(|e| e.to_string())
-                        .collect::<Vec<String>>()
-                        .join("\n"),
-                )
-            })?;
+            ubl_business_rule_validator::validate_invoice(profile, arc, config)
         }
         InvoiceStandard::CII(invoice) => {
-            let profile = determine_profile(invoice).map_err(InvoiceError::ValidationError)?;
+            let profile = determine_profile_with_config(invoice, config)
+                .map_err(InvoiceError::ValidationError)?;
             let arc = Arc::new(invoice);
-            cii_business_rule_validator::validate_invoice(profile, arc).map_err(|e| {
-                InvoiceError::ValidationError(
-                    e.iter()
-                        .map(|e| e.to_string())
-                        .collect::<Vec<String>>()
-                        .join("\n"),
-                )
-            })?;
+            cii_business_rule_validator::validate_invoice(profile, arc, config)
         }
-    }
+    };
+    let mut violations = result.err().unwrap_or_default();
+    violations.extend(config.apply(validate_custom_rules(invoice_standard, config)));
+    Ok(violations)
+}
 
-    Ok(invoice_standard)
+/// Fail on the violations other than warnings
+fn into_result(violations: Vec<ValidationError>) -> Result<(), InvoiceError> {
+    let errors = violations
+        .iter()
+        .filter(|violation| !violation.is_warning())
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(InvoiceError::ValidationError(
+            errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>()
+                .join("\n"),
+        ))
+    }
 }
+
 /// Validate a ZUGFeRD PDF file
 /// # Arguments
 /// * `file_path` - The path to the PDF file
@@ -141,176 +461,1873 @@ pub fn validate_invoice(xml: &str) -> Result<InvoiceStandard, InvoiceError> {
 /// # Errors
 /// * `InvoiceError` - If the invoice is invalid
 ///
-/// This first extracts the embedded XML file from the PDF and then delegates to `validate_invoice`
+/// This first extracts the embedded XML file from the PDF and then delegates to `validate_invoice`.
+/// Attachments named like a ZUGFeRD, Factur-X or XRechnung invoice are used directly,
+/// any other XML attachment is tried in order until one of them is an invoice.
+/// The Factur-X XMP metadata of the PDF is cross-checked against the invoice,
+/// a conflicting conformance level or file name is reported as `ValidationError::MetadataMismatch`.
 #[uniffi::export]
 pub fn validate_zugferd_pdf(file_path: &str) -> Result<InvoiceStandard, InvoiceError> {
     let pdf_document =
         Document::load(file_path).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
-    match get_embedded_xml_file(&pdf_document) {
-        Ok(embedded_file) => {
-            if let Some((_file_name, content)) = embedded_file {
-                let xml_content = String::from_utf8(content)
-                    .map_err(|err| InvoiceError::ParseError(err.to_string()))?;
-                validate_invoice(&xml_content)
-            } else {
-                Err(InvoiceError::ParseError(
-                    "No embedded XML file found".to_string(),
-                ))
-            }
-        }
-        Err(e) => Err(InvoiceError::ParseError(e.to_string())),
-    }
+    validate_zugferd_document(&pdf_document)
+}
+
+/// Validate a ZUGFeRD PDF held in memory
+/// # Arguments
+/// * `pdf` - The content of the PDF file
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice
+/// # Errors
+/// * `InvoiceError` - If the invoice is invalid
+///
+/// See `validate_zugferd_pdf` for how the embedded invoice is selected.
+pub fn validate_zugferd_pdf_bytes(pdf: &[u8]) -> Result<InvoiceStandard, InvoiceError> {
+    let pdf_document =
+        Document::load_mem(pdf).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    validate_zugferd_document(&pdf_document)
 }
 
-fn get_embedded_xml_file(pdf: &Document) -> Result<Option<(String, Vec<u8>)>, Error> {
-    let root_ref = pdf.trailer.get(b"Root")?.as_reference()?;
+/// Validate a ZUGFeRD PDF read from a stream
+/// # Arguments
+/// * `reader` - The source of the PDF file, e.g. a network stream
+/// # Returns
+/// * `InvoiceStandard` - The parsed invoice
+/// # Errors
+/// * `InvoiceError` - If the stream cannot be read or the invoice is invalid
+pub fn validate_zugferd_pdf_reader<R: Read>(
+    mut reader: R,
+) -> Result<InvoiceStandard, InvoiceError> {
+    let mut pdf = Vec::new();
+    reader
+        .read_to_end(&mut pdf)
+        .map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    validate_zugferd_pdf_bytes(&pdf)
+}
 
-    let catalog = pdf.get_object(root_ref).and_then(|obj| obj.as_dict())?;
+#[uniffi::export(name = "validate_zugferd_pdf_bytes")]
+fn ffi_validate_zugferd_pdf_bytes(pdf: Vec<u8>) -> Result<InvoiceStandard, InvoiceError> {
+    validate_zugferd_pdf_bytes(&pdf)
+}
 
-    let names = catalog.get(b"Names")?;
+fn validate_zugferd_document(pdf_document: &Document) -> Result<InvoiceStandard, InvoiceError> {
+    let (invoice_standard, report) =
+        inspect_zugferd_document(pdf_document, &ValidationConfig::default())?;
+    into_result(report.violations)?;
+    Ok(invoice_standard)
+}
 
-    let names_dict = match names {
-        Object::Dictionary(dict) => dict.clone(),
-        Object::Reference(obj_ref) => {
-            let dict_obj = pdf.get_object(*obj_ref).and_then(|obj| obj.as_dict())?;
-            dict_obj.clone()
-        }
-        _ => {
-            return Err(Error::DictKey);
-        }
-    };
+/// Validate a ZUGFeRD PDF file and report all violations of the invoice and the PDF/A-3 container
+/// # Arguments
+/// * `file_path` - The path to the PDF file
+/// # Returns
+/// * `ValidationReport` - The business rule, metadata and PDF violations
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or does not contain an invoice
+///
+/// Unlike `validate_zugferd_pdf` this also checks the container, see `validate_pdf_container`.
+#[uniffi::export]
+pub fn validate_zugferd_pdf_report(file_path: &str) -> Result<ValidationReport, InvoiceError> {
+    validate_zugferd_pdf_report_with_config(file_path, &ValidationConfig::default())
+}
 
-    names_dict.get(b"EmbeddedFiles")?;
+/// Validate a ZUGFeRD PDF file with a custom rule set and report all violations
+/// # Arguments
+/// * `file_path` - The path to the PDF file
+/// * `config` - Which business rules are checked and with which severity
+/// # Returns
+/// * `ValidationReport` - The violations of enabled business rules and all metadata and PDF violations
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or does not contain an invoice
+#[uniffi::export]
+pub fn validate_zugferd_pdf_report_with_config(
+    file_path: &str,
+    config: &ValidationConfig,
+) -> Result<ValidationReport, InvoiceError> {
+    let pdf_document =
+        Document::load(file_path).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    zugferd_report(&pdf_document, config).map(|(_, report)| report)
+}
 
-    let embedded_files_obj = names_dict.get(b"EmbeddedFiles")?;
+/// Validate a ZUGFeRD PDF held in memory and report all violations of the invoice and the PDF/A-3 container
+/// # Arguments
+/// * `pdf` - The content of the PDF file
+/// # Returns
+/// * `ValidationReport` - The business rule, metadata and PDF violations
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or does not contain an invoice
+pub fn validate_zugferd_pdf_bytes_report(pdf: &[u8]) -> Result<ValidationReport, InvoiceError> {
+    validate_zugferd_pdf_bytes_report_with_config(pdf, &ValidationConfig::default())
+}
 
-    let embedded_files_dict = match embedded_files_obj {
-        Object::Dictionary(dict) => dict.clone(),
-        Object::Reference(obj_ref) => pdf.get_object(*obj_ref)?.as_dict()?.clone(),
-        _ => return Err(Error::DictKey),
-    };
+#[uniffi::export(name = "validate_zugferd_pdf_bytes_report")]
+fn ffi_validate_zugferd_pdf_bytes_report(pdf: Vec<u8>) -> Result<ValidationReport, InvoiceError> {
+    validate_zugferd_pdf_bytes_report(&pdf)
+}
 
-    let names_array_obj = embedded_files_dict.get(b"Names")?;
+/// Validate a ZUGFeRD PDF held in memory with a custom rule set and report all violations
+/// # Arguments
+/// * `pdf` - The content of the PDF file
+/// * `config` - Which business rules are checked and with which severity
+/// # Returns
+/// * `ValidationReport` - The violations of enabled business rules and all metadata and PDF violations
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or does not contain an invoice
+pub fn validate_zugferd_pdf_bytes_report_with_config(
+    pdf: &[u8],
+    config: &ValidationConfig,
+) -> Result<ValidationReport, InvoiceError> {
+    zugferd_pdf_bytes_report(pdf, config).map(|(_, report)| report)
+}
 
-    let names_array = names_array_obj.as_array()?;
+#[uniffi::export(name = "validate_zugferd_pdf_bytes_report_with_config")]
+fn ffi_validate_zugferd_pdf_bytes_report_with_config(
+    pdf: Vec<u8>,
+    config: &ValidationConfig,
+) -> Result<ValidationReport, InvoiceError> {
+    validate_zugferd_pdf_bytes_report_with_config(&pdf, config)
+}
 
-    let mut idx = 0;
-    while idx < names_array.len() {
-        let file_name_obj = &names_array[idx];
-        let file_spec_obj = &names_array[idx + 1];
-        idx += 2;
+/// Load a ZUGFeRD PDF held in memory and collect the violations of the invoice and the container
+pub(crate) fn zugferd_pdf_bytes_report(
+    pdf: &[u8],
+    config: &ValidationConfig,
+) -> Result<(InvoiceStandard, ValidationReport), InvoiceError> {
+    let pdf_document =
+        Document::load_mem(pdf).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    zugferd_report(&pdf_document, config)
+}
 
-        let file_name = match file_name_obj {
-            Object::String(bytes, _) => String::from_utf8_lossy(bytes).to_string(),
-            _ => continue, // Not a valid file name
-        };
+fn zugferd_report(
+    pdf_document: &Document,
+    config: &ValidationConfig,
+) -> Result<(InvoiceStandard, ValidationReport), InvoiceError> {
+    let (invoice_standard, mut report) = inspect_zugferd_document(pdf_document, config)?;
+    if let Some(file_name) = &report.file_name {
+        report
+            .violations
+            .extend(validate_pdf_container(pdf_document, file_name));
+    }
+    Ok((invoice_standard, report))
+}
 
-        if !file_name.to_lowercase().ends_with(".xml") {
+/// Select the embedded invoice and collect its business rule and metadata violations
+fn inspect_zugferd_document(
+    pdf_document: &Document,
+    config: &ValidationConfig,
+) -> Result<(InvoiceStandard, ValidationReport), InvoiceError> {
+    let candidates = find_embedded_xml_files(pdf_document)
+        .map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    if candidates.is_empty() {
+        return Err(InvoiceError::ParseError(
+            "No embedded XML file found".to_string(),
+        ));
+    }
+    let (metadata, metadata_error) = match read_facturx_metadata(pdf_document) {
+        Ok(metadata) => (metadata, None),
+        Err(e) => (
+            None,
+            Some(ValidationError::MetadataMismatch(
+                BusinessRuleViolation::new(
+                    "FX-XMP-03",
+                    &format!("[FX-XMP-03]-The XMP metadata cannot be decoded: {}", e),
+                ),
+            )),
+        ),
+    };
+    for candidate in &candidates {
+        if let Some(error) = &candidate.error {
+            if candidate.preferred {
+                return Err(embedded_file_error(&candidate.file_name, error));
+            }
             continue;
         }
-
-        let file_spec_dict = match file_spec_obj {
-            Object::Reference(r) => pdf.get_object(*r)?.as_dict()?.clone(),
-            Object::Dictionary(d) => d.clone(),
-            _ => continue, // Not a valid file spec
-        };
-
-        let ef_obj = file_spec_dict.get(b"EF")?;
-
-        let ef_dict = match ef_obj {
-            Object::Reference(r) => pdf.get_object(*r)?.as_dict()?.clone(),
-            Object::Dictionary(d) => d.clone(),
-            _ => continue,
+        let xml_content = decode_xml(&candidate.content)?;
+        let (invoice_standard, mut report) = match invoice_report(&xml_content, config) {
+            Err(InvoiceError::ParseError(_)) if !candidate.preferred => continue,
+            result => result?,
         };
+        if let Some(metadata) = &metadata {
+            report.violations.extend(metadata_violations(
+                metadata,
+                &candidate.file_name,
+                &invoice_standard,
+                config,
+            ));
+        }
+        report.violations.extend(metadata_error);
+        report.file_name = Some(candidate.file_name.clone());
+        report.metadata = metadata;
+        return Ok((invoice_standard, report));
+    }
+    Err(InvoiceError::ParseError(format!(
+        "No invoice found in embedded XML files: {}",
+        candidates
+            .iter()
+            .map(|candidate| candidate.file_name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
 
-        let file_stream_ref = ef_dict.get(b"F")?;
+fn embedded_file_error(file_name: &str, error: &str) -> InvoiceError {
+    InvoiceError::PdfError(format!(
+        "The embedded file {} cannot be decoded: {}",
+        file_name, error
+    ))
+}
 
-        let file_stream = match file_stream_ref {
-            Object::Reference(r) => pdf.get_object(*r)?,
-            Object::Stream(s) => &Object::Stream(s.clone()),
-            _ => continue,
-        };
+/// The invoice XML embedded in a ZUGFeRD PDF
+#[derive(uniffi::Record)]
+pub struct EmbeddedInvoiceXml {
+    /// The name of the attachment, e.g. `factur-x.xml`
+    pub file_name: String,
+    /// The XML content of the invoice
+    pub xml: String,
+    /// The Factur-X properties declared in the XMP metadata of the PDF, including the conformance level,
+    /// `None` if there are none or the metadata cannot be decoded
+    pub metadata: Option<FacturXMetadata>,
+}
 
-        let file_bytes = match file_stream {
-            Object::Stream(ref stream) => stream.decompressed_content()?,
-            _ => continue,
-        };
+/// Extract the invoice XML from a ZUGFeRD PDF file without validating it
+/// # Arguments
+/// * `file_path` - The path to the PDF file
+/// # Returns
+/// * `EmbeddedInvoiceXml` - The most likely invoice attachment and its file name
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or has no embedded XML file
+#[uniffi::export]
+pub fn extract_zugferd_xml(file_path: &str) -> Result<EmbeddedInvoiceXml, InvoiceError> {
+    let pdf_document =
+        Document::load(file_path).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    extract_invoice_xml(&pdf_document)
+}
 
-        return Ok(Some((file_name, file_bytes)));
-    }
-    Ok(None)
+/// Extract the invoice XML from a ZUGFeRD PDF held in memory without validating it
+/// # Arguments
+/// * `pdf` - The content of the PDF file
+/// # Returns
+/// * `EmbeddedInvoiceXml` - The most likely invoice attachment and its file name
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or has no embedded XML file
+pub fn extract_zugferd_xml_bytes(pdf: &[u8]) -> Result<EmbeddedInvoiceXml, InvoiceError> {
+    let pdf_document =
+        Document::load_mem(pdf).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    extract_invoice_xml(&pdf_document)
 }
 
-#[derive(uniffi::Enum)]
-pub enum ZugferdProfile {
-    Basic,
-    En16931,
-    Extended,
+#[uniffi::export(name = "extract_zugferd_xml_bytes")]
+fn ffi_extract_zugferd_xml_bytes(pdf: Vec<u8>) -> Result<EmbeddedInvoiceXml, InvoiceError> {
+    extract_zugferd_xml_bytes(&pdf)
 }
 
-fn determine_profile(invoice: &CrossIndustryInvoice) -> Result<ZugferdProfile, String> {
-    if let Some(exchanged_context) = &invoice.rsm_exchanged_document_context {
-        if let Some(guideline_context) =
-            &exchanged_context.ram_guideline_specified_document_context_parameter
-        {
-            if let Some(guideline_id) = &guideline_context.id {
-                if let Some(id) = &guideline_id.id {
-                    return  match id.as_str() {
-                        "urn:cen.eu:en16931:2017" | "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0" |"urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0#conformant#urn:xeinkauf.de:kosit:extension:xrechnung_3.0"  => Ok(ZugferdProfile::En16931),
-                        "urn:cen.eu:en16931:2017#compliant#urn:factur-x.eu:1p0:basic"
-                        | "urn:ferd:CrossIndustryDocument:invoice:1p0:basic" => Ok(ZugferdProfile::Basic),
-                        "urn:cen.eu:en16931:2017#conformant#urn:factur-x.eu:1p0:extended" => Ok(ZugferdProfile::Extended),
-                        _ => Err(format!("Unknown guideline ID: {}", id)),
-                    };
-                }
-            }
+/// Cross-check the Factur-X XMP metadata against the embedded invoice
+fn metadata_violations(
+    metadata: &FacturXMetadata,
+    file_name: &str,
+    invoice_standard: &InvoiceStandard,
+    config: &ValidationConfig,
+) -> Vec<ValidationError> {
+    let mut violations = Vec::new();
+    if let Some(document_file_name) = &metadata.document_file_name {
+        if document_file_name != file_name {
+            violations.push(ValidationError::MetadataMismatch(BusinessRuleViolation::new(
+                "FX-XMP-01",
+                &format!(
+                    "[FX-XMP-01]-The fx:DocumentFileName '{}' of the XMP metadata does not match the embedded invoice '{}'",
+                    document_file_name, file_name
+                ),
+            )));
         }
     }
-    Err("Document did not contain Profile ID".to_string())
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::{validate_invoice, InvoiceError, InvoiceStandard};
-
-    #[test]
-    fn it_works() {
-        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
-        let invoice_standard = validate_invoice(s).unwrap();
-        match invoice_standard {
-            InvoiceStandard::UBL(invoice) => {
-                assert!(invoice.cbc_id.is_some());
+    if let (Some(conformance_level), InvoiceStandard::CII(invoice)) =
+        (&metadata.conformance_level, invoice_standard)
+    {
+        if let Ok(profile) = determine_profile_with_config(invoice, config) {
+            if !profile.matches_conformance_level(conformance_level) {
+                violations.push(ValidationError::MetadataMismatch(BusinessRuleViolation::new(
+                    "FX-XMP-02",
+                    &format!(
+                        "[FX-XMP-02]-The fx:ConformanceLevel '{}' of the XMP metadata does not match the profile '{}' of the embedded invoice",
+                        conformance_level,
+                        profile.conformance_level()
+                    ),
+                )));
             }
-            _ => panic!("Expected UBL invoice"),
         }
     }
+    violations
+}
 
-    #[test]
-    fn err_works() {
-        let s = include_str!(
-            "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
-        );
-        let res = validate_invoice(s);
-        assert!(res.is_err());
-        match res {
-            Err(e) => {
-                assert!(matches!(e, InvoiceError::ValidationError(_)));
-            }
-            _ => panic!("Expected an error"),
-        }
+fn extract_invoice_xml(pdf_document: &Document) -> Result<EmbeddedInvoiceXml, InvoiceError> {
+    let candidate = find_embedded_xml_files(pdf_document)
+        .map_err(|e| InvoiceError::ParseError(e.to_string()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| InvoiceError::ParseError("No embedded XML file found".to_string()))?;
+    if let Some(error) = &candidate.error {
+        return Err(embedded_file_error(&candidate.file_name, error));
     }
+    Ok(EmbeddedInvoiceXml {
+        xml: decode_xml(&candidate.content)?,
+        file_name: candidate.file_name,
+        metadata: read_facturx_metadata(pdf_document).ok().flatten(),
+    })
+}
 
-    #[test]
-    fn pdf_extraction_works() {
-        let invoice = crate::validate_zugferd_pdf(
-            "./tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf",
-        )
-        .unwrap();
-        match invoice {
-            InvoiceStandard::CII(invoice) => {
-                assert!(invoice.rsm_exchanged_document.is_some());
-            }
-            _ => panic!("Expected CII invoice"),
-        }
+/// List the XML files embedded in a PDF file
+/// # Arguments
+/// * `file_path` - The path to the PDF file
+/// # Returns
+/// * `Vec<EmbeddedXmlCandidate>` - The embedded XML files, most likely invoice first
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read
+#[uniffi::export]
+pub fn list_embedded_xml_files(file_path: &str) -> Result<Vec<EmbeddedXmlCandidate>, InvoiceError> {
+    let pdf_document =
+        Document::load(file_path).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    find_embedded_xml_files(&pdf_document).map_err(|e| InvoiceError::ParseError(e.to_string()))
+}
+
+/// List all files embedded in a PDF file
+/// # Arguments
+/// * `file_path` - The path to the PDF file
+/// # Returns
+/// * `Vec<PdfAttachment>` - The embedded files with their metadata and content
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read
+#[uniffi::export]
+pub fn list_pdf_attachments(file_path: &str) -> Result<Vec<PdfAttachment>, InvoiceError> {
+    let pdf_document =
+        Document::load(file_path).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    find_pdf_attachments(&pdf_document).map_err(|e| InvoiceError::PdfError(e.to_string()))
+}
+
+/// List all files embedded in a PDF held in memory
+/// # Arguments
+/// * `pdf` - The content of the PDF file
+/// # Returns
+/// * `Vec<PdfAttachment>` - The embedded files with their metadata and content
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read
+pub fn list_pdf_attachments_bytes(pdf: &[u8]) -> Result<Vec<PdfAttachment>, InvoiceError> {
+    let pdf_document =
+        Document::load_mem(pdf).map_err(|e| InvoiceError::ParseError(e.to_string()))?;
+    find_pdf_attachments(&pdf_document).map_err(|e| InvoiceError::PdfError(e.to_string()))
+}
+
+#[uniffi::export(name = "list_pdf_attachments_bytes")]
+fn ffi_list_pdf_attachments_bytes(pdf: Vec<u8>) -> Result<Vec<PdfAttachment>, InvoiceError> {
+    list_pdf_attachments_bytes(&pdf)
+}
+
+/// Extract the additional supporting documents of an invoice XML file
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `Vec<InvoiceAttachment>` - The supporting documents with their embedded content decoded
+/// # Errors
+/// * `InvoiceError::ParseError` - If the invoice cannot be parsed
+///
+/// The invoice is not validated, use `validate_invoice` for that.
+#[uniffi::export]
+pub fn extract_invoice_attachments(xml: &str) -> Result<Vec<InvoiceAttachment>, InvoiceError> {
+    Ok(invoice_attachments(&parse_invoice(xml)?))
+}
+
+/// List the invoice lines of an invoice XML file with their sub-invoice lines
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// # Returns
+/// * `Vec<InvoiceLineNode>` - The lines on the top level, each with its sub-invoice lines
+/// # Errors
+/// * `InvoiceError::ParseError` - If the invoice cannot be parsed
+///
+/// The invoice is not validated, use `validate_invoice` for that.
+#[uniffi::export]
+pub fn extract_invoice_lines(xml: &str) -> Result<Vec<InvoiceLineNode>, InvoiceError> {
+    Ok(invoice_line_tree(&parse_invoice(xml)?))
+}
+
+/// The XML syntaxes of EN 16931
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum InvoiceSyntax {
+    /// OASIS Universal Business Language 2.1
+    Ubl,
+    /// UN/CEFACT Cross Industry Invoice D16B
+    Cii,
+}
+
+impl InvoiceStandard {
+    /// The syntax of the invoice
+    pub fn syntax(&self) -> InvoiceSyntax {
+        match self {
+            InvoiceStandard::UBL(_) => InvoiceSyntax::Ubl,
+            InvoiceStandard::CII(_) => InvoiceSyntax::Cii,
+        }
+    }
+
+    /// The invoice number (BT-1)
+    pub fn invoice_number(&self) -> Option<&str> {
+        match self {
+            InvoiceStandard::UBL(invoice) => invoice.cbc_id.as_deref(),
+            InvoiceStandard::CII(invoice) => invoice
+                .rsm_exchanged_document
+                .as_ref()
+                .and_then(|document| document.ram_id.as_ref())
+                .and_then(|id| id.id.as_deref()),
+        }
+    }
+
+    /// The specification identifier (BT-24), e.g. `urn:cen.eu:en16931:2017`
+    pub fn specification_identifier(&self) -> Option<&str> {
+        match self {
+            InvoiceStandard::UBL(invoice) => invoice.cbc_customization_id.as_deref(),
+            InvoiceStandard::CII(invoice) => invoice
+                .rsm_exchanged_document_context
+                .as_ref()
+                .and_then(|context| {
+                    context
+                        .ram_guideline_specified_document_context_parameter
+                        .as_ref()
+                })
+                .and_then(|parameter| parameter.id.as_ref())
+                .and_then(|id| id.id.as_deref()),
+        }
+    }
+
+    /// The business process type (BT-23)
+    pub fn business_process(&self) -> Option<&str> {
+        match self {
+            InvoiceStandard::UBL(invoice) => invoice.cbc_profile_id.as_deref(),
+            InvoiceStandard::CII(invoice) => invoice
+                .rsm_exchanged_document_context
+                .as_ref()
+                .and_then(|context| {
+                    context
+                        .ram_business_process_specified_document_context_parameter
+                        .as_ref()
+                })
+                .and_then(|parameter| parameter.ram_id.as_ref())
+                .and_then(|id| id.id.as_deref()),
+        }
+    }
+
+    /// The specification the invoice declares, `None` if its specification identifier is not known
+    ///
+    /// See `find_specification`.
+    pub fn specification(&self) -> Option<Specification> {
+        self.specification_with_config(&ValidationConfig::default())
+    }
+
+    /// The specification the invoice declares with the specifications of a configuration,
+    /// `None` if its specification identifier is not known
+    ///
+    /// See `ValidationConfig::find_specification`.
+    pub fn specification_with_config(&self, config: &ValidationConfig) -> Option<Specification> {
+        self.specification_identifier()
+            .and_then(|identifier| config.find_specification(identifier, self.syntax()))
+    }
+
+    /// The ZUGFeRD / Factur-X profile of a CII invoice, `None` for UBL or an unknown guideline
+    pub fn zugferd_profile(&self) -> Option<ZugferdProfile> {
+        self.zugferd_profile_with_config(&ValidationConfig::default())
+    }
+
+    /// The ZUGFeRD / Factur-X profile of a CII invoice with the specifications of a configuration,
+    /// `None` for UBL or an unknown guideline
+    pub fn zugferd_profile_with_config(&self, config: &ValidationConfig) -> Option<ZugferdProfile> {
+        match self {
+            InvoiceStandard::UBL(_) => None,
+            InvoiceStandard::CII(invoice) => determine_profile_with_config(invoice, config).ok(),
+        }
+    }
+
+    /// Convert the invoice to another syntax
+    /// # Arguments
+    /// * `syntax` - The target syntax
+    /// # Returns
+    /// * `InvoiceStandard` - The converted invoice, or the invoice itself if it already has the target syntax
+    ///
+    /// A CII invoice parsed from ZUGFeRD 1.0 declares the succeeding Factur-X profile afterwards,
+    /// as the ZUGFeRD 1.0 guideline IDs are not valid in CII D16B or UBL.
+    pub fn convert(self, syntax: InvoiceSyntax) -> InvoiceStandard {
+        match (self, syntax) {
+            (InvoiceStandard::UBL(invoice), InvoiceSyntax::Cii) => {
+                InvoiceStandard::CII(ubl_to_cii(&invoice))
+            }
+            (InvoiceStandard::CII(invoice), InvoiceSyntax::Ubl) => {
+                InvoiceStandard::UBL(cii_to_ubl(&with_facturx_guideline_id(invoice)))
+            }
+            (InvoiceStandard::CII(invoice), InvoiceSyntax::Cii) => {
+                InvoiceStandard::CII(with_facturx_guideline_id(invoice))
+            }
+            (invoice, _) => invoice,
+        }
+    }
+}
+
+/// Convert an invoice XML file between UBL and CII
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// * `syntax` - The target syntax
+/// # Returns
+/// * `String` - The XML content of the converted invoice
+/// # Errors
+/// * `InvoiceError::ParseError` - If the invoice cannot be parsed or the converted invoice cannot be serialized
+///
+/// The invoice is not validated, use `validate_invoice` on the result for that.
+/// Elements and attributes outside the model are dropped, as they have no counterpart in the target syntax,
+/// even if the invoice already has the target syntax.
+#[uniffi::export]
+pub fn convert_invoice(xml: &str, syntax: InvoiceSyntax) -> Result<String, InvoiceError> {
+    einvoice_deps_yaserde::ser::to_string(&parse_invoice(xml)?.convert(syntax))
+        .map_err(InvoiceError::ParseError)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum ZugferdProfile {
+    Minimum,
+    BasicWl,
+    Basic,
+    En16931,
+    Extended,
+}
+
+impl ZugferdProfile {
+    /// The `fx:ConformanceLevel` declared in the XMP metadata of a ZUGFeRD PDF
+    pub fn conformance_level(&self) -> &'static str {
+        match self {
+            ZugferdProfile::Minimum => "MINIMUM",
+            ZugferdProfile::BasicWl => "BASIC WL",
+            ZugferdProfile::Basic => "BASIC",
+            ZugferdProfile::En16931 => "EN 16931",
+            ZugferdProfile::Extended => "EXTENDED",
+        }
+    }
+
+    /// Whether a declared `fx:ConformanceLevel` describes this profile
+    ///
+    /// ZUGFeRD 2.0 calls EN 16931 `COMFORT` and XRechnung is declared as `XRECHNUNG`.
+    pub fn matches_conformance_level(&self, conformance_level: &str) -> bool {
+        let conformance_level = conformance_level.trim().to_uppercase();
+        match self {
+            ZugferdProfile::En16931 => {
+                matches!(
+                    conformance_level.as_str(),
+                    "EN 16931" | "COMFORT" | "XRECHNUNG"
+                )
+            }
+            profile => conformance_level == profile.conformance_level(),
+        }
+    }
+}
+
+/// The profile selecting the rules a UBL invoice is checked with, EN 16931 if its specification is not known
+fn ubl_profile(invoice: &UblInvoice, config: &ValidationConfig) -> ZugferdProfile {
+    invoice
+        .cbc_customization_id
+        .as_deref()
+        .and_then(|id| config.find_specification(id, InvoiceSyntax::Ubl))
+        .map_or(ZugferdProfile::En16931, |specification| {
+            specification.profile
+        })
+}
+
+/// The profile of a CII invoice with a guideline ID of the configuration or a built-in one
+fn determine_profile_with_config(
+    invoice: &CrossIndustryInvoice,
+    config: &ValidationConfig,
+) -> Result<ZugferdProfile, String> {
+    cii_specification(invoice, config).map(|specification| specification.profile)
+}
+
+/// The specification a CII invoice declares with a guideline ID of the configuration or a built-in one
+fn cii_specification(
+    invoice: &CrossIndustryInvoice,
+    config: &ValidationConfig,
+) -> Result<Specification, String> {
+    if let Some(exchanged_context) = &invoice.rsm_exchanged_document_context {
+        if let Some(guideline_context) =
+            &exchanged_context.ram_guideline_specified_document_context_parameter
+        {
+            if let Some(guideline_id) = &guideline_context.id {
+                if let Some(id) = &guideline_id.id {
+                    return config
+                        .find_specification(id, InvoiceSyntax::Cii)
+                        .ok_or_else(|| format!("Unknown guideline ID: {}", id));
+                }
+            }
+        }
+    }
+    Err("Document did not contain Profile ID".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{validate_invoice, InvoiceError, InvoiceStandard};
+
+    #[test]
+    fn it_works() {
+        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let invoice_standard = validate_invoice(s).unwrap();
+        match invoice_standard {
+            InvoiceStandard::UBL(invoice) => {
+                assert!(invoice.cbc_id.is_some());
+            }
+            _ => panic!("Expected UBL invoice"),
+        }
+    }
+
+    #[test]
+    fn err_works() {
+        let s = include_str!(
+            "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
+        );
+        let res = validate_invoice(s);
+        assert!(res.is_err());
+        match res {
+            Err(e) => {
+                assert!(matches!(e, InvoiceError::ValidationError(_)));
+            }
+            _ => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn pdf_extraction_works() {
+        let invoice = crate::validate_zugferd_pdf(
+            "./tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf",
+        )
+        .unwrap();
+        match invoice {
+            InvoiceStandard::CII(invoice) => {
+                assert!(invoice.rsm_exchanged_document.is_some());
+            }
+            _ => panic!("Expected CII invoice"),
+        }
+    }
+
+    #[test]
+    fn pdf_extraction_from_bytes_works() {
+        let pdf = include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf");
+        assert!(matches!(
+            crate::validate_zugferd_pdf_reader(&pdf[..]),
+            Ok(InvoiceStandard::CII(_))
+        ));
+        let embedded = crate::extract_zugferd_xml_bytes(pdf).unwrap();
+        assert!(embedded.file_name.ends_with(".xml"));
+        assert!(embedded.xml.contains("CrossIndustryInvoice"));
+        assert!(crate::validate_zugferd_pdf_bytes(b"not a pdf").is_err());
+    }
+
+    #[test]
+    fn pdf_metadata_mismatch_works() {
+        let pdf = include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf");
+        let embedded = crate::extract_zugferd_xml_bytes(pdf).unwrap();
+        let metadata = embedded.metadata.unwrap();
+        assert_eq!(metadata.conformance_level.as_deref(), Some("EXTENDED"));
+        assert_eq!(metadata.document_file_name.as_deref(), Some("factur-x.xml"));
+
+        // Relabel the EXTENDED invoice as BASIC
+        let mut document = lopdf::Document::load_mem(pdf).unwrap();
+        let metadata_id = document
+            .catalog()
+            .unwrap()
+            .get(b"Metadata")
+            .and_then(lopdf::Object::as_reference)
+            .unwrap();
+        let stream = document
+            .get_object_mut(metadata_id)
+            .and_then(lopdf::Object::as_stream_mut)
+            .unwrap();
+        let xmp = String::from_utf8(
+            stream
+                .decompressed_content()
+                .unwrap_or(stream.content.clone()),
+        )
+        .unwrap()
+        .replace(">EXTENDED<", ">BASIC<");
+        stream.dict.remove(b"Filter");
+        stream.set_content(xmp.into_bytes());
+        let mut relabelled = Vec::new();
+        document.save_to(&mut relabelled).unwrap();
+
+        match crate::validate_zugferd_pdf_bytes(&relabelled) {
+            Err(InvoiceError::ValidationError(message)) => {
+                assert!(message.contains("Metadata mismatch"));
+                assert!(message.contains("FX-XMP-02"));
+            }
+            _ => panic!("Expected a metadata mismatch"),
+        }
+
+        // Metadata that cannot be decoded is reported, not an error
+        let stream = document
+            .get_object_mut(metadata_id)
+            .and_then(lopdf::Object::as_stream_mut)
+            .unwrap();
+        stream.dict.set("Filter", "JBIG2Decode");
+        let mut undecodable = Vec::new();
+        document.save_to(&mut undecodable).unwrap();
+        assert!(crate::extract_zugferd_xml_bytes(&undecodable)
+            .unwrap()
+            .metadata
+            .is_none());
+        let report = crate::validate_zugferd_pdf_bytes_report(&undecodable).unwrap();
+        let rule_ids = report
+            .violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        assert!(rule_ids
+            .iter()
+            .any(|violation| violation.contains("FX-XMP-03")));
+        assert!(rule_ids
+            .iter()
+            .any(|violation| violation.contains("PDF-01")));
+        match crate::validate_zugferd_pdf_bytes(&undecodable) {
+            Err(InvoiceError::ValidationError(message)) => assert!(message.contains("FX-XMP-03")),
+            _ => panic!("Expected a metadata violation"),
+        }
+    }
+
+    #[test]
+    fn pdf_container_validation_works() {
+        use lopdf::dictionary;
+
+        let pdf = include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf");
+        let report = crate::validate_zugferd_pdf_bytes_report(pdf).unwrap();
+        assert!(report.is_valid());
+
+        let mut document = lopdf::Document::load_mem(pdf).unwrap();
+        let action = document.add_object(dictionary! {
+            "S" => "JavaScript",
+            "JS" => lopdf::Object::string_literal("app.alert('hello');"),
+        });
+        document.catalog_mut().unwrap().set("OpenAction", action);
+        let mut scripted = Vec::new();
+        document.save_to(&mut scripted).unwrap();
+
+        let report = crate::validate_zugferd_pdf_bytes_report(&scripted).unwrap();
+        assert!(report.violations.iter().any(|violation| matches!(
+            violation,
+            crate::ValidationError::PdfViolation(violation) if violation.rule_id == "PDF-08"
+        )));
+    }
+
+    #[test]
+    fn invoice_attachment_extraction_works() {
+        for xml in [
+            include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml"),
+            include_str!("../tests/inputs/cii/01.01_comprehensive_test_uncefact.xml"),
+        ] {
+            let attachments = crate::extract_invoice_attachments(xml).unwrap();
+            let embedded = attachments
+                .iter()
+                .filter(|attachment| attachment.content.is_some())
+                .collect::<Vec<_>>();
+            assert_eq!(embedded.len(), 2);
+            assert_eq!(
+                embedded[0].file_name.as_deref(),
+                Some("01_15_Anhang_01.pdf")
+            );
+            assert!(embedded[0].mime_code_allowed);
+            assert!(embedded[0].content.as_ref().unwrap().starts_with(b"%PDF"));
+        }
+
+        // An attachment that is not valid base64 does not hide the others
+        let xml = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let mut invoice = match crate::parse_invoice(xml).unwrap() {
+            InvoiceStandard::UBL(invoice) => invoice,
+            _ => panic!("Expected UBL invoice"),
+        };
+        let object = invoice
+            .cac_additional_document_reference
+            .iter_mut()
+            .find_map(|document| {
+                document
+                    .cac_attachment
+                    .as_mut()
+                    .and_then(|attachment| attachment.cbc_embedded_document_binary_object.as_mut())
+            })
+            .unwrap();
+        object.text = Some("not base64!".to_string());
+        let attachments = crate::invoice_attachments(&InvoiceStandard::UBL(invoice));
+        let broken = attachments
+            .iter()
+            .filter(|attachment| attachment.error.is_some())
+            .collect::<Vec<_>>();
+        assert_eq!(broken.len(), 1);
+        assert!(broken[0].content.is_none());
+        assert_eq!(
+            attachments
+                .iter()
+                .filter(|attachment| attachment.content.is_some())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn invoice_attachment_editing_works() {
+        let xml = include_str!("../tests/inputs/cii/01.01a-INVOICE_uncefact.xml");
+        let mut invoice = match validate_invoice(xml).unwrap() {
+            InvoiceStandard::CII(invoice) => invoice,
+            _ => panic!("Expected CII invoice"),
+        };
+        invoice
+            .add_attachment(
+                "TS-1",
+                "timesheet.pdf",
+                "application/pdf",
+                Some("Timesheet"),
+                b"%PDF-1.7",
+            )
+            .unwrap();
+        assert!(invoice
+            .add_attachment(
+                "TS-2",
+                "timesheet.pdf",
+                "application/pdf",
+                None,
+                b"%PDF-1.7"
+            )
+            .is_err());
+        assert!(invoice
+            .add_attachment("TS-3", "timesheet.txt", "text/plain", None, b"hours")
+            .is_err());
+        invoice
+            .replace_attachment("timesheet.pdf", "text/csv", b"day;hours")
+            .unwrap();
+
+        let xml = einvoice_deps_yaserde::ser::to_string(&invoice).unwrap();
+        let attachments = crate::extract_invoice_attachments(&xml).unwrap();
+        let timesheet = attachments.last().unwrap();
+        assert_eq!(timesheet.type_code.as_deref(), Some("916"));
+        assert_eq!(timesheet.mime_code.as_deref(), Some("text/csv"));
+        assert_eq!(timesheet.content.as_deref(), Some(&b"day;hours"[..]));
+        assert!(validate_invoice(&xml).is_ok());
+
+        assert!(invoice.remove_attachment("timesheet.pdf"));
+        assert!(!invoice.remove_attachment("timesheet.pdf"));
+    }
+
+    #[test]
+    fn pdf_embedding_works() {
+        let hybrid = hybrid_pdf();
+
+        let document = lopdf::Document::load_mem(&hybrid).unwrap();
+        let candidate = crate::find_embedded_xml_files(&document).unwrap().remove(0);
+        let (file_name, content) = (candidate.file_name, candidate.content);
+        assert_eq!(file_name, "factur-x.xml");
+        assert!(validate_invoice(&String::from_utf8(content).unwrap()).is_ok());
+        let catalog = document.catalog().unwrap();
+        assert_eq!(catalog.get(b"AF").unwrap().as_array().unwrap().len(), 1);
+        assert!(crate::validate_zugferd_pdf_bytes_report(&hybrid)
+            .unwrap()
+            .is_valid());
+
+        // The Factur-X properties declare the XRechnung specification of the invoice
+        let metadata = crate::read_facturx_metadata(&document).unwrap().unwrap();
+        assert_eq!(metadata.conformance_level.as_deref(), Some("XRECHNUNG"));
+        assert_eq!(metadata.version.as_deref(), Some("1.0"));
+        // The existing metadata is kept and the extension schema is declared once
+        let xmp = crate::pdf::pdf_metadata::xmp_packet(&document)
+            .unwrap()
+            .unwrap();
+        let xmp = String::from_utf8(xmp).unwrap();
+        assert!(xmp.contains("Groß und Breit Bau AG: Invoice 47110819"));
+        assert!(xmp.contains("<pdf:Producer>pypdf</pdf:Producer>"));
+        assert!(xmp.contains("<xmp:CreateDate>2024-09-23T16:17:10+00:00</xmp:CreateDate>"));
+        assert_eq!(xmp.matches("Factur-X PDFA Extension Schema").count(), 1);
+        assert_eq!(xmp.matches("<xmp:ModifyDate>").count(), 1);
+        assert_eq!(xmp.matches("<pdfaid:part>").count(), 1);
+        let info = document
+            .trailer
+            .get_deref(b"Info", &document)
+            .and_then(lopdf::Object::as_dict)
+            .unwrap();
+        assert!(info.has(b"ModDate"));
+    }
+
+    #[test]
+    fn pdf_attachment_discovery_works() {
+        use lopdf::{dictionary, Object, Stream};
+
+        let hybrid = hybrid_pdf();
+        let mut document = lopdf::Document::load_mem(&hybrid).unwrap();
+
+        // Move the invoice into a nested name tree behind an unrelated XML attachment
+        let decoy = document.add_object(Stream::new(dictionary! {}, b"<note/>".to_vec()));
+        let decoy_spec = document.add_object(dictionary! {
+            "Type" => "Filespec",
+            "F" => Object::string_literal("attachment.xml"),
+            "EF" => dictionary! { "F" => decoy },
+        });
+        let decoy_kid = document.add_object(dictionary! {
+            "Names" => vec![Object::string_literal("attachment.xml"), decoy_spec.into()],
+        });
+        let catalog = document.catalog().unwrap();
+        let mut names = catalog
+            .get_deref(b"Names", &document)
+            .and_then(Object::as_dict)
+            .unwrap()
+            .clone();
+        let invoice_kid = match names.get(b"EmbeddedFiles").unwrap().clone() {
+            Object::Reference(id) => id,
+            tree => document.add_object(tree),
+        };
+        names.set(
+            "EmbeddedFiles",
+            dictionary! { "Kids" => vec![decoy_kid.into(), invoice_kid.into()] },
+        );
+        document.catalog_mut().unwrap().set("Names", names);
+
+        let candidates = crate::find_embedded_xml_files(&document).unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].file_name, "factur-x.xml");
+        assert!(candidates[0].preferred && candidates[0].associated);
+        assert_eq!(candidates[1].file_name, "attachment.xml");
+        assert_eq!(candidates[1].content, b"<note/>");
+
+        let mut saved = Vec::new();
+        document.save_to(&mut saved).unwrap();
+        let attachments = crate::list_pdf_attachments_bytes(&saved).unwrap();
+        assert_eq!(attachments.len(), 2);
+        let invoice = attachments
+            .iter()
+            .find(|attachment| attachment.file_name == "factur-x.xml")
+            .unwrap();
+        assert_eq!(invoice.mime_type.as_deref(), Some("text/xml"));
+        assert_eq!(invoice.af_relationship.as_deref(), Some("Alternative"));
+        assert_eq!(invoice.size, invoice.content.len() as u64);
+    }
+
+    #[test]
+    fn broken_pdf_attachment_is_reported() {
+        use lopdf::{dictionary, Object, Stream};
+
+        let hybrid = hybrid_pdf();
+        let mut document = lopdf::Document::load_mem(&hybrid).unwrap();
+
+        // An attachment with a filter that cannot be decoded
+        let broken = document.add_object(Stream::new(
+            dictionary! { "Filter" => "JBIG2Decode" },
+            b"not an XML file".to_vec(),
+        ));
+        let broken_spec = document.add_object(dictionary! {
+            "Type" => "Filespec",
+            "F" => Object::string_literal("broken.xml"),
+            "EF" => dictionary! { "F" => broken },
+        });
+        let mut associated_files = document
+            .catalog()
+            .unwrap()
+            .get(b"AF")
+            .and_then(Object::as_array)
+            .unwrap()
+            .clone();
+        associated_files.push(broken_spec.into());
+        document.catalog_mut().unwrap().set("AF", associated_files);
+
+        let candidates = crate::find_embedded_xml_files(&document).unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert!(candidates[0].error.is_none());
+        assert_eq!(candidates[1].file_name, "broken.xml");
+        assert!(candidates[1].error.is_some() && candidates[1].content.is_empty());
+
+        let mut saved = Vec::new();
+        document.save_to(&mut saved).unwrap();
+        let report = crate::validate_zugferd_pdf_bytes_report(&saved).unwrap();
+        assert_eq!(report.file_name.as_deref(), Some("factur-x.xml"));
+        let attachments = crate::list_pdf_attachments_bytes(&saved).unwrap();
+        let broken = attachments
+            .iter()
+            .find(|attachment| attachment.file_name == "broken.xml")
+            .unwrap();
+        assert!(broken.error.is_some() && broken.size == 0);
+    }
+
+    #[test]
+    fn pdf_rendering_works() {
+        let xml = include_str!("../tests/inputs/cii/01.01a-INVOICE_uncefact.xml");
+        let invoice = match validate_invoice(xml).unwrap() {
+            InvoiceStandard::CII(invoice) => invoice,
+            _ => panic!("Expected CII invoice"),
+        };
+        let pdf = crate::render_zugferd_pdf(&invoice).unwrap();
+
+        let document = lopdf::Document::load_mem(&pdf).unwrap();
+        assert!(!document.get_pages().is_empty());
+        let file_name = &crate::find_embedded_xml_files(&document).unwrap()[0].file_name;
+        assert_eq!(file_name, "factur-x.xml");
+
+        // PDF/A requires embedded fonts and an output intent for the device colours
+        let catalog = document.catalog().unwrap();
+        let output_intent = catalog
+            .get_deref(b"OutputIntents", &document)
+            .and_then(lopdf::Object::as_array)
+            .unwrap()[0]
+            .as_dict()
+            .unwrap();
+        assert!(output_intent.get(b"DestOutputProfile").is_ok());
+        let (_, page_id) = document.get_pages().into_iter().next().unwrap();
+        let fonts = document.get_page_fonts(page_id).unwrap();
+        assert_eq!(fonts.len(), 2);
+        for font in fonts.values() {
+            assert_eq!(font.get(b"Subtype").unwrap().as_name().unwrap(), b"Type0");
+            let cid_font = font
+                .get_deref(b"DescendantFonts", &document)
+                .and_then(lopdf::Object::as_array)
+                .unwrap()[0]
+                .as_reference()
+                .and_then(|id| document.get_dictionary(id))
+                .unwrap();
+            let descriptor = cid_font.get_deref(b"FontDescriptor", &document).unwrap();
+            assert!(descriptor.as_dict().unwrap().get(b"FontFile2").is_ok());
+        }
+        let text = document.extract_text(&[1]).unwrap();
+        assert!(text.contains("Invoice"), "{}", text);
+    }
+
+    #[test]
+    fn html_rendering_works() {
+        let ubl = validate_invoice(include_str!(
+            "../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml"
+        ))
+        .unwrap();
+        let html = crate::render_html(&ubl);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("BG-4 Seller"));
+        assert!(html.contains("BG-25 Invoice line"));
+        assert!(html.contains("href=\"data:"));
+
+        let cii = validate_invoice(include_str!(
+            "../tests/inputs/cii/01.01a-INVOICE_uncefact.xml"
+        ))
+        .unwrap();
+        let html = crate::render_html(&cii);
+        assert!(html.contains("BG-22 Document totals"));
+        assert!(html.contains("Invoice 123456XX"));
+    }
+
+    #[test]
+    fn invoice_conversion_works() {
+        let ubl = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let cii = crate::convert_invoice(ubl, crate::InvoiceSyntax::Cii).unwrap();
+        match validate_invoice(&cii).unwrap() {
+            InvoiceStandard::CII(invoice) => {
+                let line_items = &invoice
+                    .rsm_supply_chain_trade_transaction
+                    .unwrap()
+                    .ram_included_supply_chain_trade_line_item;
+                assert!(!line_items.is_empty());
+            }
+            _ => panic!("Expected CII invoice"),
+        }
+
+        let ubl_again = crate::convert_invoice(&cii, crate::InvoiceSyntax::Ubl).unwrap();
+        match validate_invoice(&ubl_again).unwrap() {
+            InvoiceStandard::UBL(invoice) => {
+                assert_eq!(invoice.cbc_id.as_deref(), Some("1234567"));
+                assert!(invoice.cbc_note[0].starts_with("#AAC#"));
+            }
+            _ => panic!("Expected UBL invoice"),
+        }
+    }
+
+    #[test]
+    fn batch_validation_works() {
+        let input = |name: &str, content: &[u8]| crate::BatchInput {
+            name: name.to_string(),
+            content: content.to_vec(),
+        };
+        let report = crate::validate_batch(vec![
+            input(
+                "valid.xml",
+                include_bytes!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml"),
+            ),
+            input(
+                "invalid.xml",
+                include_bytes!(
+                    "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
+                ),
+            ),
+            input(
+                "invoice.pdf",
+                include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf"),
+            ),
+            input("broken.xml", b"<Invoice"),
+        ]);
+        let names = report
+            .documents
+            .iter()
+            .map(|document| document.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["valid.xml", "invalid.xml", "invoice.pdf", "broken.xml"]
+        );
+        assert!(report.documents[0].is_valid());
+        assert!(!report.documents[1].is_valid());
+        assert!(report.documents[3].error.is_some());
+
+        let statistics = report.statistics;
+        assert_eq!(statistics.total, 4);
+        assert_eq!(statistics.passed, 2);
+        assert_eq!(statistics.failed, 1);
+        assert_eq!(statistics.errors, 1);
+        assert!(statistics
+            .rules
+            .iter()
+            .all(|rule| rule.count == 1 && !rule.rule_id.is_empty()));
+        assert!(statistics
+            .profiles
+            .iter()
+            .any(|profile| profile.profile == "EXTENDED" && profile.count == 1));
+    }
+
+    #[test]
+    fn svrl_output_works() {
+        let s = include_str!(
+            "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
+        );
+        let report = crate::validate_invoice_report(s).unwrap();
+        let svrl = crate::validation_report_to_svrl(&report);
+        let document = xmltree::Element::parse(svrl.as_bytes()).unwrap();
+        assert_eq!(document.name, "schematron-output");
+        let failed_assert = document.get_child("failed-assert").unwrap();
+        assert_eq!(failed_assert.attributes["id"], "BR-01");
+        assert_eq!(failed_assert.attributes["flag"], "fatal");
+        assert_eq!(failed_assert.attributes["location"], "/ubl:Invoice");
+        assert!(failed_assert.attributes["test"].contains("cbc:CustomizationID"));
+        assert!(failed_assert
+            .get_child("text")
+            .and_then(|text| text.get_text())
+            .is_some_and(|text| text.contains("Specification identifier")));
+    }
+
+    #[test]
+    fn rule_catalogue_works() {
+        let catalogue = crate::rule_catalogue();
+        assert!(catalogue.len() > 1000);
+        assert!(catalogue.iter().any(|rule| rule.implemented));
+        assert!(catalogue.iter().any(|rule| !rule.implemented));
+
+        let br_01 = crate::find_rule("br-01");
+        assert_eq!(br_01.len(), 2);
+        let ubl = br_01
+            .iter()
+            .find(|rule| rule.syntax == crate::InvoiceSyntax::Ubl)
+            .unwrap();
+        assert_eq!(ubl.severity, crate::RuleSeverity::Fatal);
+        assert_eq!(
+            ubl.message,
+            "An Invoice shall have a Specification identifier (BT-24)."
+        );
+        assert_eq!(ubl.test, "normalize-space(cbc:CustomizationID) != ''");
+        assert_eq!(ubl.business_terms, ["BT-24"]);
+        assert!(ubl.implemented);
+        assert!(crate::find_rule("BR-UNKNOWN").is_empty());
+
+        let basic = crate::rules_for(
+            crate::InvoiceSyntax::Cii,
+            Some(crate::ZugferdProfile::Basic),
+        );
+        let en16931 = crate::rules_for(
+            crate::InvoiceSyntax::Cii,
+            Some(crate::ZugferdProfile::En16931),
+        );
+        assert!(!basic.is_empty() && basic.len() < en16931.len());
+    }
+
+    #[test]
+    fn validation_config_works() {
+        let s = include_str!(
+            "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
+        );
+        let rule_ids = |report: &crate::ValidationReport| {
+            report
+                .violations
+                .iter()
+                .map(|violation| match violation {
+                    crate::ValidationError::Fatal(v) => format!("fatal {}", v.rule_id),
+                    crate::ValidationError::Warning(v) => format!("warning {}", v.rule_id),
+                    violation => violation.to_string(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let default = crate::validate_invoice_report(s).unwrap();
+        assert!(rule_ids(&default).contains(&"fatal BR-01".to_string()));
+
+        let downgraded = crate::ValidationConfig {
+            severity_overrides: vec![crate::SeverityOverride {
+                rule_id: "br-01".to_string(),
+                severity: crate::RuleSeverity::Warning,
+            }],
+            ..Default::default()
+        };
+        let report = crate::validate_invoice_report_with_config(s, &downgraded).unwrap();
+        assert!(rule_ids(&report).contains(&"warning BR-01".to_string()));
+        // Warnings do not make the invoice invalid
+        assert!(report.is_valid());
+        assert!(crate::validate_invoice_with_config(s, &downgraded).is_ok());
+        let batch = crate::validate_batch_with_config(
+            vec![crate::BatchInput {
+                name: "downgraded.xml".to_string(),
+                content: s.as_bytes().to_vec(),
+            }],
+            &downgraded,
+        );
+        assert!(batch.documents[0].is_valid());
+        assert_eq!((batch.statistics.passed, batch.statistics.failed), (1, 0));
+
+        let suppressed = crate::ValidationConfig {
+            suppressed_rules: vec!["BR-01".to_string()],
+            ..Default::default()
+        };
+        assert!(crate::validate_invoice_with_config(s, &suppressed).is_ok());
+
+        let only_br_co = crate::ValidationConfig {
+            rule_families: vec!["BR-CO".to_string()],
+            ..Default::default()
+        };
+        let report = crate::validate_invoice_report_with_config(s, &only_br_co).unwrap();
+        assert!(report.violations.is_empty());
+    }
+
+    #[test]
+    fn custom_rules_work() {
+        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml").replacen(
+            "<cbc:ID>1234567</cbc:ID>",
+            "<cbc:ID>CUSTOM-1</cbc:ID>",
+            1,
+        );
+        let forbidden_prefix = "CUSTOM-".to_string();
+        let checked = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = checked.clone();
+        let config = crate::ValidationConfig::default().with_rule(crate::FnRule::new(
+            "ACME-01",
+            move |invoice| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                match invoice.invoice_number() {
+                    Some(number) if number.starts_with(&forbidden_prefix) => Err(
+                        crate::ValidationError::Fatal(crate::BusinessRuleViolation::new(
+                            "ACME-01",
+                            "[ACME-01]-Reserved number",
+                        )),
+                    ),
+                    _ => Ok(()),
+                }
+            },
+        ));
+        assert_eq!(config.custom_rules.len(), 1);
+        assert_eq!(config.custom_rules[0].rule_id(), "ACME-01");
+
+        let report = crate::validate_invoice_report_with_config(&s, &config).unwrap();
+        assert!(matches!(
+            report.violations.as_slice(),
+            [crate::ValidationError::Fatal(violation)] if violation.rule_id == "ACME-01"
+        ));
+        let suppressed = crate::ValidationConfig {
+            suppressed_rules: vec!["ACME-01".to_string()],
+            ..config.clone()
+        };
+        assert!(crate::validate_invoice_with_config(&s, &suppressed).is_ok());
+        // Disabled rules are not run at all
+        assert_eq!(checked.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Rules only apply to the validations they are configured for
+        assert!(crate::validate_invoice(&s).is_ok());
+        assert_eq!(checked.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn source_locations_work() {
+        let s = include_str!(
+            "../tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml"
+        );
+        let report = crate::validate_invoice_report(s).unwrap();
+        let location = report
+            .violations
+            .iter()
+            .find_map(|violation| match violation {
+                crate::ValidationError::Fatal(v) if v.rule_id == "BR-01" => v.location.clone(),
+                _ => None,
+            });
+        assert_eq!(
+            location.map(|location| (location.path, location.line)),
+            Some(("/ubl:Invoice".to_string(), 2))
+        );
+
+        let s = include_str!("../tests/inputs/ubl/01.01a-INVOICE_ubl.xml").replacen(
+            "<cac:AccountingSupplierParty>",
+            "<cac:AdditionalDocumentReference><cbc:ID>1</cbc:ID><cac:Attachment>\
+             <cbc:EmbeddedDocumentBinaryObject mimeCode=\"application/pdf\" filename=\"1.pdf\">aGk=</cbc:EmbeddedDocumentBinaryObject>\
+             </cac:Attachment></cac:AdditionalDocumentReference>\n\
+             <cac:AdditionalDocumentReference><cbc:ID>2</cbc:ID><cac:Attachment>\
+             <cbc:EmbeddedDocumentBinaryObject mimeCode=\"application/zip\" filename=\"2.zip\">aGk=</cbc:EmbeddedDocumentBinaryObject>\
+             </cac:Attachment></cac:AdditionalDocumentReference>\n\
+             <cac:AccountingSupplierParty>",
+            1,
+        );
+        let report = crate::validate_invoice_report(&s).unwrap();
+        let violation = report
+            .violations
+            .iter()
+            .find_map(|violation| match violation {
+                crate::ValidationError::Fatal(v) if v.rule_id == "BR-CL-24" => Some(v),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            violation.element.as_deref(),
+            Some("cac:AdditionalDocumentReference[2]/cac:Attachment/cbc:EmbeddedDocumentBinaryObject")
+        );
+        let location = violation.location.clone().unwrap();
+        assert_eq!(
+            location.path,
+            "/ubl:Invoice/cac:AdditionalDocumentReference[2]/cac:Attachment/cbc:EmbeddedDocumentBinaryObject"
+        );
+        let line = s[..s.find("mimeCode=\"application/zip\"").unwrap()]
+            .matches('\n')
+            .count()
+            + 1;
+        assert_eq!(location.line, line as u64);
+
+        let error = crate::parse_invoice(
+            "<Invoice xmlns=\"urn:oasis:names:specification:ubl:schema:xsd:Invoice-2\">\n  <ID>1</ID>\n  <ID>2</Invoice>",
+        );
+        assert!(matches!(
+            error,
+            Err(crate::InvoiceError::ParseError(message)) if message.contains("line 3")
+        ));
+        let error = crate::parse_invoice("<Unknown/>");
+        assert!(matches!(
+            error,
+            Err(crate::InvoiceError::ParseError(message)) if !message.contains("line")
+        ));
+    }
+
+    #[test]
+    fn unknown_elements_round_trip() {
+        let s = include_str!("../tests/inputs/ubl/01.01a-INVOICE_ubl.xml")
+            .replacen(
+                "<cbc:CustomizationID>",
+                "<ext:UBLExtensions xmlns:ext=\"urn:oasis:names:specification:ubl:schema:xsd:CommonExtensionComponents-2\">\
+                 <ext:UBLExtension><ext:ExtensionContent><Signature>abc</Signature></ext:ExtensionContent></ext:UBLExtension>\
+                 </ext:UBLExtensions><cbc:CustomizationID>",
+                1,
+            )
+            .replacen(
+                "</cbc:IssueDate>",
+                "</cbc:IssueDate><cbc:IssueTime>12:00:00</cbc:IssueTime>",
+                1,
+            );
+        let crate::InvoiceStandard::UBL(invoice) = crate::parse_invoice(&s).unwrap() else {
+            panic!("not parsed as UBL");
+        };
+        assert!(invoice.unknown_elements.is_empty());
+        let crate::InvoiceStandard::UBL(mut invoice) = crate::parse_invoice_lossless(&s).unwrap()
+        else {
+            panic!("not parsed as UBL");
+        };
+        assert_eq!(invoice.unknown_elements.len(), 2);
+        assert_eq!(invoice.unknown_elements[0].parent, "/ubl:Invoice[1]");
+        assert_eq!(invoice.unknown_elements[0].preceding, None);
+        assert_eq!(
+            invoice.unknown_elements[1].preceding.as_deref(),
+            Some("cbc:IssueDate[1]")
+        );
+
+        invoice.cbc_note.push("Added note".to_string());
+        let xml =
+            einvoice_deps_yaserde::ser::to_string(&crate::InvoiceStandard::UBL(invoice)).unwrap();
+        let extensions = xml.find("UBLExtensions>").unwrap();
+        assert!(extensions < xml.find("CustomizationID>").unwrap());
+        assert!(xml.contains("<Signature>abc</Signature>"));
+        let issue_time = xml.find("IssueTime>").unwrap();
+        assert!(xml.find("IssueDate>").unwrap() < issue_time);
+        assert!(issue_time < xml.find("InvoiceTypeCode>").unwrap());
+        assert!(xml.contains("Added note"));
+
+        let crate::InvoiceStandard::UBL(reparsed) = crate::parse_invoice_lossless(&xml).unwrap()
+        else {
+            panic!("not parsed as UBL");
+        };
+        let signature = reparsed.unknown_elements[0].xml.as_str();
+        let signature = xmltree::Element::parse(signature.as_bytes()).unwrap();
+        let signature = signature
+            .get_child("UBLExtension")
+            .and_then(|extension| extension.get_child("ExtensionContent"))
+            .and_then(|content| content.get_child("Signature"))
+            .unwrap();
+        assert_eq!(signature.namespace, None);
+        assert_eq!(reparsed.unknown_elements.len(), 2);
+    }
+
+    #[test]
+    fn format_detection_works() {
+        let ubl = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let info = crate::detect_format(ubl).unwrap();
+        assert_eq!(info.syntax, crate::InvoiceSyntax::Ubl);
+        assert_eq!(info.root_element, "Invoice");
+        assert_eq!(
+            info.specification_identifier.as_deref(),
+            Some("urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0")
+        );
+        assert_eq!(
+            info.business_process.as_deref(),
+            Some("urn:fdc:peppol.eu:2017:poacc:billing:01:1.0")
+        );
+        assert_eq!(info.profile, Some(crate::ZugferdProfile::En16931));
+
+        let cii = include_str!("../tests/inputs/cii/01.01_comprehensive_test_uncefact.xml");
+        let info = crate::detect_format(cii).unwrap();
+        assert_eq!(info.syntax, crate::InvoiceSyntax::Cii);
+        assert_eq!(info.root_element, "CrossIndustryInvoice");
+        assert_eq!(
+            info.business_process.as_deref(),
+            Some("urn:fdc:peppol.eu:2017:poacc:billing:01:1.0")
+        );
+        assert_eq!(info.profile, Some(crate::ZugferdProfile::En16931));
+
+        assert!(crate::detect_format("<Order/>").is_err());
+        let error = crate::parse_invoice("<Order/>");
+        assert!(matches!(
+            error,
+            Err(crate::InvoiceError::ParseError(message)) if message.contains("root element: Order")
+        ));
+    }
+
+    #[test]
+    fn byte_input_works() {
+        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let expected =
+            einvoice_deps_yaserde::ser::to_string(&validate_invoice(s).unwrap()).unwrap();
+
+        let latin1 = s.replacen("encoding=\"UTF-8\"", "encoding=\"ISO-8859-1\"", 1);
+        let (latin1, _, unmappable) = encoding_rs::WINDOWS_1252.encode(&latin1);
+        assert!(!unmappable);
+        let mut utf16 = vec![0xff, 0xfe];
+        utf16.extend(
+            s.replacen("encoding=\"UTF-8\"", "encoding=\"UTF-16\"", 1)
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        let mut utf8 = b"\xef\xbb\xbf".to_vec();
+        utf8.extend(s.as_bytes());
+        for bytes in [latin1.to_vec(), utf16, utf8] {
+            let invoice = crate::validate_invoice_bytes(&bytes).unwrap();
+            assert_eq!(
+                einvoice_deps_yaserde::ser::to_string(&invoice).unwrap(),
+                expected
+            );
+        }
+
+        let error = crate::decode_xml(b"<?xml version=\"1.0\" encoding=\"EBCDIC-XY\"?><a/>");
+        assert!(matches!(
+            error,
+            Err(InvoiceError::ParseError(message)) if message.contains("Unsupported encoding")
+        ));
+    }
+
+    #[test]
+    fn specification_registry_works() {
+        let specification = crate::find_specification(
+            "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0",
+            crate::InvoiceSyntax::Ubl,
+        )
+        .unwrap();
+        assert_eq!(specification.name, "Peppol BIS Billing 3.0");
+        assert!(crate::find_specification(
+            "urn:cen.eu:en16931:2017#conformant#urn:factur-x.eu:1p0:extended",
+            crate::InvoiceSyntax::Ubl
+        )
+        .is_none());
+        let fallback = crate::find_specification(
+            "urn:cen.eu:en16931:2017#compliant#urn:example.com:cius:1.0",
+            crate::InvoiceSyntax::Cii,
+        )
+        .unwrap();
+        assert_eq!(fallback.profile, crate::ZugferdProfile::En16931);
+        for (identifier, profile, conformance_level) in [
+            (
+                "urn:factur-x.eu:1p0:minimum",
+                crate::ZugferdProfile::Minimum,
+                "MINIMUM",
+            ),
+            (
+                "urn:factur-x.eu:1p0:basicwl",
+                crate::ZugferdProfile::BasicWl,
+                "BASIC WL",
+            ),
+            (
+                "urn:zugferd.de:2p0:minimum",
+                crate::ZugferdProfile::Minimum,
+                "MINIMUM",
+            ),
+            (
+                "urn:zugferd.de:2p0:basicwl",
+                crate::ZugferdProfile::BasicWl,
+                "BASIC WL",
+            ),
+        ] {
+            let specification =
+                crate::find_specification(identifier, crate::InvoiceSyntax::Cii).unwrap();
+            assert_eq!(specification.profile, profile);
+            assert_eq!(specification.conformance_level, conformance_level);
+            assert!(profile.matches_conformance_level(conformance_level));
+        }
+        // Profiles without lines do not check line rules
+        let rules = crate::rules_for(
+            crate::InvoiceSyntax::Cii,
+            Some(crate::ZugferdProfile::BasicWl),
+        );
+        assert!(rules.iter().any(|rule| rule.rule_id == "BR-CO-13"));
+        assert!(rules.iter().all(|rule| rule.rule_id != "BR-21"));
+        let rules = crate::rules_for(
+            crate::InvoiceSyntax::Cii,
+            Some(crate::ZugferdProfile::Minimum),
+        );
+        assert!(rules.iter().any(|rule| rule.rule_id == "BR-CO-15"));
+        assert!(rules.iter().all(|rule| rule.rule_id != "BR-10"));
+
+        let identifier = "urn:example.com:extension:registry-test";
+        let s = include_str!("../tests/inputs/cii/01.01_comprehensive_test_uncefact.xml").replacen(
+            "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0</ram:ID>",
+            &format!("{}</ram:ID>", identifier),
+            1,
+        );
+        assert!(crate::validate_invoice_report(&s).is_err());
+        let config = crate::ValidationConfig {
+            specifications: vec![crate::Specification {
+                identifier: identifier.to_string(),
+                name: "Registry test".to_string(),
+                profile: crate::ZugferdProfile::Extended,
+                syntax: Some(crate::InvoiceSyntax::Cii),
+                conformance_level: "EXTENDED".to_string(),
+                facturx_version: "1.0".to_string(),
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            config
+                .find_specification(identifier, crate::InvoiceSyntax::Cii)
+                .map(|specification| specification.profile),
+            Some(crate::ZugferdProfile::Extended)
+        );
+        assert!(crate::validate_invoice_report_with_config(&s, &config).is_ok());
+        // Specifications only apply to the calls they are configured for
+        let invoice = crate::parse_invoice(&s).unwrap();
+        assert!(invoice.specification().is_none());
+        assert!(crate::validate_invoice_report(&s).is_err());
+        assert_eq!(
+            invoice
+                .specification_with_config(&config)
+                .map(|specification| specification.name),
+            Some("Registry test".to_string())
+        );
+        assert_eq!(
+            invoice.zugferd_profile_with_config(&config),
+            Some(crate::ZugferdProfile::Extended)
+        );
+        assert_eq!(crate::detect_format(&s).unwrap().profile, None);
+        assert_eq!(
+            crate::detect_format_with_config(&s, &config)
+                .unwrap()
+                .profile,
+            Some(crate::ZugferdProfile::Extended)
+        );
+        let batch = crate::validate_batch_with_config(
+            vec![crate::BatchInput {
+                name: "registry.xml".to_string(),
+                content: s.as_bytes().to_vec(),
+            }],
+            &config,
+        );
+        assert_eq!(batch.documents[0].profile.as_deref(), Some("EXTENDED"));
+        let crate::InvoiceStandard::CII(invoice) = invoice else {
+            panic!("Expected CII invoice");
+        };
+        let pdf = include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf");
+        assert!(crate::embed_invoice_into_pdf(pdf, &invoice).is_err());
+        let hybrid = crate::embed_invoice_into_pdf_with_config(pdf, &invoice, &config).unwrap();
+        let document = lopdf::Document::load_mem(&hybrid).unwrap();
+        let metadata = crate::read_facturx_metadata(&document).unwrap().unwrap();
+        assert_eq!(metadata.conformance_level.as_deref(), Some("EXTENDED"));
+        let rendered = crate::render_zugferd_pdf_with_config(&invoice, &config).unwrap();
+        assert!(crate::render_zugferd_pdf(&invoice).is_err());
+        assert!(crate::validate_zugferd_pdf_bytes_report_with_config(&rendered, &config).is_ok());
+
+        // UBL invoices are checked with the rule set of their specification
+        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml").replacen(
+            "mimeCode=\"application/pdf\"",
+            "mimeCode=\"application/zip\"",
+            1,
+        );
+        let has_br_cl_24 = |report: crate::ValidationReport| {
+            report
+                .violations
+                .iter()
+                .any(|violation| violation.to_string().contains("BR-CL-24"))
+        };
+        assert!(has_br_cl_24(crate::validate_invoice_report(&s).unwrap()));
+        let identifier = crate::parse_invoice(&s)
+            .unwrap()
+            .specification_identifier()
+            .unwrap()
+            .to_string();
+        let config = crate::ValidationConfig {
+            specifications: vec![crate::Specification {
+                identifier,
+                name: "Registry test BASIC".to_string(),
+                profile: crate::ZugferdProfile::Basic,
+                syntax: Some(crate::InvoiceSyntax::Ubl),
+                conformance_level: "BASIC".to_string(),
+                facturx_version: "1.0".to_string(),
+            }],
+            ..Default::default()
+        };
+        let report = crate::validate_invoice_report_with_config(&s, &config).unwrap();
+        assert!(!has_br_cl_24(report));
+        assert!(crate::rules_for(
+            crate::InvoiceSyntax::Ubl,
+            Some(crate::ZugferdProfile::Basic)
+        )
+        .iter()
+        .all(|rule| rule.rule_id != "BR-CL-24"));
+    }
+
+    #[test]
+    fn zugferd1_invoices_work() {
+        let s = include_str!("../tests/inputs/zugferd/ZUGFeRD1_COMFORT_Rechnung.xml");
+        let info = crate::detect_format(s).unwrap();
+        assert_eq!(info.syntax, crate::InvoiceSyntax::Cii);
+        assert_eq!(info.root_element, "CrossIndustryDocument");
+        assert_eq!(info.profile, Some(crate::ZugferdProfile::En16931));
+
+        let report = crate::validate_invoice_report(s).unwrap();
+        assert!(report.is_valid(), "{:?}", report.violations);
+        let InvoiceStandard::CII(invoice) = crate::parse_invoice(s).unwrap() else {
+            panic!("ZUGFeRD 1.0 invoices are converted to CII");
+        };
+        let context = invoice.rsm_exchanged_document_context.as_ref().unwrap();
+        let guideline = context
+            .ram_guideline_specified_document_context_parameter
+            .as_ref()
+            .and_then(|parameter| parameter.id.as_ref())
+            .and_then(|id| id.id.as_deref());
+        assert_eq!(
+            guideline,
+            Some("urn:ferd:CrossIndustryDocument:invoice:1p0:comfort")
+        );
+        let xml = crate::convert_invoice(s, crate::InvoiceSyntax::Cii).unwrap();
+        assert!(!xml.contains("urn:ferd"), "{}", xml);
+        assert!(xml.contains("urn:cen.eu:en16931:2017"), "{}", xml);
+        let xml = crate::convert_invoice(s, crate::InvoiceSyntax::Ubl).unwrap();
+        assert!(
+            xml.contains("<cbc:CustomizationID>urn:cen.eu:en16931:2017</cbc:CustomizationID>"),
+            "{}",
+            xml
+        );
+        let pdf = crate::render_zugferd_pdf(&invoice).unwrap();
+        let pdf = lopdf::Document::load_mem(&pdf).unwrap();
+        let metadata = crate::read_facturx_metadata(&pdf).unwrap().unwrap();
+        assert_eq!(metadata.conformance_level.as_deref(), Some("EN 16931"));
+        let document = invoice.rsm_exchanged_document.as_ref().unwrap();
+        assert_eq!(
+            document.ram_id.as_ref().unwrap().id.as_deref(),
+            Some("RE-2014-0815")
+        );
+        assert_eq!(
+            document.ram_included_note[1].ram_content.as_deref(),
+            Some("Lieferant GmbH\nLieferantenstraße 20\n80333 München")
+        );
+        let settlement = invoice
+            .rsm_supply_chain_trade_transaction
+            .as_ref()
+            .and_then(|transaction| transaction.ram_applicable_header_trade_settlement.as_ref())
+            .unwrap();
+        let charge = &settlement.ram_specified_trade_allowance_charge[0];
+        assert_eq!(charge.ram_reason.as_deref(), Some("Versandkosten"));
+        assert_eq!(charge.ram_actual_amount.as_deref(), Some("10.00"));
+    }
+
+    /// A CII invoice with two lines declaring the EXTENDED profile, which allows sub-invoice lines
+    fn extended_cii_invoice() -> crate::CrossIndustryInvoice {
+        let s = include_str!("../tests/inputs/cii/01.01_comprehensive_test_uncefact.xml");
+        let InvoiceStandard::CII(mut invoice) = crate::parse_invoice(s).unwrap() else {
+            panic!("Expected a CII invoice");
+        };
+        invoice
+            .rsm_exchanged_document_context
+            .as_mut()
+            .and_then(|context| {
+                context
+                    .ram_guideline_specified_document_context_parameter
+                    .as_mut()
+            })
+            .and_then(|parameter| parameter.id.as_mut())
+            .unwrap()
+            .id =
+            Some("urn:cen.eu:en16931:2017#conformant#urn:factur-x.eu:1p0:extended".to_string());
+        invoice
+    }
+
+    /// A visual PDF with a valid XRechnung CII invoice embedded
+    fn hybrid_pdf() -> Vec<u8> {
+        let pdf = include_bytes!("../tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf");
+        let xml = include_str!("../tests/inputs/cii/01.01a-INVOICE_uncefact.xml");
+        let InvoiceStandard::CII(invoice) = validate_invoice(xml).unwrap() else {
+            panic!("Expected CII invoice");
+        };
+        crate::embed_invoice_into_pdf(pdf, &invoice).unwrap()
+    }
+
+    fn has_br_co_10(xml: &str) -> bool {
+        crate::validate_invoice_report(xml)
+            .unwrap()
+            .violations
+            .iter()
+            .any(|violation| violation.to_string().contains("BR-CO-10"))
+    }
+
+    #[test]
+    fn plain_invoice_lines_work() {
+        let invoice_standard = InvoiceStandard::CII(extended_cii_invoice());
+        let xml = einvoice_deps_yaserde::ser::to_string(&invoice_standard).unwrap();
+        assert!(crate::validate_invoice_report(&xml).unwrap().is_valid());
+
+        let tree = crate::invoice_line_tree(&invoice_standard);
+        assert_eq!(tree.len(), 2);
+        assert!(tree
+            .iter()
+            .all(|line| line.kind == crate::InvoiceLineKind::Line
+                && line.children.is_empty()
+                && line.in_totals()));
+
+        // A line net amount that is not a decimal cannot add up to the sum
+        let broken = xml.replacen(
+            "<ram:LineTotalAmount>4743.75</ram:LineTotalAmount>",
+            "<ram:LineTotalAmount>4743,75</ram:LineTotalAmount>",
+            1,
+        );
+        assert!(has_br_co_10(&broken));
+        let wrong = xml.replacen(
+            "<ram:LineTotalAmount>10781.25</ram:LineTotalAmount>",
+            "<ram:LineTotalAmount>10781.26</ram:LineTotalAmount>",
+            1,
+        );
+        assert!(has_br_co_10(&wrong));
+
+        // BR-CO-10 is checked for the EN 16931 profile as well
+        let s = include_str!("../tests/inputs/cii/01.01_comprehensive_test_uncefact.xml");
+        assert!(!has_br_co_10(s));
+        let wrong = s.replacen(
+            "<ram:LineTotalAmount>10781.25</ram:LineTotalAmount>",
+            "<ram:LineTotalAmount>10781.26</ram:LineTotalAmount>",
+            1,
+        );
+        assert!(has_br_co_10(&wrong));
+    }
+
+    #[test]
+    fn information_lines_work() {
+        let mut invoice = extended_cii_invoice();
+        let lines = &mut invoice
+            .rsm_supply_chain_trade_transaction
+            .as_mut()
+            .unwrap()
+            .ram_included_supply_chain_trade_line_item;
+        let mut information = lines[0].clone();
+        let document = information
+            .ram_associated_document_line_document
+            .as_mut()
+            .unwrap();
+        document.ram_line_id = Some("3".to_string());
+        document.ram_line_status_reason_code = Some("INFORMATION".to_string());
+        lines.push(information);
+        let invoice_standard = InvoiceStandard::CII(invoice);
+        let xml = einvoice_deps_yaserde::ser::to_string(&invoice_standard).unwrap();
+        assert!(crate::validate_invoice_report(&xml).unwrap().is_valid());
+
+        let tree = crate::extract_invoice_lines(&xml).unwrap();
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree[2].kind, crate::InvoiceLineKind::Information);
+        assert!(!tree[2].in_totals());
+        assert!(tree[..2].iter().all(crate::InvoiceLineNode::in_totals));
+
+        // Without its status the information line counts
+        let xml = xml.replacen(
+            "<ram:LineStatusReasonCode>INFORMATION</ram:LineStatusReasonCode>",
+            "",
+            1,
+        );
+        assert!(has_br_co_10(&xml));
+    }
+
+    #[test]
+    fn sub_invoice_lines_work() {
+        let mut invoice = extended_cii_invoice();
+        let lines = &mut invoice
+            .rsm_supply_chain_trade_transaction
+            .as_mut()
+            .unwrap()
+            .ram_included_supply_chain_trade_line_item;
+        let mut detail = lines[0].clone();
+        let document = detail
+            .ram_associated_document_line_document
+            .as_mut()
+            .unwrap();
+        document.ram_line_id = Some("1.1".to_string());
+        document.ram_parent_line_id = Some("1".to_string());
+        document.ram_line_status_reason_code = Some("DETAIL".to_string());
+        lines.insert(1, detail);
+        lines[0]
+            .ram_associated_document_line_document
+            .as_mut()
+            .unwrap()
+            .ram_line_status_reason_code = Some("GROUP".to_string());
+        let invoice_standard = InvoiceStandard::CII(invoice);
+        let xml = einvoice_deps_yaserde::ser::to_string(&invoice_standard).unwrap();
+        assert!(xml.contains("<ram:ParentLineID>1</ram:ParentLineID>"));
+        assert!(crate::validate_invoice_report(&xml).unwrap().is_valid());
+
+        let tree = crate::extract_invoice_lines(&xml).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].kind, crate::InvoiceLineKind::Group);
+        assert_eq!(tree[0].children.len(), 1);
+        assert_eq!(tree[0].children[0].id.as_deref(), Some("1.1"));
+        assert_eq!(tree[0].children[0].kind, crate::InvoiceLineKind::Detail);
+        assert!(!tree[0].children[0].in_totals());
+        assert_eq!(tree[1].kind, crate::InvoiceLineKind::Line);
+
+        // Without its parent the detail line counts twice
+        let xml = xml.replacen("<ram:ParentLineID>1</ram:ParentLineID>", "", 1);
+        let xml = xml.replacen(
+            "<ram:LineStatusReasonCode>DETAIL</ram:LineStatusReasonCode>",
+            "",
+            1,
+        );
+        assert!(has_br_co_10(&xml));
+
+        let s = include_str!("../tests/inputs/ubl/01.01_comprehensive_test_ubl.xml");
+        let InvoiceStandard::UBL(mut invoice) = crate::parse_invoice(s).unwrap() else {
+            panic!("Expected a UBL invoice");
+        };
+        invoice.cac_invoice_line[0].cac_sub_invoice_line.push(
+            crate::ubl::ubl_model::SubInvoiceLine {
+                cbc_id: Some(crate::ubl::ubl_model::Identifier {
+                    id: Some("1.1".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        );
+        let tree = crate::invoice_line_tree(&InvoiceStandard::UBL(invoice));
+        assert_eq!(tree[0].kind, crate::InvoiceLineKind::Group);
+        assert_eq!(tree[0].children[0].parent_id, tree[0].id);
+        assert_eq!(tree[0].children[0].kind, crate::InvoiceLineKind::Detail);
     }
 }
@@ -0,0 +1,270 @@
+use crate::cii::cii_model::RamIncludedSupplyChainTradeLineItem;
+use crate::ubl::ubl_model::{InvoiceLine, SubInvoiceLine, SubSubInvoiceLine, SubSubSubInvoiceLine};
+use crate::InvoiceStandard;
+
+/// The role of an invoice line in a hierarchy of group and sub-invoice lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum InvoiceLineKind {
+    /// A line without sub-invoice lines
+    Line,
+    /// A line grouping sub-invoice lines, its net amount includes theirs
+    Group,
+    /// A sub-invoice line, its net amount is included in that of its group line
+    Detail,
+    /// A line for information only, its net amount is not part of any total
+    Information,
+}
+
+/// An invoice line (BG-25) with its sub-invoice lines
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct InvoiceLineNode {
+    /// Invoice line identifier (BT-126)
+    pub id: Option<String>,
+    /// The identifier of the group line, `None` for a line on the top level
+    pub parent_id: Option<String>,
+    /// The role of the line, from the CII `LineStatusReasonCode` or the UBL nesting
+    pub kind: InvoiceLineKind,
+    /// Item name (BT-153)
+    pub item_name: Option<String>,
+    /// Invoiced quantity (BT-129)
+    pub quantity: Option<String>,
+    /// Invoiced quantity unit of measure code (BT-130)
+    pub unit_code: Option<String>,
+    /// Invoice line net amount (BT-131)
+    pub net_amount: Option<String>,
+    /// The sub-invoice lines in document order
+    pub children: Vec<InvoiceLineNode>,
+}
+
+impl InvoiceLineNode {
+    /// Whether the net amount is part of the sum of invoice line net amounts (BT-106)
+    pub fn in_totals(&self) -> bool {
+        line_in_totals(self.parent_id.is_some(), self.kind)
+    }
+}
+
+/// The amounts of sub-invoice lines are included in their group line, those of information lines in no total
+fn line_in_totals(has_parent: bool, kind: InvoiceLineKind) -> bool {
+    !has_parent && matches!(kind, InvoiceLineKind::Line | InvoiceLineKind::Group)
+}
+
+/// The identifier of a UBL invoice or sub-invoice line, the parent of its sub-invoice lines
+macro_rules! ubl_line_id {
+    ($line:expr) => {
+        $line.cbc_id.as_ref().and_then(|id| id.id.clone())
+    };
+}
+
+/// A node for any of the UBL line types, which only differ in the type of their sub-invoice lines
+macro_rules! ubl_line_node {
+    ($line:expr, $parent_id:expr, $children:expr) => {{
+        let line = $line;
+        let parent_id: &Option<String> = $parent_id;
+        let children: Vec<InvoiceLineNode> = $children;
+        InvoiceLineNode {
+            id: ubl_line_id!(line),
+            parent_id: parent_id.clone(),
+            kind: match (parent_id, children.is_empty()) {
+                (_, false) => InvoiceLineKind::Group,
+                (Some(_), true) => InvoiceLineKind::Detail,
+                (None, true) => InvoiceLineKind::Line,
+            },
+            item_name: line
+                .cac_item
+                .as_ref()
+                .and_then(|item| item.cbc_name.clone()),
+            quantity: line
+                .cbc_invoiced_quantity
+                .as_ref()
+                .and_then(|quantity| quantity.text.clone()),
+            unit_code: line
+                .cbc_invoiced_quantity
+                .as_ref()
+                .and_then(|quantity| quantity.unit_code.clone()),
+            net_amount: line
+                .cbc_line_extension_amount
+                .as_ref()
+                .and_then(|amount| amount.value)
+                .map(|value| value.to_string()),
+            children,
+        }
+    }};
+}
+
+/// List the invoice lines of an invoice with their sub-invoice lines
+/// # Arguments
+/// * `invoice` - The invoice
+/// # Returns
+/// * `Vec<InvoiceLineNode>` - The lines on the top level in document order, each with its sub-invoice lines
+///
+/// UBL nests `cac:SubInvoiceLine` in its group line, CII lists every line on the same level
+/// and refers to the group line by `ram:ParentLineID`.
+/// CII lines referring to an unknown line are kept on the top level with their `parent_id`.
+pub fn invoice_line_tree(invoice: &InvoiceStandard) -> Vec<InvoiceLineNode> {
+    match invoice {
+        InvoiceStandard::UBL(invoice) => invoice.cac_invoice_line.iter().map(ubl_line).collect(),
+        InvoiceStandard::CII(invoice) => cii_line_tree(
+            invoice
+                .rsm_supply_chain_trade_transaction
+                .as_ref()
+                .map(|transaction| {
+                    transaction
+                        .ram_included_supply_chain_trade_line_item
+                        .as_slice()
+                })
+                .unwrap_or_default(),
+        ),
+    }
+}
+
+/// Whether the net amount of a CII line is part of the sum of invoice line net amounts (BT-106)
+///
+/// Same as `InvoiceLineNode::in_totals`, without building the tree: whether a line has sub-invoice lines
+/// only decides between a group line and a line, which both count.
+pub(crate) fn cii_line_in_totals(line: &RamIncludedSupplyChainTradeLineItem) -> bool {
+    line_in_totals(parent_line_id(line).is_some(), cii_line_kind(line, false))
+}
+
+fn ubl_line(line: &InvoiceLine) -> InvoiceLineNode {
+    let id = ubl_line_id!(line);
+    let children = line
+        .cac_sub_invoice_line
+        .iter()
+        .map(|sub_line| ubl_sub_line(sub_line, &id))
+        .collect();
+    ubl_line_node!(line, &None, children)
+}
+
+fn ubl_sub_line(line: &SubInvoiceLine, parent_id: &Option<String>) -> InvoiceLineNode {
+    let id = ubl_line_id!(line);
+    let children = line
+        .cac_sub_invoice_line
+        .iter()
+        .map(|sub_line| ubl_sub_sub_line(sub_line, &id))
+        .collect();
+    ubl_line_node!(line, parent_id, children)
+}
+
+fn ubl_sub_sub_line(line: &SubSubInvoiceLine, parent_id: &Option<String>) -> InvoiceLineNode {
+    let id = ubl_line_id!(line);
+    let children = line
+        .cac_sub_invoice_line
+        .iter()
+        .map(|sub_line| ubl_sub_sub_sub_line(sub_line, &id))
+        .collect();
+    ubl_line_node!(line, parent_id, children)
+}
+
+fn ubl_sub_sub_sub_line(
+    line: &SubSubSubInvoiceLine,
+    parent_id: &Option<String>,
+) -> InvoiceLineNode {
+    ubl_line_node!(line, parent_id, Vec::new())
+}
+
+fn cii_line_tree(lines: &[RamIncludedSupplyChainTradeLineItem]) -> Vec<InvoiceLineNode> {
+    let parents = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| {
+            let parent_id = parent_line_id(line)?;
+            lines.iter().enumerate().position(|(candidate, parent)| {
+                candidate != index && line_id(parent).as_deref() == Some(parent_id.as_str())
+            })
+        })
+        .collect::<Vec<_>>();
+    let mut visited = vec![false; lines.len()];
+    let mut tree = Vec::new();
+    for index in 0..lines.len() {
+        if parents[index].is_none() {
+            tree.push(cii_line_node(lines, &parents, index, &mut visited));
+        }
+    }
+    // Lines whose parent references form a cycle have no line on the top level to start from
+    for index in 0..lines.len() {
+        if !visited[index] {
+            tree.push(cii_line_node(lines, &parents, index, &mut visited));
+        }
+    }
+    tree
+}
+
+fn cii_line_node(
+    lines: &[RamIncludedSupplyChainTradeLineItem],
+    parents: &[Option<usize>],
+    index: usize,
+    visited: &mut [bool],
+) -> InvoiceLineNode {
+    visited[index] = true;
+    let mut children = Vec::new();
+    for child in 0..lines.len() {
+        if parents[child] == Some(index) && !visited[child] {
+            children.push(cii_line_node(lines, parents, child, visited));
+        }
+    }
+    let line = &lines[index];
+    let parent_id = parent_line_id(line);
+    let quantity = line
+        .ram_specified_line_trade_delivery
+        .as_ref()
+        .and_then(|delivery| delivery.ram_billed_quantity.as_ref());
+    InvoiceLineNode {
+        id: line_id(line),
+        kind: cii_line_kind(line, !children.is_empty()),
+        parent_id,
+        item_name: line
+            .ram_specified_trade_product
+            .as_ref()
+            .and_then(|product| product.ram_name.clone()),
+        quantity: quantity.and_then(|quantity| quantity.text.clone()),
+        unit_code: quantity.and_then(|quantity| quantity.unit_code.clone()),
+        net_amount: line
+            .ram_specified_line_trade_settlement
+            .as_ref()
+            .and_then(|settlement| {
+                settlement
+                    .ram_specified_trade_settlement_line_monetary_summation
+                    .as_ref()
+            })
+            .and_then(|summation| summation.ram_line_total_amount.clone()),
+        children,
+    }
+}
+
+/// The role of a CII line, from its `LineStatusReasonCode` or else from its position in the tree
+fn cii_line_kind(
+    line: &RamIncludedSupplyChainTradeLineItem,
+    has_children: bool,
+) -> InvoiceLineKind {
+    match line_status_reason_code(line).as_deref() {
+        Some("INFORMATION") => InvoiceLineKind::Information,
+        Some("GROUP") => InvoiceLineKind::Group,
+        Some("DETAIL") => InvoiceLineKind::Detail,
+        _ if has_children => InvoiceLineKind::Group,
+        _ if parent_line_id(line).is_some() => InvoiceLineKind::Detail,
+        _ => InvoiceLineKind::Line,
+    }
+}
+
+fn line_id(line: &RamIncludedSupplyChainTradeLineItem) -> Option<String> {
+    line.ram_associated_document_line_document
+        .as_ref()
+        .and_then(|document| document.ram_line_id.as_deref())
+        .map(|id| id.trim().to_string())
+}
+
+fn parent_line_id(line: &RamIncludedSupplyChainTradeLineItem) -> Option<String> {
+    line.ram_associated_document_line_document
+        .as_ref()
+        .and_then(|document| document.ram_parent_line_id.as_deref())
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+fn line_status_reason_code(line: &RamIncludedSupplyChainTradeLineItem) -> Option<String> {
+    line.ram_associated_document_line_document
+        .as_ref()
+        .and_then(|document| document.ram_line_status_reason_code.as_deref())
+        .map(|code| code.trim().to_uppercase())
+}
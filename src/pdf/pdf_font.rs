@@ -0,0 +1,222 @@
+use lopdf::{dictionary, Document, Object, ObjectId, Stream};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use subsetter::GlyphRemapper;
+use ttf_parser::{Face, GlyphId};
+
+/// DejaVu Sans, licensed under the Bitstream Vera license, see `assets/fonts/LICENSE`
+pub(crate) const DEJAVU_SANS: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans.ttf");
+pub(crate) const DEJAVU_SANS_BOLD: &[u8] = include_bytes!("../../assets/fonts/DejaVuSans-Bold.ttf");
+
+/// The sRGB IEC61966-2.1 colour profile, licensed under CC0, see `assets/icc/LICENSE`
+const SRGB_ICC_PROFILE: &[u8] = include_bytes!("../../assets/icc/sRGB-v4.icc");
+const SRGB_OUTPUT_CONDITION: &str = "sRGB IEC61966-2.1";
+
+/// Shown for characters the font has no glyph for, PDF/A forbids showing `.notdef`
+const REPLACEMENT_CHARACTER: char = '?';
+
+/// A TrueType font embedded as a subset of the glyphs shown
+///
+/// Text is encoded as two byte glyph ids of the subset (`Identity-H`), every glyph is
+/// remapped on first use, so the subset only has to be built once all pages are written.
+pub(crate) struct EmbeddedFont {
+    name: &'static str,
+    data: &'static [u8],
+    face: Face<'static>,
+    glyphs: GlyphRemapper,
+    /// The character shown by each glyph of the subset, for the `ToUnicode` CMap
+    characters: BTreeMap<u16, char>,
+}
+
+impl EmbeddedFont {
+    /// Load a bundled font
+    /// # Arguments
+    /// * `name` - The PostScript name of the font
+    /// * `data` - The TrueType font file
+    pub(crate) fn new(name: &'static str, data: &'static [u8]) -> Self {
+        Self {
+            name,
+            data,
+            face: Face::parse(data, 0).expect("the bundled font is a valid TrueType font"),
+            glyphs: GlyphRemapper::new(),
+            characters: BTreeMap::new(),
+        }
+    }
+
+    /// The width of a text in points
+    pub(crate) fn width(&self, text: &str, size: f32) -> f32 {
+        let units = text
+            .chars()
+            .map(|c| self.advance(self.glyph(c).1))
+            .sum::<f32>();
+        units * size / self.face.units_per_em() as f32
+    }
+
+    /// Encode a text as glyph ids of the subset, adding the glyphs to the subset
+    pub(crate) fn encode(&mut self, text: &str) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(text.len() * 2);
+        for c in text.chars() {
+            let (c, glyph) = self.glyph(c);
+            let glyph = self.glyphs.remap(glyph.0);
+            self.characters.insert(glyph, c);
+            encoded.extend(glyph.to_be_bytes());
+        }
+        encoded
+    }
+
+    /// Add the subset to a document as a `Type0` font
+    /// # Returns
+    /// * `ObjectId` - The font dictionary to refer to from the page resources
+    pub(crate) fn write(&self, document: &mut Document) -> Result<ObjectId, lopdf::Error> {
+        let subset = subsetter::subset(self.data, 0, &self.glyphs)
+            .map_err(|e| lopdf::Error::Syntax(format!("Failed to subset {}: {}", self.name, e)))?;
+        let base_font = format!("{}+{}", self.subset_tag(), self.name);
+        let scale = 1000.0 / self.face.units_per_em() as f32;
+        let scaled = |value: i16| (value as f32 * scale).round() as i64;
+
+        let mut font_file = Stream::new(dictionary! { "Length1" => subset.len() as i64 }, subset);
+        font_file.compress()?;
+        let font_file_id = document.add_object(font_file);
+
+        let bounding_box = self.face.global_bounding_box();
+        let descriptor_id = document.add_object(dictionary! {
+            "Type" => "FontDescriptor",
+            "FontName" => Object::Name(base_font.clone().into_bytes()),
+            "Flags" => 32,
+            "FontBBox" => vec![
+                scaled(bounding_box.x_min).into(),
+                scaled(bounding_box.y_min).into(),
+                scaled(bounding_box.x_max).into(),
+                scaled(bounding_box.y_max).into(),
+            ],
+            "ItalicAngle" => self.face.italic_angle() as i64,
+            "Ascent" => scaled(self.face.ascender()),
+            "Descent" => scaled(self.face.descender()),
+            "CapHeight" => scaled(self.face.capital_height().unwrap_or(self.face.ascender())),
+            "StemV" => 80,
+            "FontFile2" => font_file_id,
+        });
+
+        let widths = self
+            .glyphs
+            .remapped_gids()
+            .map(|glyph| ((self.advance(GlyphId(glyph)) * scale).round() as i64).into())
+            .collect::<Vec<Object>>();
+        let cid_font_id = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "CIDFontType2",
+            "BaseFont" => Object::Name(base_font.clone().into_bytes()),
+            "CIDSystemInfo" => dictionary! {
+                "Registry" => Object::string_literal("Adobe"),
+                "Ordering" => Object::string_literal("Identity"),
+                "Supplement" => 0,
+            },
+            "FontDescriptor" => descriptor_id,
+            "W" => vec![0.into(), Object::Array(widths)],
+            "CIDToGIDMap" => "Identity",
+        });
+
+        let mut to_unicode = Stream::new(dictionary! {}, self.to_unicode().into_bytes());
+        to_unicode.compress()?;
+        let to_unicode_id = document.add_object(to_unicode);
+
+        Ok(document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type0",
+            "BaseFont" => Object::Name(base_font.into_bytes()),
+            "Encoding" => "Identity-H",
+            "DescendantFonts" => vec![cid_font_id.into()],
+            "ToUnicode" => to_unicode_id,
+        }))
+    }
+
+    /// The glyph shown for a character and the character it shows
+    fn glyph(&self, c: char) -> (char, GlyphId) {
+        let c = if c.is_whitespace() { ' ' } else { c };
+        match self.face.glyph_index(c) {
+            Some(glyph) if glyph.0 != 0 => (c, glyph),
+            _ => (
+                REPLACEMENT_CHARACTER,
+                self.face
+                    .glyph_index(REPLACEMENT_CHARACTER)
+                    .expect("the bundled font has a glyph for '?'"),
+            ),
+        }
+    }
+
+    fn advance(&self, glyph: GlyphId) -> f32 {
+        self.face.glyph_hor_advance(glyph).unwrap_or_default() as f32
+    }
+
+    /// Six upper case letters naming the subset, derived from the glyphs it contains
+    fn subset_tag(&self) -> String {
+        let mut hash = self
+            .glyphs
+            .remapped_gids()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, glyph| {
+                (hash ^ glyph as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+        (0..6)
+            .map(|_| {
+                let letter = (b'A' + (hash % 26) as u8) as char;
+                hash /= 26;
+                letter
+            })
+            .collect()
+    }
+
+    /// A CMap from the glyph ids of the subset to the characters they show
+    fn to_unicode(&self) -> String {
+        let mut cmap = String::from(
+            "/CIDInit /ProcSet findresource begin\n\
+             12 dict begin\n\
+             begincmap\n\
+             /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+             /CMapName /Adobe-Identity-UCS def\n\
+             /CMapType 2 def\n\
+             1 begincodespacerange\n\
+             <0000> <FFFF>\n\
+             endcodespacerange\n",
+        );
+        let characters = self.characters.iter().collect::<Vec<_>>();
+        // A bfchar block may hold at most 100 mappings
+        for block in characters.chunks(100) {
+            writeln!(cmap, "{} beginbfchar", block.len()).unwrap();
+            for (glyph, c) in block {
+                let mut utf16 = [0u16; 2];
+                let unicode = c
+                    .encode_utf16(&mut utf16)
+                    .iter()
+                    .map(|unit| format!("{:04X}", unit))
+                    .collect::<String>();
+                writeln!(cmap, "<{:04X}> <{}>", glyph, unicode).unwrap();
+            }
+            cmap.push_str("endbfchar\n");
+        }
+        cmap.push_str(
+            "endcmap\n\
+             CMapName currentdict /CMap defineresource pop\n\
+             end\n\
+             end\n",
+        );
+        cmap
+    }
+}
+
+/// Add the sRGB output intent PDF/A requires for device dependent colours
+/// # Returns
+/// * `Object` - The `OutputIntents` array of the document catalog
+pub(crate) fn srgb_output_intents(document: &mut Document) -> Result<Object, lopdf::Error> {
+    let mut profile = Stream::new(dictionary! { "N" => 3 }, SRGB_ICC_PROFILE.to_vec());
+    profile.compress()?;
+    let profile_id = document.add_object(profile);
+    Ok(vec![dictionary! {
+        "Type" => "OutputIntent",
+        "S" => "GTS_PDFA1",
+        "OutputConditionIdentifier" => Object::string_literal(SRGB_OUTPUT_CONDITION),
+        "Info" => Object::string_literal(SRGB_OUTPUT_CONDITION),
+        "DestOutputProfile" => profile_id,
+    }
+    .into()]
+    .into())
+}
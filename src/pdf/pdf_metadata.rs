@@ -0,0 +1,558 @@
+use crate::pdf::pdf_reader::stream_content;
+use einvoice_deps_yaserde::__xml::name::OwnedName;
+use einvoice_deps_yaserde::__xml::reader::{EventReader, ParserConfig, XmlEvent};
+use einvoice_deps_yaserde::__xml::writer::{
+    self, EmitterConfig, EventWriter, XmlEvent as WriterEvent,
+};
+use lopdf::{decode_text_string, Document, Object};
+use std::collections::HashSet;
+
+/// Namespaces of the Factur-X / ZUGFeRD XMP extension schema across its versions
+const FACTUR_X_NAMESPACES: [&str; 3] = [
+    "urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#",
+    "urn:zugferd:pdfa:CrossIndustryDocument:invoice:2p0#",
+    "urn:ferd:pdfa:CrossIndustryDocument:invoice:1p0#",
+];
+
+const RDF_NAMESPACE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+pub(crate) const PDFA_ID_NAMESPACE: &str = "http://www.aiim.org/pdfa/ns/id/";
+const PDFA_EXTENSION_NAMESPACE: &str = "http://www.aiim.org/pdfa/ns/extension/";
+const PDFA_SCHEMA_NAMESPACE: &str = "http://www.aiim.org/pdfa/ns/schema#";
+const PDFA_PROPERTY_NAMESPACE: &str = "http://www.aiim.org/pdfa/ns/property#";
+const XMP_NAMESPACE: &str = "http://ns.adobe.com/xap/1.0/";
+const PDF_NAMESPACE: &str = "http://ns.adobe.com/pdf/1.3/";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+
+/// The XMP packet a PDF without metadata starts from
+const EMPTY_XMP: &str = r#"<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"/></x:xmpmeta>"#;
+
+/// The document information dictionary entries and the XMP properties PDF/A requires to match them:
+/// entry, namespace, prefix, property and the container of language alternatives or ordered values
+const INFO_PROPERTIES: [(&str, &str, &str, &str, Option<&str>); 7] = [
+    ("Title", DC_NAMESPACE, "dc", "title", Some("Alt")),
+    ("Author", DC_NAMESPACE, "dc", "creator", Some("Seq")),
+    ("Subject", DC_NAMESPACE, "dc", "description", Some("Alt")),
+    ("Keywords", PDF_NAMESPACE, "pdf", "Keywords", None),
+    ("Creator", XMP_NAMESPACE, "xmp", "CreatorTool", None),
+    ("Producer", PDF_NAMESPACE, "pdf", "Producer", None),
+    ("CreationDate", XMP_NAMESPACE, "xmp", "CreateDate", None),
+];
+
+/// The properties of the Factur-X extension schema with their description
+const FACTUR_X_PROPERTIES: [(&str, &str); 4] = [
+    ("DocumentFileName", "The name of the embedded XML document"),
+    (
+        "DocumentType",
+        "The type of the hybrid document in capital letters, e.g. INVOICE or ORDER",
+    ),
+    (
+        "Version",
+        "The actual version of the standard applying to the embedded XML document",
+    ),
+    (
+        "ConformanceLevel",
+        "The conformance level of the embedded XML document",
+    ),
+];
+
+/// The Factur-X / ZUGFeRD properties declared in the XMP metadata of a PDF
+#[derive(Debug, Clone, PartialEq, uniffi::Record)]
+pub struct FacturXMetadata {
+    /// `fx:DocumentType`, e.g. `INVOICE`
+    pub document_type: Option<String>,
+    /// `fx:DocumentFileName`, the name of the embedded XML file
+    pub document_file_name: Option<String>,
+    /// `fx:Version`, the version of the extension schema
+    pub version: Option<String>,
+    /// `fx:ConformanceLevel`, e.g. `BASIC`, `EN 16931` or `EXTENDED`
+    pub conformance_level: Option<String>,
+}
+
+/// Read the Factur-X / ZUGFeRD properties from the XMP metadata of a PDF
+/// # Arguments
+/// * `document` - The PDF document
+/// # Returns
+/// * `Option<FacturXMetadata>` - The declared properties, `None` if the PDF declares none
+/// # Errors
+/// * `lopdf::Error` - If the metadata stream cannot be decoded
+pub fn read_facturx_metadata(document: &Document) -> Result<Option<FacturXMetadata>, lopdf::Error> {
+    let Some(xmp) = xmp_packet(document)? else {
+        return Ok(None);
+    };
+    let properties = xmp_properties(&xmp)
+        .into_iter()
+        .filter(|(namespace, _, _)| FACTUR_X_NAMESPACES.contains(&namespace.as_str()))
+        .collect::<Vec<_>>();
+    if properties.is_empty() {
+        return Ok(None);
+    }
+    let property = |name: &str| {
+        properties
+            .iter()
+            .find(|(_, local_name, _)| local_name == name)
+            .map(|(_, _, value)| value.clone())
+    };
+    Ok(Some(FacturXMetadata {
+        document_type: property("DocumentType"),
+        document_file_name: property("DocumentFileName"),
+        version: property("Version"),
+        conformance_level: property("ConformanceLevel"),
+    }))
+}
+
+/// The content of the document level `/Metadata` stream
+pub(crate) fn xmp_packet(document: &Document) -> Result<Option<Vec<u8>>, lopdf::Error> {
+    match document.catalog()?.get_deref(b"Metadata", document) {
+        Ok(Object::Stream(stream)) => stream_content(stream).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/// Collect the simple properties of all `rdf:Description` elements as namespace / name / value triples
+///
+/// XMP allows a property both as a child element and as an attribute of its description, so both are read.
+/// Properties with structured values like `rdf:Bag` are skipped.
+pub(crate) fn xmp_properties(xmp: &[u8]) -> Vec<(String, String, String)> {
+    let mut properties = Vec::new();
+    let mut in_description = 0usize;
+    let mut current: Option<(String, String, String)> = None;
+    let mut depth = 0usize;
+
+    for event in EventReader::new(xmp) {
+        match event {
+            Ok(XmlEvent::StartElement {
+                name, attributes, ..
+            }) => {
+                depth += 1;
+                let namespace = name.namespace.clone().unwrap_or_default();
+                if namespace == RDF_NAMESPACE && name.local_name == "Description" {
+                    in_description = depth;
+                    for attribute in attributes {
+                        let attribute_namespace = attribute.name.namespace.unwrap_or_default();
+                        if !attribute_namespace.is_empty() && attribute_namespace != RDF_NAMESPACE {
+                            properties.push((
+                                attribute_namespace,
+                                attribute.name.local_name,
+                                attribute.value,
+                            ));
+                        }
+                    }
+                } else if in_description > 0 && depth == in_description + 1 {
+                    current = Some((namespace, name.local_name, String::new()));
+                } else {
+                    // Structured value, not a simple property
+                    current = None;
+                }
+            }
+            Ok(XmlEvent::Characters(text)) | Ok(XmlEvent::CData(text)) => {
+                if let Some((_, _, value)) = current.as_mut() {
+                    value.push_str(&text);
+                }
+            }
+            Ok(XmlEvent::EndElement { .. }) => {
+                if depth == in_description + 1 {
+                    if let Some((namespace, name, value)) = current.take() {
+                        properties.push((namespace, name, value.trim().to_string()));
+                    }
+                }
+                if depth == in_description {
+                    in_description = 0;
+                }
+                depth -= 1;
+            }
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    properties
+}
+
+/// Write the XMP metadata of a hybrid PDF, keeping the properties of its current metadata
+/// # Arguments
+/// * `document` - The PDF document
+/// * `metadata` - The Factur-X properties to declare
+/// * `date` - The modification date in XMP format, e.g. `2025-01-17T12:00:00+00:00`
+/// # Returns
+/// * `String` - The XMP packet
+///
+/// The PDF/A identification, the Factur-X properties and their extension schema and the modification dates
+/// replace existing ones. Entries of the document information dictionary the metadata lacks are added,
+/// so that both stay in sync as PDF/A requires. Metadata that cannot be read is replaced.
+pub(crate) fn facturx_xmp_packet(
+    document: &Document,
+    metadata: &FacturXMetadata,
+    date: &str,
+) -> String {
+    let info = info_properties(document);
+    let xmp = xmp_packet(document)
+        .ok()
+        .flatten()
+        .and_then(|xmp| merge_xmp(&xmp, metadata, date, &info))
+        .or_else(|| merge_xmp(EMPTY_XMP.as_bytes(), metadata, date, &info))
+        .expect("the empty XMP packet can be merged");
+    format!(
+        "<?xpacket begin=\"{}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n{}\n<?xpacket end=\"w\"?>",
+        '\u{feff}', xmp
+    )
+}
+
+/// An entry of the document information dictionary as the XMP property it corresponds to
+struct InfoProperty {
+    namespace: &'static str,
+    prefix: &'static str,
+    name: &'static str,
+    container: Option<&'static str>,
+    value: String,
+}
+
+fn info_properties(document: &Document) -> Vec<InfoProperty> {
+    let Ok(info) = document
+        .trailer
+        .get_deref(b"Info", document)
+        .and_then(Object::as_dict)
+    else {
+        return Vec::new();
+    };
+    INFO_PROPERTIES
+        .iter()
+        .filter_map(|(entry, namespace, prefix, name, container)| {
+            let value =
+                decode_text_string(info.get_deref(entry.as_bytes(), document).ok()?).ok()?;
+            let value = if *entry == "CreationDate" {
+                xmp_date(&value)?
+            } else {
+                value
+            };
+            Some(InfoProperty {
+                namespace,
+                prefix,
+                name,
+                container: *container,
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Convert a PDF date like `D:20250117120000+01'00'` to the XMP format `2025-01-17T12:00:00+01:00`
+fn xmp_date(pdf_date: &str) -> Option<String> {
+    let date = pdf_date.strip_prefix("D:").unwrap_or(pdf_date);
+    let digits = date
+        .get(..14)
+        .filter(|d| d.bytes().all(|b| b.is_ascii_digit()))?;
+    let offset = match date[14..].replace('\'', "").as_str() {
+        "" | "Z" => "Z".to_string(),
+        offset if offset.len() == 5 && matches!(&offset[..1], "+" | "-") => {
+            format!("{}:{}", &offset[..3], &offset[3..])
+        }
+        _ => return None,
+    };
+    Some(format!(
+        "{}-{}-{}T{}:{}:{}{}",
+        &digits[..4],
+        &digits[4..6],
+        &digits[6..8],
+        &digits[8..10],
+        &digits[10..12],
+        &digits[12..14],
+        offset
+    ))
+}
+
+fn is(name: &OwnedName, namespace: &str, local_name: &str) -> bool {
+    name.namespace.as_deref() == Some(namespace) && name.local_name == local_name
+}
+
+/// Whether a property is replaced by the Factur-X metadata
+fn is_replaced(name: &OwnedName) -> bool {
+    let namespace = name.namespace.as_deref().unwrap_or_default();
+    namespace == PDFA_ID_NAMESPACE
+        || FACTUR_X_NAMESPACES.contains(&namespace)
+        || (namespace == XMP_NAMESPACE
+            && matches!(name.local_name.as_str(), "ModifyDate" | "MetadataDate"))
+}
+
+/// Copy an XMP packet without the replaced properties and extension schemas, then add the Factur-X metadata
+///
+/// `None` if the packet is not well-formed or has no `rdf:RDF` element to add to.
+fn merge_xmp(
+    xmp: &[u8],
+    metadata: &FacturXMetadata,
+    date: &str,
+    info: &[InfoProperty],
+) -> Option<String> {
+    let reader = EventReader::new_with_config(
+        xmp,
+        ParserConfig::new()
+            .trim_whitespace(true)
+            .ignore_comments(true),
+    );
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .write_document_declaration(false)
+        .create_writer(Vec::new());
+    let mut path: Vec<OwnedName> = Vec::new();
+    // The depth within a replaced property, which is left out
+    let mut skipped = 0usize;
+    // An extension schema being read, left out if it declares the Factur-X properties
+    let mut schema: Option<(usize, Vec<XmlEvent>)> = None;
+    // A description written only once it turns out to keep a property
+    let mut description: Option<XmlEvent> = None;
+    let mut present = HashSet::new();
+    let mut has_schemas = false;
+    let mut merged = false;
+    for event in reader {
+        let event = event.ok()?;
+        if skipped > 0 {
+            match event {
+                XmlEvent::StartElement { .. } => skipped += 1,
+                XmlEvent::EndElement { .. } => skipped -= 1,
+                _ => {}
+            }
+            continue;
+        }
+        match event {
+            XmlEvent::StartElement {
+                name,
+                attributes,
+                namespace,
+            } => {
+                let parent = path.last();
+                let grandparent = path.len().checked_sub(2).map(|index| &path[index]);
+                if parent.is_some_and(|parent| is(parent, RDF_NAMESPACE, "Description")) {
+                    if is_replaced(&name) {
+                        skipped = 1;
+                        continue;
+                    }
+                    present.insert((name.namespace.clone(), name.local_name.clone()));
+                }
+                let attributes = if is(&name, RDF_NAMESPACE, "Description") {
+                    attributes
+                        .into_iter()
+                        .filter(|attribute| !is_replaced(&attribute.name))
+                        .inspect(|attribute| {
+                            present.insert((
+                                attribute.name.namespace.clone(),
+                                attribute.name.local_name.clone(),
+                            ));
+                        })
+                        .collect()
+                } else {
+                    attributes
+                };
+                let in_schemas = |element: Option<&OwnedName>| {
+                    element.is_some_and(|element| is(element, PDFA_EXTENSION_NAMESPACE, "schemas"))
+                };
+                if is(&name, RDF_NAMESPACE, "Bag") && in_schemas(parent) {
+                    has_schemas = true;
+                }
+                if is(&name, RDF_NAMESPACE, "li")
+                    && parent.is_some_and(|parent| is(parent, RDF_NAMESPACE, "Bag"))
+                    && in_schemas(grandparent)
+                {
+                    schema = Some((path.len(), Vec::new()));
+                }
+                let is_description = is(&name, RDF_NAMESPACE, "Description");
+                let is_empty = attributes
+                    .iter()
+                    .all(|attribute| is(&attribute.name, RDF_NAMESPACE, "about"));
+                path.push(name.clone());
+                let event = XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                };
+                if let Some(description) = description.take() {
+                    write_event(&mut writer, &description)?;
+                }
+                match schema.as_mut() {
+                    Some((_, events)) => events.push(event),
+                    None if is_description && is_empty => description = Some(event),
+                    None => write_event(&mut writer, &event)?,
+                }
+            }
+            XmlEvent::EndElement { name } => {
+                path.pop();
+                // A description without properties is left out
+                if description.take().is_some() {
+                    continue;
+                }
+                if is(&name, RDF_NAMESPACE, "Bag")
+                    && path
+                        .last()
+                        .is_some_and(|parent| is(parent, PDFA_EXTENSION_NAMESPACE, "schemas"))
+                {
+                    write_extension_schema(&mut writer).ok()?;
+                }
+                if is(&name, RDF_NAMESPACE, "RDF") {
+                    write_properties(&mut writer, metadata, date, info, &present, has_schemas)
+                        .ok()?;
+                    merged = true;
+                }
+                let event = XmlEvent::EndElement { name };
+                match schema.take() {
+                    Some((depth, mut events)) => {
+                        events.push(event);
+                        if path.len() > depth {
+                            schema = Some((depth, events));
+                        } else if !declares_facturx_schema(&events) {
+                            for event in &events {
+                                write_event(&mut writer, event)?;
+                            }
+                        }
+                    }
+                    None => write_event(&mut writer, &event)?,
+                }
+            }
+            XmlEvent::Characters(_) | XmlEvent::CData(_) => {
+                if let Some(description) = description.take() {
+                    write_event(&mut writer, &description)?;
+                }
+                match schema.as_mut() {
+                    Some((_, events)) => events.push(event),
+                    None => write_event(&mut writer, &event)?,
+                }
+            }
+            // The packet wrapper is written anew
+            _ => {}
+        }
+    }
+    if !merged {
+        return None;
+    }
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+fn write_event(writer: &mut EventWriter<Vec<u8>>, event: &XmlEvent) -> Option<()> {
+    match event.as_writer_event() {
+        Some(event) => writer.write(event).ok(),
+        None => Some(()),
+    }
+}
+
+/// Whether an extension schema read from the packet describes a Factur-X namespace
+fn declares_facturx_schema(events: &[XmlEvent]) -> bool {
+    events.iter().any(|event| {
+        matches!(event, XmlEvent::Characters(text) if FACTUR_X_NAMESPACES.contains(&text.trim()))
+    })
+}
+
+/// Write a simple property
+fn write_property(
+    writer: &mut EventWriter<Vec<u8>>,
+    name: &str,
+    value: &str,
+) -> writer::Result<()> {
+    writer.write(WriterEvent::start_element(name))?;
+    writer.write(WriterEvent::characters(value))?;
+    writer.write(WriterEvent::end_element())
+}
+
+/// Write the PDF/A identification, the modification dates, the Factur-X properties and the
+/// information dictionary entries the packet lacks in a new `rdf:Description`
+fn write_properties(
+    writer: &mut EventWriter<Vec<u8>>,
+    metadata: &FacturXMetadata,
+    date: &str,
+    info: &[InfoProperty],
+    present: &HashSet<(Option<String>, String)>,
+    has_schemas: bool,
+) -> writer::Result<()> {
+    writer.write(
+        WriterEvent::start_element("rdf:Description")
+            .attr("rdf:about", "")
+            .ns("pdfaid", PDFA_ID_NAMESPACE)
+            .ns("xmp", XMP_NAMESPACE)
+            .ns("fx", FACTUR_X_NAMESPACES[0]),
+    )?;
+    write_property(writer, "pdfaid:part", "3")?;
+    write_property(writer, "pdfaid:conformance", "B")?;
+    write_property(writer, "xmp:ModifyDate", date)?;
+    write_property(writer, "xmp:MetadataDate", date)?;
+    for (name, value) in [
+        ("fx:DocumentType", &metadata.document_type),
+        ("fx:DocumentFileName", &metadata.document_file_name),
+        ("fx:Version", &metadata.version),
+        ("fx:ConformanceLevel", &metadata.conformance_level),
+    ] {
+        if let Some(value) = value {
+            write_property(writer, name, value)?;
+        }
+    }
+    for property in info {
+        if present.contains(&(
+            Some(property.namespace.to_string()),
+            property.name.to_string(),
+        )) {
+            continue;
+        }
+        let name = format!("{}:{}", property.prefix, property.name);
+        writer.write(
+            WriterEvent::start_element(name.as_str()).ns(property.prefix, property.namespace),
+        )?;
+        match property.container {
+            Some(container) => {
+                writer.write(WriterEvent::start_element(
+                    format!("rdf:{}", container).as_str(),
+                ))?;
+                let item = WriterEvent::start_element("rdf:li");
+                writer.write(if container == "Alt" {
+                    item.attr("xml:lang", "x-default")
+                } else {
+                    item
+                })?;
+                writer.write(WriterEvent::characters(&property.value))?;
+                writer.write(WriterEvent::end_element())?;
+                writer.write(WriterEvent::end_element())?;
+            }
+            None => writer.write(WriterEvent::characters(&property.value))?,
+        }
+        writer.write(WriterEvent::end_element())?;
+    }
+    writer.write(WriterEvent::end_element())?;
+
+    if !has_schemas {
+        writer.write(
+            WriterEvent::start_element("rdf:Description")
+                .attr("rdf:about", "")
+                .ns("pdfaExtension", PDFA_EXTENSION_NAMESPACE),
+        )?;
+        writer.write(WriterEvent::start_element("pdfaExtension:schemas"))?;
+        writer.write(WriterEvent::start_element("rdf:Bag"))?;
+        write_extension_schema(writer)?;
+        writer.write(WriterEvent::end_element())?;
+        writer.write(WriterEvent::end_element())?;
+        writer.write(WriterEvent::end_element())?;
+    }
+    Ok(())
+}
+
+/// Write the `rdf:li` declaring the Factur-X extension schema
+fn write_extension_schema(writer: &mut EventWriter<Vec<u8>>) -> writer::Result<()> {
+    writer.write(
+        WriterEvent::start_element("rdf:li")
+            .attr("rdf:parseType", "Resource")
+            .ns("pdfaSchema", PDFA_SCHEMA_NAMESPACE)
+            .ns("pdfaProperty", PDFA_PROPERTY_NAMESPACE),
+    )?;
+    write_property(
+        writer,
+        "pdfaSchema:schema",
+        "Factur-X PDFA Extension Schema",
+    )?;
+    write_property(writer, "pdfaSchema:namespaceURI", FACTUR_X_NAMESPACES[0])?;
+    write_property(writer, "pdfaSchema:prefix", "fx")?;
+    writer.write(WriterEvent::start_element("pdfaSchema:property"))?;
+    writer.write(WriterEvent::start_element("rdf:Seq"))?;
+    for (name, description) in FACTUR_X_PROPERTIES {
+        writer.write(WriterEvent::start_element("rdf:li").attr("rdf:parseType", "Resource"))?;
+        write_property(writer, "pdfaProperty:name", name)?;
+        write_property(writer, "pdfaProperty:valueType", "Text")?;
+        write_property(writer, "pdfaProperty:category", "external")?;
+        write_property(writer, "pdfaProperty:description", description)?;
+        writer.write(WriterEvent::end_element())?;
+    }
+    writer.write(WriterEvent::end_element())?;
+    writer.write(WriterEvent::end_element())?;
+    writer.write(WriterEvent::end_element())
+}
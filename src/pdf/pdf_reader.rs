@@ -0,0 +1,340 @@
+use lopdf::{Dictionary, Document, Object, ObjectId, Stream};
+
+/// File names under which ZUGFeRD, Factur-X and XRechnung embed the invoice, in order of preference
+pub const INVOICE_FILE_NAMES: [&str; 4] = [
+    "factur-x.xml",
+    "zugferd-invoice.xml",
+    "ZUGFeRD-invoice.xml",
+    "xrechnung.xml",
+];
+
+/// AFRelationship values a hybrid invoice uses for its structured data
+const INVOICE_AF_RELATIONSHIPS: [&str; 3] = ["Alternative", "Data", "Source"];
+
+/// An XML file attached to a PDF that may carry the structured invoice
+#[derive(uniffi::Record)]
+pub struct EmbeddedXmlCandidate {
+    /// The file name from the file specification, falling back to the name tree key
+    pub file_name: String,
+    /// The `/AFRelationship` of the file specification, e.g. `Alternative`
+    pub af_relationship: Option<String>,
+    /// Whether the file specification is listed in the document level `/AF` array
+    pub associated: bool,
+    /// Whether the file name is one of the `INVOICE_FILE_NAMES`
+    pub preferred: bool,
+    /// The decoded content of the embedded file, empty if it cannot be decoded
+    pub content: Vec<u8>,
+    /// Why the content of the embedded file cannot be decoded, e.g. an unsupported filter
+    pub error: Option<String>,
+}
+
+impl EmbeddedXmlCandidate {
+    fn rank(&self) -> (usize, bool, bool) {
+        let name_rank = INVOICE_FILE_NAMES
+            .iter()
+            .position(|name| *name == self.file_name)
+            .or_else(|| {
+                INVOICE_FILE_NAMES
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(&self.file_name))
+            })
+            .unwrap_or(INVOICE_FILE_NAMES.len());
+        let relationship = self
+            .af_relationship
+            .as_deref()
+            .is_some_and(|relationship| INVOICE_AF_RELATIONSHIPS.contains(&relationship));
+        (name_rank, !relationship, !self.associated)
+    }
+}
+
+/// Find all XML files attached to a PDF, most likely invoice first
+/// # Arguments
+/// * `document` - The PDF document
+/// # Returns
+/// * `Vec<EmbeddedXmlCandidate>` - The attached XML files
+/// # Errors
+/// * `lopdf::Error` - If the PDF structure is invalid
+///
+/// Attachments are collected from the `EmbeddedFiles` name tree including its `/Kids` and from the document level `/AF` array.
+/// Candidates are ordered by `INVOICE_FILE_NAMES`, then by an `/AFRelationship` of `Alternative`, `Data` or `Source`,
+/// then by being associated with the document and finally by their order in the PDF.
+/// A file that cannot be decoded is listed with its `error` instead of failing the whole PDF.
+pub fn find_embedded_xml_files(
+    document: &Document,
+) -> Result<Vec<EmbeddedXmlCandidate>, lopdf::Error> {
+    let mut candidates = Vec::new();
+    for file_spec in attached_file_specs(document)? {
+        if !file_spec.file_name.to_lowercase().ends_with(".xml") {
+            continue;
+        }
+        let (content, error) = match file_spec_content(document, &file_spec.dict) {
+            Ok(Some(content)) => (content, None),
+            Ok(None) => continue,
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        candidates.push(EmbeddedXmlCandidate {
+            preferred: INVOICE_FILE_NAMES
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&file_spec.file_name)),
+            af_relationship: file_spec.af_relationship(),
+            file_name: file_spec.file_name,
+            associated: file_spec.associated,
+            content,
+            error,
+        });
+    }
+    candidates.sort_by_key(EmbeddedXmlCandidate::rank);
+    Ok(candidates)
+}
+
+/// A file embedded in a PDF
+#[derive(uniffi::Record)]
+pub struct PdfAttachment {
+    /// The file name from the file specification, falling back to the name tree key
+    pub file_name: String,
+    /// The `/Desc` of the file specification
+    pub description: Option<String>,
+    /// The MIME type from the `/Subtype` of the embedded file stream, e.g. `text/csv`
+    pub mime_type: Option<String>,
+    /// The `/AFRelationship` of the file specification, e.g. `Supplement`
+    pub af_relationship: Option<String>,
+    /// Whether the file specification is listed in the document level `/AF` array
+    pub associated: bool,
+    /// The size of the decompressed content in bytes
+    pub size: u64,
+    /// The decompressed content of the embedded file, empty if it cannot be decompressed
+    pub content: Vec<u8>,
+    /// Why the content of the embedded file cannot be decompressed, e.g. an unsupported filter
+    pub error: Option<String>,
+}
+
+/// Find all files embedded in a PDF
+/// # Arguments
+/// * `document` - The PDF document
+/// # Returns
+/// * `Vec<PdfAttachment>` - The embedded files in the order of the PDF
+/// # Errors
+/// * `lopdf::Error` - If the PDF structure is invalid
+///
+/// Attachments are collected from the `EmbeddedFiles` name tree including its `/Kids` and from the document level `/AF` array.
+/// File specifications without an embedded file stream, i.e. references to external files, are skipped.
+/// A file that cannot be decompressed is listed with its `error` instead of failing the whole PDF.
+pub fn find_pdf_attachments(document: &Document) -> Result<Vec<PdfAttachment>, lopdf::Error> {
+    let mut attachments = Vec::new();
+    for file_spec in attached_file_specs(document)? {
+        let Some(stream) = embedded_file_stream(document, &file_spec.dict) else {
+            continue;
+        };
+        let (content, error) = match stream_content(stream) {
+            Ok(content) => (content, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        attachments.push(PdfAttachment {
+            description: file_spec
+                .dict
+                .get(b"Desc")
+                .and_then(Object::as_str)
+                .ok()
+                .map(decode_text),
+            mime_type: stream
+                .dict
+                .get(b"Subtype")
+                .and_then(Object::as_name_str)
+                .ok()
+                .map(ToString::to_string),
+            af_relationship: file_spec.af_relationship(),
+            file_name: file_spec.file_name,
+            associated: file_spec.associated,
+            size: content.len() as u64,
+            content,
+            error,
+        });
+    }
+    Ok(attachments)
+}
+
+/// A file specification attached to a PDF
+pub(crate) struct AttachedFileSpec {
+    /// The file name from the file specification, falling back to the name tree key
+    pub(crate) file_name: String,
+    /// The file specification dictionary
+    pub(crate) dict: Dictionary,
+    /// Whether the file specification is listed in the document level `/AF` array
+    pub(crate) associated: bool,
+}
+
+impl AttachedFileSpec {
+    pub(crate) fn af_relationship(&self) -> Option<String> {
+        self.dict
+            .get(b"AFRelationship")
+            .and_then(Object::as_name_str)
+            .ok()
+            .map(ToString::to_string)
+    }
+}
+
+/// Collect the file specifications of the `EmbeddedFiles` name tree and the document level `/AF` array
+pub(crate) fn attached_file_specs(
+    document: &Document,
+) -> Result<Vec<AttachedFileSpec>, lopdf::Error> {
+    let associated = associated_file_specs(document)?;
+    let mut seen: Vec<ObjectId> = Vec::new();
+    let mut file_specs = Vec::new();
+
+    let entries = embedded_file_entries(document)?
+        .into_iter()
+        .map(|(name, file_spec)| (Some(name), file_spec))
+        .chain(
+            associated
+                .iter()
+                .cloned()
+                .map(|file_spec| (None, file_spec)),
+        );
+    for (key, file_spec) in entries {
+        if let Object::Reference(id) = file_spec {
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id);
+        }
+        let Ok((id, Object::Dictionary(dict))) = document.dereference(&file_spec) else {
+            continue;
+        };
+        let Some(file_name) = file_spec_name(dict).or_else(|| key.map(|key| decode_text(&key)))
+        else {
+            continue;
+        };
+        file_specs.push(AttachedFileSpec {
+            file_name,
+            dict: dict.clone(),
+            associated: associated.iter().any(|other| match (id, other) {
+                (Some(id), Object::Reference(other)) => id == *other,
+                _ => other == &file_spec,
+            }),
+        });
+    }
+    Ok(file_specs)
+}
+
+/// The entries of the `EmbeddedFiles` name tree as file name / file specification pairs
+pub(crate) fn embedded_file_entries(
+    document: &Document,
+) -> Result<Vec<(Vec<u8>, Object)>, lopdf::Error> {
+    let catalog = document.catalog()?;
+    let names = match catalog.get_deref(b"Names", document) {
+        Ok(Object::Dictionary(names)) => names,
+        _ => return Ok(vec![]),
+    };
+    match names.get_deref(b"EmbeddedFiles", document) {
+        Ok(Object::Dictionary(embedded_files)) => {
+            let mut entries = Vec::new();
+            collect_name_tree(document, embedded_files, &mut entries, 0)?;
+            Ok(entries)
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Flatten a PDF name tree into its key / value pairs
+pub(crate) fn collect_name_tree(
+    document: &Document,
+    node: &Dictionary,
+    entries: &mut Vec<(Vec<u8>, Object)>,
+    depth: usize,
+) -> Result<(), lopdf::Error> {
+    if depth > 32 {
+        return Err(lopdf::Error::ReferenceLimit);
+    }
+    if let Ok(names) = node
+        .get_deref(b"Names", document)
+        .and_then(Object::as_array)
+    {
+        for pair in names.chunks(2) {
+            if let [Object::String(name, _), value] = pair {
+                entries.push((name.clone(), value.clone()));
+            }
+        }
+    }
+    if let Ok(kids) = node.get_deref(b"Kids", document).and_then(Object::as_array) {
+        for kid in kids {
+            if let Ok((_, Object::Dictionary(kid))) = document.dereference(kid) {
+                collect_name_tree(document, kid, entries, depth + 1)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn associated_file_specs(document: &Document) -> Result<Vec<Object>, lopdf::Error> {
+    Ok(document
+        .catalog()?
+        .get_deref(b"AF", document)
+        .and_then(Object::as_array)
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// The file name of a file specification, preferring the unicode `/UF` entry
+pub(crate) fn file_spec_name(file_spec: &Dictionary) -> Option<String> {
+    file_spec
+        .get(b"UF")
+        .or_else(|_| file_spec.get(b"F"))
+        .and_then(Object::as_str)
+        .ok()
+        .map(decode_text)
+}
+
+/// The embedded file stream of a file specification
+pub(crate) fn embedded_file_stream<'a>(
+    document: &'a Document,
+    file_spec: &'a Dictionary,
+) -> Option<&'a Stream> {
+    let ef = file_spec
+        .get_deref(b"EF", document)
+        .and_then(Object::as_dict)
+        .ok()?;
+    match ef
+        .get_deref(b"UF", document)
+        .or_else(|_| ef.get_deref(b"F", document))
+    {
+        Ok(Object::Stream(stream)) => Some(stream),
+        _ => None,
+    }
+}
+
+/// The decompressed content of the embedded file stream of a file specification
+pub(crate) fn file_spec_content(
+    document: &Document,
+    file_spec: &Dictionary,
+) -> Result<Option<Vec<u8>>, lopdf::Error> {
+    embedded_file_stream(document, file_spec)
+        .map(stream_content)
+        .transpose()
+}
+
+/// The decompressed content of a stream
+pub(crate) fn stream_content(stream: &Stream) -> Result<Vec<u8>, lopdf::Error> {
+    // Streams without a filter are stored as is
+    if stream
+        .filters()
+        .map(|filters| filters.is_empty())
+        .unwrap_or(true)
+    {
+        Ok(stream.content.clone())
+    } else {
+        stream.decompressed_content()
+    }
+}
+
+/// Decode a PDF text string, which is either UTF-16BE with a byte order mark or PDFDocEncoding
+fn decode_text(bytes: &[u8]) -> String {
+    match bytes {
+        [0xfe, 0xff, rest @ ..] => String::from_utf16_lossy(
+            &rest
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
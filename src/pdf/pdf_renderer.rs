@@ -0,0 +1,641 @@
+use crate::cii::cii_model::{
+    DateTimeString, PostalTradeAddress, RamIncludedSupplyChainTradeLineItem, TradeParty,
+};
+use crate::convert::zugferd1_to_cii::with_facturx_guideline_id;
+use crate::pdf::pdf_font::{srgb_output_intents, EmbeddedFont, DEJAVU_SANS, DEJAVU_SANS_BOLD};
+use crate::pdf::pdf_writer::embed_xml;
+use crate::preserve::unknown_elements::to_string_with_unknown_elements;
+use crate::{cii_specification, CrossIndustryInvoice, InvoiceError, ValidationConfig};
+use lopdf::content::{Content, Operation};
+use lopdf::{dictionary, Document, Object, ObjectId, Stream};
+
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const MARGIN: f32 = 50.0;
+const FONT_SIZE: f32 = 9.0;
+const LINE_HEIGHT: f32 = 12.0;
+
+/// Render a CII invoice as a ZUGFeRD/Factur-X PDF/A-3
+/// # Arguments
+/// * `invoice` - The invoice to render and embed
+/// # Returns
+/// * `Vec<u8>` - The content of the hybrid PDF
+/// # Errors
+/// * `InvoiceError` - If the invoice has no known profile or the PDF cannot be written
+///
+/// The visual pages show the parties, the invoice lines, the VAT breakdown, the totals and the
+/// payment instructions. Text is set in subsets of DejaVu Sans embedded into the PDF, characters
+/// the font has no glyph for are replaced by `?`. Colours are declared by an sRGB output intent.
+pub fn render_zugferd_pdf(invoice: &CrossIndustryInvoice) -> Result<Vec<u8>, InvoiceError> {
+    render_zugferd_pdf_with_config(invoice, &ValidationConfig::default())
+}
+
+/// Render a CII invoice as a ZUGFeRD/Factur-X PDF/A-3 with the specifications of a configuration
+/// # Arguments
+/// * `invoice` - The invoice to render and embed
+/// * `config` - The specifications known in addition to the built-in ones, declaring the conformance level
+/// # Returns
+/// * `Vec<u8>` - The content of the hybrid PDF
+/// # Errors
+/// * `InvoiceError` - If the invoice has no known profile or the PDF cannot be written
+pub fn render_zugferd_pdf_with_config(
+    invoice: &CrossIndustryInvoice,
+    config: &ValidationConfig,
+) -> Result<Vec<u8>, InvoiceError> {
+    let invoice = &with_facturx_guideline_id(invoice.clone());
+    let specification =
+        cii_specification(invoice, config).map_err(InvoiceError::ValidationError)?;
+    let xml = to_string_with_unknown_elements(invoice, &invoice.unknown_elements)
+        .map_err(InvoiceError::ParseError)?;
+
+    let mut document =
+        render_document(invoice).map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+    embed_xml(&mut document, xml.as_bytes(), &specification)
+        .map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+
+    let mut output = Vec::new();
+    document
+        .save_to(&mut output)
+        .map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+    Ok(output)
+}
+
+fn render_document(invoice: &CrossIndustryInvoice) -> Result<Document, lopdf::Error> {
+    let mut page = PageWriter::new();
+    let document = invoice.rsm_exchanged_document.as_ref();
+    let transaction = invoice.rsm_supply_chain_trade_transaction.as_ref();
+    let agreement = transaction.and_then(|t| t.ram_applicable_header_trade_agreement.as_ref());
+    let settlement = transaction.and_then(|t| t.ram_applicable_header_trade_settlement.as_ref());
+    let currency = settlement
+        .and_then(|s| s.ram_invoice_currency_code.clone())
+        .unwrap_or_default();
+
+    let title = document_title(document.and_then(|d| d.ram_type_code.as_deref()));
+    page.text(MARGIN, 18.0, true, title);
+    page.advance(28.0);
+
+    let mut header = vec![(
+        "Invoice number",
+        document
+            .and_then(|d| d.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+    )];
+    header.push((
+        "Issue date",
+        document
+            .and_then(|d| d.ram_issue_date_time.as_ref())
+            .and_then(|d| d.udt_date_time_string.as_ref())
+            .map(format_date),
+    ));
+    header.push((
+        "Due date",
+        settlement
+            .and_then(|s| s.ram_specified_trade_payment_terms.as_ref())
+            .and_then(|t| t.ram_due_date_date_time.as_ref())
+            .and_then(|d| d.udt_date_time_string.as_ref())
+            .map(format_date),
+    ));
+    header.push((
+        "Buyer reference",
+        agreement.and_then(|a| a.ram_buyer_reference.clone()),
+    ));
+    header.push((
+        "Order reference",
+        agreement
+            .and_then(|a| a.ram_buyer_order_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    ));
+    header.push(("Currency", Some(currency.clone()).filter(|c| !c.is_empty())));
+    for (label, value) in header {
+        if let Some(value) = value {
+            page.label_value(label, &value);
+        }
+    }
+    page.advance(LINE_HEIGHT);
+
+    let seller = party_lines(agreement.and_then(|a| a.ram_seller_trade_party.as_ref()));
+    let buyer = party_lines(agreement.and_then(|a| a.ram_buyer_trade_party.as_ref()));
+    page.text(MARGIN, FONT_SIZE, true, "Seller");
+    page.text(PAGE_WIDTH / 2.0, FONT_SIZE, true, "Buyer");
+    page.advance(LINE_HEIGHT);
+    for index in 0..seller.len().max(buyer.len()) {
+        if let Some(line) = seller.get(index) {
+            page.text(MARGIN, FONT_SIZE, false, line);
+        }
+        if let Some(line) = buyer.get(index) {
+            page.text(PAGE_WIDTH / 2.0, FONT_SIZE, false, line);
+        }
+        page.advance(LINE_HEIGHT);
+    }
+    page.advance(LINE_HEIGHT);
+
+    render_lines(
+        &mut page,
+        transaction
+            .map(|t| t.ram_included_supply_chain_trade_line_item.as_slice())
+            .unwrap_or_default(),
+    );
+
+    if let Some(settlement) = settlement {
+        page.ensure_space(LINE_HEIGHT * (settlement.ram_applicable_trade_tax.len() + 3) as f32);
+        page.text(MARGIN, FONT_SIZE, true, "VAT breakdown");
+        page.advance(LINE_HEIGHT);
+        let columns = [MARGIN, 160.0, 300.0, 430.0];
+        for (x, label) in columns
+            .iter()
+            .zip(["Category", "Rate", "Taxable amount", "VAT amount"])
+        {
+            page.text(*x, FONT_SIZE, true, label);
+        }
+        page.advance(LINE_HEIGHT);
+        for tax in &settlement.ram_applicable_trade_tax {
+            page.text(
+                columns[0],
+                FONT_SIZE,
+                false,
+                tax.ram_category_code.as_deref().unwrap_or_default(),
+            );
+            page.text(
+                columns[1],
+                FONT_SIZE,
+                false,
+                &tax.ram_rate_applicable_percent
+                    .as_ref()
+                    .map(|rate| format!("{} %", rate))
+                    .unwrap_or_default(),
+            );
+            page.text(
+                columns[2],
+                FONT_SIZE,
+                false,
+                tax.ram_basis_amount.as_deref().unwrap_or_default(),
+            );
+            page.text(
+                columns[3],
+                FONT_SIZE,
+                false,
+                tax.ram_calculated_amount.as_deref().unwrap_or_default(),
+            );
+            page.advance(LINE_HEIGHT);
+            if let Some(reason) = &tax.ram_exemption_reason {
+                page.wrapped_text(columns[0], FONT_SIZE, reason, PAGE_WIDTH - 2.0 * MARGIN);
+            }
+        }
+        page.advance(LINE_HEIGHT);
+
+        if let Some(summation) =
+            &settlement.ram_specified_trade_settlement_header_monetary_summation
+        {
+            let tax_total = summation
+                .ram_tax_total_amount
+                .iter()
+                .find(|amount| amount.currency_id.as_deref() == Some(currency.as_str()))
+                .or(summation.ram_tax_total_amount.first())
+                .and_then(|amount| amount.text.clone());
+            let totals = [
+                (
+                    "Sum of invoice lines",
+                    summation.ram_line_total_amount.clone(),
+                ),
+                ("Allowances", summation.ram_allowance_total_amount.clone()),
+                ("Charges", summation.ram_charge_total_amount.clone()),
+                (
+                    "Total without VAT",
+                    summation.ram_tax_basis_total_amount.clone(),
+                ),
+                ("Total VAT", tax_total),
+                ("Total with VAT", summation.ram_grand_total_amount.clone()),
+                ("Paid amount", summation.ram_total_prepaid_amount.clone()),
+                ("Rounding amount", summation.ram_rounding_amount.clone()),
+                ("Amount due", summation.ram_due_payable_amount.clone()),
+            ];
+            page.ensure_space(LINE_HEIGHT * totals.len() as f32);
+            for (label, amount) in totals {
+                if let Some(amount) = amount {
+                    let bold = label == "Amount due";
+                    page.text(330.0, FONT_SIZE, bold, label);
+                    page.text_right(
+                        PAGE_WIDTH - MARGIN,
+                        FONT_SIZE,
+                        bold,
+                        &format!("{} {}", amount, currency),
+                    );
+                    page.advance(LINE_HEIGHT);
+                }
+            }
+            page.advance(LINE_HEIGHT);
+        }
+
+        let mut payment = Vec::new();
+        if let Some(terms) = settlement
+            .ram_specified_trade_payment_terms
+            .as_ref()
+            .and_then(|t| t.ram_description.clone())
+        {
+            payment.push(("Payment terms", terms));
+        }
+        if let Some(reference) = &settlement.ram_payment_reference {
+            payment.push(("Payment reference", reference.clone()));
+        }
+        for means in &settlement.ram_specified_trade_settlement_payment_means {
+            if let Some(account) = &means.ram_payee_party_creditor_financial_account {
+                if let Some(name) = &account.ram_account_name {
+                    payment.push(("Account holder", name.clone()));
+                }
+                if let Some(iban) = &account.ram_ibanid {
+                    payment.push(("IBAN", iban.clone()));
+                } else if let Some(id) = &account.ram_proprietary_id {
+                    payment.push(("Account ID", id.clone()));
+                }
+            }
+            if let Some(bic) = means
+                .ram_payee_specified_creditor_financial_institution
+                .as_ref()
+                .and_then(|institution| institution.ram_bicid.clone())
+            {
+                payment.push(("BIC", bic));
+            }
+        }
+        if let Some(mandate) = settlement
+            .ram_specified_trade_payment_terms
+            .as_ref()
+            .and_then(|t| t.ram_direct_debit_mandate_id.clone())
+        {
+            payment.push(("Mandate reference", mandate));
+        }
+        if !payment.is_empty() {
+            page.ensure_space(LINE_HEIGHT * (payment.len() + 1) as f32);
+            page.text(MARGIN, FONT_SIZE, true, "Payment instructions");
+            page.advance(LINE_HEIGHT);
+            for (label, value) in payment {
+                page.label_value(label, &value);
+            }
+        }
+    }
+
+    if let Some(document) = document {
+        if !document.ram_included_note.is_empty() {
+            page.advance(LINE_HEIGHT);
+            page.ensure_space(LINE_HEIGHT * 2.0);
+            page.text(MARGIN, FONT_SIZE, true, "Notes");
+            page.advance(LINE_HEIGHT);
+            for note in &document.ram_included_note {
+                if let Some(content) = &note.ram_content {
+                    page.wrapped_text(MARGIN, FONT_SIZE, content, PAGE_WIDTH - 2.0 * MARGIN);
+                }
+            }
+        }
+    }
+
+    page.finish()
+}
+
+fn render_lines(page: &mut PageWriter, lines: &[RamIncludedSupplyChainTradeLineItem]) {
+    let columns = [MARGIN, 80.0, 330.0, 385.0, 445.0, PAGE_WIDTH - MARGIN];
+    let header = |page: &mut PageWriter| {
+        page.text(columns[0], FONT_SIZE, true, "Pos.");
+        page.text(columns[1], FONT_SIZE, true, "Description");
+        page.text_right(columns[2] + 40.0, FONT_SIZE, true, "Quantity");
+        page.text(columns[3], FONT_SIZE, true, "Unit");
+        page.text_right(columns[4] + 40.0, FONT_SIZE, true, "Net price");
+        page.text_right(columns[5], FONT_SIZE, true, "Amount");
+        page.advance(LINE_HEIGHT);
+        page.rule();
+    };
+    page.ensure_space(LINE_HEIGHT * 3.0);
+    header(page);
+
+    for line in lines {
+        let product = line.ram_specified_trade_product.as_ref();
+        let name = product.and_then(|p| p.ram_name.clone()).unwrap_or_default();
+        let description_lines = page.wrap(&name, columns[2] - columns[1] - 50.0, FONT_SIZE);
+        if page.ensure_space(LINE_HEIGHT * description_lines.len().max(1) as f32) {
+            header(page);
+        }
+
+        let line_id = line
+            .ram_associated_document_line_document
+            .as_ref()
+            .and_then(|d| d.ram_line_id.clone())
+            .unwrap_or_default();
+        let quantity = line
+            .ram_specified_line_trade_delivery
+            .as_ref()
+            .and_then(|d| d.ram_billed_quantity.as_ref());
+        let net_price = line
+            .ram_specified_line_trade_agreement
+            .as_ref()
+            .and_then(|a| a.ram_net_price_product_trade_price.as_ref())
+            .and_then(|p| p.ram_charge_amount.clone())
+            .unwrap_or_default();
+        let settlement = line.ram_specified_line_trade_settlement.as_ref();
+        let amount = settlement
+            .and_then(|s| {
+                s.ram_specified_trade_settlement_line_monetary_summation
+                    .as_ref()
+            })
+            .and_then(|s| s.ram_line_total_amount.clone())
+            .unwrap_or_default();
+
+        page.text(
+            columns[0],
+            FONT_SIZE,
+            false,
+            &page.truncate(&line_id, columns[1] - columns[0] - 4.0, FONT_SIZE),
+        );
+        page.text_right(
+            columns[2] + 40.0,
+            FONT_SIZE,
+            false,
+            quantity.and_then(|q| q.text.as_deref()).unwrap_or_default(),
+        );
+        page.text(
+            columns[3],
+            FONT_SIZE,
+            false,
+            quantity
+                .and_then(|q| q.unit_code.as_deref())
+                .unwrap_or_default(),
+        );
+        page.text_right(columns[4] + 40.0, FONT_SIZE, false, &net_price);
+        page.text_right(columns[5], FONT_SIZE, false, &amount);
+        for description in description_lines {
+            page.text(columns[1], FONT_SIZE, false, &description);
+            page.advance(LINE_HEIGHT);
+        }
+    }
+    page.rule();
+    page.advance(LINE_HEIGHT);
+}
+
+fn document_title(type_code: Option<&str>) -> &'static str {
+    match type_code {
+        Some("381") => "Credit note",
+        Some("384") => "Corrected invoice",
+        Some("389") => "Self-billed invoice",
+        Some("326") => "Partial invoice",
+        Some("386") => "Prepayment invoice",
+        _ => "Invoice",
+    }
+}
+
+fn party_lines(party: Option<&TradeParty>) -> Vec<String> {
+    let Some(party) = party else {
+        return vec![];
+    };
+    let mut lines = Vec::new();
+    lines.extend(party.ram_name.clone());
+    lines.extend(address_lines(party.ram_postal_trade_address.as_ref()));
+    if let Some(contact) = &party.ram_defined_trade_contact {
+        lines.extend(contact.ram_person_name.clone());
+        lines.extend(
+            contact
+                .ram_telephone_universal_communication
+                .as_ref()
+                .and_then(|t| t.ram_complete_number.clone()),
+        );
+        lines.extend(
+            contact
+                .ram_email_uriuniversal_communication
+                .as_ref()
+                .and_then(|e| e.ram_uriid.clone()),
+        );
+    }
+    for registration in &party.ram_specified_tax_registration {
+        if let Some(id) = registration.ram_id.as_ref() {
+            let label = match id.scheme_id.as_deref() {
+                Some("VA") => "VAT ID",
+                _ => "Tax number",
+            };
+            lines.extend(id.text.as_ref().map(|id| format!("{}: {}", label, id)));
+        }
+    }
+    lines
+}
+
+fn address_lines(address: Option<&PostalTradeAddress>) -> Vec<String> {
+    let Some(address) = address else {
+        return vec![];
+    };
+    let mut lines = Vec::new();
+    lines.extend(address.ram_line_one.clone());
+    lines.extend(address.ram_line_two.clone());
+    lines.extend(address.ram_line_three.clone());
+    let city = [
+        address.ram_postcode_code.clone(),
+        address.ram_city_name.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    if !city.is_empty() {
+        lines.push(city);
+    }
+    lines.extend(address.ram_country_sub_division_name.clone());
+    lines.extend(address.ram_country_id.clone());
+    lines
+}
+
+fn format_date(date: &DateTimeString) -> String {
+    let text = date.text.trim();
+    if date.format == "102" && text.len() == 8 && text.bytes().all(|b| b.is_ascii_digit()) {
+        format!("{}-{}-{}", &text[0..4], &text[4..6], &text[6..8])
+    } else {
+        text.to_string()
+    }
+}
+
+struct PageWriter {
+    pages: Vec<Vec<Operation>>,
+    y: f32,
+    regular: EmbeddedFont,
+    bold: EmbeddedFont,
+}
+
+impl PageWriter {
+    fn new() -> Self {
+        Self {
+            pages: vec![vec![]],
+            y: PAGE_HEIGHT - MARGIN,
+            regular: EmbeddedFont::new("DejaVuSans", DEJAVU_SANS),
+            bold: EmbeddedFont::new("DejaVuSans-Bold", DEJAVU_SANS_BOLD),
+        }
+    }
+
+    fn font(&self, bold: bool) -> &EmbeddedFont {
+        if bold {
+            &self.bold
+        } else {
+            &self.regular
+        }
+    }
+
+    fn truncate(&self, text: &str, width: f32, size: f32) -> String {
+        let mut truncated = text.to_string();
+        while self.regular.width(&truncated, size) > width && truncated.pop().is_some() {}
+        truncated
+    }
+
+    fn wrap(&self, text: &str, width: f32, size: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut current = String::new();
+        for word in text.split_whitespace() {
+            let candidate = if current.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", current, word)
+            };
+            if self.regular.width(&candidate, size) > width && !current.is_empty() {
+                lines.push(std::mem::replace(&mut current, word.to_string()));
+            } else {
+                current = candidate;
+            }
+        }
+        if !current.is_empty() || lines.is_empty() {
+            lines.push(current);
+        }
+        lines
+    }
+
+    fn operations(&mut self) -> &mut Vec<Operation> {
+        self.pages.last_mut().expect("at least one page")
+    }
+
+    fn advance(&mut self, height: f32) {
+        self.y -= height;
+        self.ensure_space(0.0);
+    }
+
+    /// Start a new page if less than `height` is left, returns whether a page break happened
+    fn ensure_space(&mut self, height: f32) -> bool {
+        if self.y - height < MARGIN {
+            self.pages.push(vec![]);
+            self.y = PAGE_HEIGHT - MARGIN;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn text(&mut self, x: f32, size: f32, bold: bool, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let y = self.y;
+        let (font, encoded) = if bold {
+            ("F2", self.bold.encode(text))
+        } else {
+            ("F1", self.regular.encode(text))
+        };
+        self.operations().extend([
+            Operation::new("BT", vec![]),
+            Operation::new("Tf", vec![font.into(), size.into()]),
+            Operation::new("Td", vec![x.into(), y.into()]),
+            Operation::new(
+                "Tj",
+                vec![Object::String(encoded, lopdf::StringFormat::Hexadecimal)],
+            ),
+            Operation::new("ET", vec![]),
+        ]);
+    }
+
+    fn text_right(&mut self, right: f32, size: f32, bold: bool, text: &str) {
+        self.text(right - self.font(bold).width(text, size), size, bold, text);
+    }
+
+    fn wrapped_text(&mut self, x: f32, size: f32, text: &str, width: f32) {
+        for paragraph in text.lines() {
+            for line in self.wrap(paragraph, width, size) {
+                self.text(x, size, false, &line);
+                self.advance(LINE_HEIGHT);
+            }
+        }
+    }
+
+    fn label_value(&mut self, label: &str, value: &str) {
+        self.text(MARGIN, FONT_SIZE, true, label);
+        self.text(MARGIN + 110.0, FONT_SIZE, false, value);
+        self.advance(LINE_HEIGHT);
+    }
+
+    fn rule(&mut self) {
+        let y = self.y + LINE_HEIGHT - 3.0;
+        self.operations().extend([
+            Operation::new("w", vec![0.5.into()]),
+            Operation::new("m", vec![MARGIN.into(), y.into()]),
+            Operation::new("l", vec![(PAGE_WIDTH - MARGIN).into(), y.into()]),
+            Operation::new("S", vec![]),
+        ]);
+    }
+
+    fn finish(mut self) -> Result<Document, lopdf::Error> {
+        let mut pages = std::mem::take(&mut self.pages);
+        let page_count = pages.len();
+        for (index, operations) in pages.iter_mut().enumerate() {
+            let footer = format!("Page {} of {}", index + 1, page_count);
+            operations.extend([
+                Operation::new("BT", vec![]),
+                Operation::new("Tf", vec!["F1".into(), 8.into()]),
+                Operation::new(
+                    "Td",
+                    vec![
+                        (PAGE_WIDTH - MARGIN - self.regular.width(&footer, 8.0)).into(),
+                        (MARGIN / 2.0).into(),
+                    ],
+                ),
+                Operation::new(
+                    "Tj",
+                    vec![Object::String(
+                        self.regular.encode(&footer),
+                        lopdf::StringFormat::Hexadecimal,
+                    )],
+                ),
+                Operation::new("ET", vec![]),
+            ]);
+        }
+
+        // The fonts are subset to the glyphs used once every page is written
+        let mut document = Document::with_version("1.7");
+        let pages_id = document.new_object_id();
+        let font_id = self.regular.write(&mut document)?;
+        let bold_font_id = self.bold.write(&mut document)?;
+        let resources_id = document.add_object(dictionary! {
+            "Font" => dictionary! {
+                "F1" => font_id,
+                "F2" => bold_font_id,
+            },
+        });
+
+        let mut kids: Vec<Object> = Vec::with_capacity(page_count);
+        for operations in pages {
+            let content = Content { operations };
+            let content_id = document.add_object(Stream::new(dictionary! {}, content.encode()?));
+            let page_id: ObjectId = document.add_object(dictionary! {
+                "Type" => "Page",
+                "Parent" => pages_id,
+                "Contents" => content_id,
+            });
+            kids.push(page_id.into());
+        }
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => page_count as i64,
+                "Kids" => kids,
+                "Resources" => resources_id,
+                "MediaBox" => vec![0.into(), 0.into(), PAGE_WIDTH.into(), PAGE_HEIGHT.into()],
+            }),
+        );
+        let output_intents = srgb_output_intents(&mut document)?;
+        let catalog_id = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "Pages" => pages_id,
+            "OutputIntents" => output_intents,
+        });
+        document.trailer.set("Root", catalog_id);
+        document.compress();
+        Ok(document)
+    }
+}
@@ -0,0 +1,210 @@
+use crate::pdf::pdf_metadata::{xmp_packet, xmp_properties, PDFA_ID_NAMESPACE};
+use crate::pdf::pdf_reader::{attached_file_specs, embedded_file_stream};
+use crate::{BusinessRuleViolation, ValidationError};
+use lopdf::{Document, Object};
+
+/// AFRelationship values PDF/A-3 allows for associated files
+const AF_RELATIONSHIPS: [&str; 8] = [
+    "Source",
+    "Data",
+    "Alternative",
+    "Supplement",
+    "EncryptedPayload",
+    "FormData",
+    "Schema",
+    "Unspecified",
+];
+
+/// AFRelationship values Factur-X allows for the invoice
+const INVOICE_AF_RELATIONSHIPS: [&str; 3] = ["Data", "Source", "Alternative"];
+
+/// Validate the PDF/A-3 container of a hybrid invoice
+/// # Arguments
+/// * `document` - The PDF document
+/// * `invoice_file_name` - The name of the attachment carrying the invoice
+/// # Returns
+/// * `Vec<ValidationError>` - A `ValidationError::PdfViolation` for every requirement the container does not meet
+///
+/// This checks the PDF/A identification schema of the XMP metadata, the association and relationship of the
+/// embedded files, the MIME type and modification date of their streams and forbidden features like encryption
+/// and JavaScript. It does not replace a full PDF/A validator, fonts, colour spaces and transparency are not checked.
+pub fn validate_pdf_container(
+    document: &Document,
+    invoice_file_name: &str,
+) -> Vec<ValidationError> {
+    let mut violations = Vec::new();
+    let mut violation = |rule_id: &str, text: String| {
+        violations.push(ValidationError::PdfViolation(BusinessRuleViolation::new(
+            rule_id,
+            &format!("[{}]-{}", rule_id, text),
+        )));
+    };
+
+    let xmp = xmp_packet(document);
+    let properties = match &xmp {
+        Ok(xmp) => xmp.as_deref().map(xmp_properties).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+    let pdfa_property = |name: &str| {
+        properties
+            .iter()
+            .find(|(namespace, local_name, _)| namespace == PDFA_ID_NAMESPACE && local_name == name)
+            .map(|(_, _, value)| value.as_str())
+    };
+    match (pdfa_property("part"), &xmp) {
+        (None, Err(e)) => violation(
+            "PDF-01",
+            format!("The XMP metadata cannot be decoded: {}", e),
+        ),
+        (None, Ok(_)) => violation(
+            "PDF-01",
+            "The XMP metadata does not contain a PDF/A identification schema (pdfaid:part)"
+                .to_string(),
+        ),
+        (Some("3"), _) => {}
+        (Some(part), _) => violation(
+            "PDF-02",
+            format!(
+                "Hybrid invoices must be PDF/A-3, but pdfaid:part is '{}'",
+                part
+            ),
+        ),
+    }
+    if let Some(conformance) = pdfa_property("conformance") {
+        if !matches!(conformance, "A" | "B" | "U") {
+            violation(
+                "PDF-02",
+                format!(
+                    "The pdfaid:conformance '{}' is not one of A, B or U",
+                    conformance
+                ),
+            );
+        }
+    }
+
+    match attached_file_specs(document) {
+        Ok(file_specs) => {
+            if !file_specs
+                .iter()
+                .any(|file_spec| file_spec.file_name == invoice_file_name)
+            {
+                violation(
+                    "PDF-03",
+                    format!(
+                        "The invoice '{}' is not an embedded file",
+                        invoice_file_name
+                    ),
+                );
+            }
+            for file_spec in &file_specs {
+                let is_invoice = file_spec.file_name == invoice_file_name;
+                let name = &file_spec.file_name;
+                if is_invoice && !file_spec.associated {
+                    violation(
+                        "PDF-03",
+                        format!(
+                            "The invoice '{}' is not associated with the document in the catalog /AF array",
+                            name
+                        ),
+                    );
+                }
+                match file_spec.af_relationship() {
+                    None => violation(
+                        "PDF-04",
+                        format!("The embedded file '{}' has no /AFRelationship", name),
+                    ),
+                    Some(relationship)
+                        if is_invoice
+                            && !INVOICE_AF_RELATIONSHIPS.contains(&relationship.as_str()) =>
+                    {
+                        violation(
+                            "PDF-04",
+                            format!(
+                                "The /AFRelationship of the invoice '{}' is '{}', but must be one of Data, Source or Alternative",
+                                name, relationship
+                            ),
+                        )
+                    }
+                    Some(relationship) if !AF_RELATIONSHIPS.contains(&relationship.as_str()) => {
+                        violation(
+                            "PDF-04",
+                            format!(
+                                "The /AFRelationship '{}' of the embedded file '{}' is not defined by PDF/A-3",
+                                relationship, name
+                            ),
+                        )
+                    }
+                    Some(_) => {}
+                }
+                let Some(stream_dict) =
+                    embedded_file_stream(document, &file_spec.dict).map(|stream| &stream.dict)
+                else {
+                    violation(
+                        "PDF-05",
+                        format!("The embedded file '{}' has no embedded file stream", name),
+                    );
+                    continue;
+                };
+                match stream_dict.get(b"Subtype").and_then(Object::as_name_str) {
+                    Err(_) => violation(
+                        "PDF-05",
+                        format!(
+                            "The embedded file '{}' does not declare a MIME type in /Subtype",
+                            name
+                        ),
+                    ),
+                    Ok(mime_type) if is_invoice && mime_type != "text/xml" => violation(
+                        "PDF-05",
+                        format!(
+                            "The MIME type of the invoice '{}' is '{}', but must be text/xml",
+                            name, mime_type
+                        ),
+                    ),
+                    Ok(_) => {}
+                }
+                let has_mod_date = stream_dict
+                    .get_deref(b"Params", document)
+                    .and_then(Object::as_dict)
+                    .map(|params| params.has(b"ModDate"))
+                    .unwrap_or(false);
+                if !has_mod_date {
+                    violation(
+                        "PDF-06",
+                        format!("The embedded file '{}' has no /Params /ModDate", name),
+                    );
+                }
+            }
+        }
+        Err(e) => violation(
+            "PDF-03",
+            format!("The embedded files cannot be read: {}", e),
+        ),
+    }
+
+    if document.trailer.has(b"Encrypt") {
+        violation("PDF-07", "PDF/A does not allow encryption".to_string());
+    }
+    if contains_javascript(document) {
+        violation("PDF-08", "PDF/A does not allow JavaScript".to_string());
+    }
+    violations
+}
+
+fn contains_javascript(document: &Document) -> bool {
+    let has_javascript_names = document
+        .catalog()
+        .and_then(|catalog| catalog.get_deref(b"Names", document))
+        .and_then(Object::as_dict)
+        .map(|names| names.has(b"JavaScript"))
+        .unwrap_or(false);
+    has_javascript_names
+        || document.objects.values().any(|object| {
+            let dict = match object {
+                Object::Dictionary(dict) => dict,
+                Object::Stream(stream) => &stream.dict,
+                _ => return false,
+            };
+            dict.has(b"JS")
+                || dict.get(b"S").and_then(Object::as_name_str).ok() == Some("JavaScript")
+        })
+}
@@ -0,0 +1,226 @@
+use crate::convert::zugferd1_to_cii::with_facturx_guideline_id;
+use crate::pdf::pdf_metadata::facturx_xmp_packet;
+use crate::pdf::pdf_reader::embedded_file_entries;
+use crate::preserve::unknown_elements::to_string_with_unknown_elements;
+use crate::{
+    cii_specification, CrossIndustryInvoice, FacturXMetadata, InvoiceError, Specification,
+    ValidationConfig,
+};
+use chrono::Utc;
+use lopdf::{dictionary, Dictionary, Document, Object, ObjectId, Stream};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+pub const FACTUR_X_FILE_NAME: &str = "factur-x.xml";
+
+/// Embed a CII invoice into an existing PDF, turning it into a ZUGFeRD/Factur-X PDF/A-3
+/// # Arguments
+/// * `pdf_bytes` - The content of the visual PDF
+/// * `invoice` - The invoice to embed as `factur-x.xml`
+/// # Returns
+/// * `Vec<u8>` - The content of the hybrid PDF
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or written, or the invoice has no known profile
+///
+/// An existing `factur-x.xml` attachment is replaced. The Factur-X version and conformance level of the
+/// invoice's specification are merged into the existing XMP metadata, keeping its other properties.
+/// The PDF/A-3 identification is declared, but the visual content (fonts, colour spaces) is not converted.
+pub fn embed_invoice_into_pdf(
+    pdf_bytes: &[u8],
+    invoice: &CrossIndustryInvoice,
+) -> Result<Vec<u8>, InvoiceError> {
+    embed_invoice_into_pdf_with_config(pdf_bytes, invoice, &ValidationConfig::default())
+}
+
+/// Embed a CII invoice into an existing PDF with the specifications of a configuration
+/// # Arguments
+/// * `pdf_bytes` - The content of the visual PDF
+/// * `invoice` - The invoice to embed as `factur-x.xml`
+/// * `config` - The specifications known in addition to the built-in ones, declaring the conformance level
+/// # Returns
+/// * `Vec<u8>` - The content of the hybrid PDF
+/// # Errors
+/// * `InvoiceError` - If the PDF cannot be read or written, or the invoice has no known profile
+pub fn embed_invoice_into_pdf_with_config(
+    pdf_bytes: &[u8],
+    invoice: &CrossIndustryInvoice,
+    config: &ValidationConfig,
+) -> Result<Vec<u8>, InvoiceError> {
+    let invoice = &with_facturx_guideline_id(invoice.clone());
+    let specification =
+        cii_specification(invoice, config).map_err(InvoiceError::ValidationError)?;
+    let xml = to_string_with_unknown_elements(invoice, &invoice.unknown_elements)
+        .map_err(InvoiceError::ParseError)?;
+    let mut document =
+        Document::load_mem(pdf_bytes).map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+
+    embed_xml(&mut document, xml.as_bytes(), &specification)
+        .map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+
+    let mut output = Vec::new();
+    document
+        .save_to(&mut output)
+        .map_err(|e| InvoiceError::PdfError(e.to_string()))?;
+    Ok(output)
+}
+
+pub(crate) fn embed_xml(
+    document: &mut Document,
+    xml: &[u8],
+    specification: &Specification,
+) -> Result<(), lopdf::Error> {
+    if document.version.as_str() < "1.7" {
+        document.version = "1.7".to_string();
+    }
+    let now = Utc::now();
+    let pdf_date = format!("D:{}+00'00'", now.format("%Y%m%d%H%M%S"));
+
+    let mut embedded_file = Stream::new(
+        dictionary! {
+            "Type" => "EmbeddedFile",
+            "Subtype" => "text/xml",
+            "Params" => dictionary! {
+                "Size" => xml.len() as i64,
+                "ModDate" => Object::string_literal(pdf_date.as_str()),
+            },
+        },
+        xml.to_vec(),
+    );
+    embedded_file.compress()?;
+    let embedded_file_id = document.add_object(embedded_file);
+
+    let file_spec_id = document.add_object(dictionary! {
+        "Type" => "Filespec",
+        "F" => Object::string_literal(FACTUR_X_FILE_NAME),
+        "UF" => Object::string_literal(FACTUR_X_FILE_NAME),
+        "Desc" => Object::string_literal("Factur-X Invoice"),
+        "AFRelationship" => "Alternative",
+        "EF" => dictionary! {
+            "F" => embedded_file_id,
+            "UF" => embedded_file_id,
+        },
+    });
+
+    let metadata = FacturXMetadata {
+        document_type: Some("INVOICE".to_string()),
+        document_file_name: Some(FACTUR_X_FILE_NAME.to_string()),
+        version: Some(specification.facturx_version.clone()),
+        conformance_level: Some(specification.conformance_level.clone()),
+    };
+    let xmp = facturx_xmp_packet(
+        document,
+        &metadata,
+        &now.format("%Y-%m-%dT%H:%M:%S+00:00").to_string(),
+    );
+    let metadata_id = document.add_object(
+        Stream::new(
+            dictionary! {
+                "Type" => "Metadata",
+                "Subtype" => "XML",
+            },
+            xmp.into_bytes(),
+        )
+        .with_compression(false),
+    );
+    if let Ok(Object::Reference(info_id)) = document.trailer.get(b"Info") {
+        let info_id = *info_id;
+        if let Ok(info) = document.get_dictionary_mut(info_id) {
+            info.set("ModDate", Object::string_literal(pdf_date.as_str()));
+        }
+    }
+
+    let entries = embedded_file_entries(document)?
+        .into_iter()
+        .filter(|(name, _)| name != FACTUR_X_FILE_NAME.as_bytes())
+        .chain(std::iter::once((
+            FACTUR_X_FILE_NAME.as_bytes().to_vec(),
+            Object::Reference(file_spec_id),
+        )))
+        .collect::<Vec<_>>();
+    let embedded_files_id = document.add_object(dictionary! {
+        "Names" => sorted_name_array(entries),
+    });
+
+    let root_id = document.trailer.get(b"Root")?.as_reference()?;
+    let names_id = match document.get_dictionary(root_id)?.get(b"Names") {
+        Ok(Object::Reference(names_id)) => Some(*names_id),
+        _ => None,
+    };
+    match names_id {
+        Some(names_id) => {
+            document
+                .get_dictionary_mut(names_id)?
+                .set("EmbeddedFiles", embedded_files_id);
+        }
+        None => {
+            let catalog = document.get_dictionary_mut(root_id)?;
+            let mut names = match catalog.get(b"Names") {
+                Ok(Object::Dictionary(names)) => names.clone(),
+                _ => Dictionary::new(),
+            };
+            names.set("EmbeddedFiles", embedded_files_id);
+            catalog.set("Names", names);
+        }
+    }
+
+    let mut associated_files = associated_files(document, root_id)?
+        .into_iter()
+        .filter(|file_spec| !is_factur_x_file_spec(document, file_spec))
+        .collect::<Vec<_>>();
+    associated_files.push(Object::Reference(file_spec_id));
+
+    let catalog = document.get_dictionary_mut(root_id)?;
+    catalog.set("AF", associated_files);
+    catalog.set("Metadata", metadata_id);
+
+    if !document.trailer.has(b"ID") {
+        let id = document_id(xml, &pdf_date);
+        document.trailer.set(
+            "ID",
+            vec![
+                Object::String(id.clone(), lopdf::StringFormat::Hexadecimal),
+                Object::String(id, lopdf::StringFormat::Hexadecimal),
+            ],
+        );
+    }
+    Ok(())
+}
+
+fn sorted_name_array(mut entries: Vec<(Vec<u8>, Object)>) -> Vec<Object> {
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+        .into_iter()
+        .flat_map(|(name, value)| [Object::String(name, lopdf::StringFormat::Literal), value])
+        .collect()
+}
+
+fn associated_files(document: &Document, root_id: ObjectId) -> Result<Vec<Object>, lopdf::Error> {
+    let catalog = document.get_dictionary(root_id)?;
+    Ok(catalog
+        .get_deref(b"AF", document)
+        .and_then(Object::as_array)
+        .cloned()
+        .unwrap_or_default())
+}
+
+fn is_factur_x_file_spec(document: &Document, file_spec: &Object) -> bool {
+    document
+        .dereference(file_spec)
+        .and_then(|(_, object)| object.as_dict())
+        .and_then(|dict| dict.get(b"UF").or_else(|_| dict.get(b"F")))
+        .and_then(Object::as_str)
+        .map(|name| name == FACTUR_X_FILE_NAME.as_bytes())
+        .unwrap_or(false)
+}
+
+fn document_id(xml: &[u8], pdf_date: &str) -> Vec<u8> {
+    let mut id = Vec::with_capacity(16);
+    for seed in [0u8, 1u8] {
+        let mut hasher = DefaultHasher::new();
+        seed.hash(&mut hasher);
+        xml.hash(&mut hasher);
+        pdf_date.hash(&mut hasher);
+        id.extend_from_slice(&hasher.finish().to_be_bytes());
+    }
+    id
+}
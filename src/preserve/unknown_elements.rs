@@ -0,0 +1,299 @@
+use crate::detect::format_detector::qualified_name;
+use crate::InvoiceStandard;
+use einvoice_deps_yaserde::__xml::attribute::Attribute;
+use einvoice_deps_yaserde::__xml::name::Name;
+use einvoice_deps_yaserde::__xml::namespace::NS_NO_PREFIX;
+use einvoice_deps_yaserde::__xml::reader::{EventReader, ParserConfig, XmlEvent};
+use einvoice_deps_yaserde::__xml::writer::{EmitterConfig, XmlEvent as WriterEvent};
+use einvoice_deps_yaserde::ser::{Config, Serializer};
+use einvoice_deps_yaserde::YaSerialize;
+use einvoice_deps_yaserde_derive::{YaDeserialize, YaSerialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Write};
+
+/// An element of an invoice that is not part of the model, e.g. `ext:UBLExtensions`, kept as XML
+///
+/// Paths consist of the qualified names of the elements with their number among equally named siblings,
+/// e.g. `/ubl:Invoice[1]/cac:InvoiceLine[2]`, and use the prefixes `ubl`, `cac`, `cbc`, `ext`, `rsm`, `ram`, `udt` and `qdt`
+/// regardless of the prefixes declared in the invoice.
+///
+/// Only whole elements are kept. Unknown attributes of modelled elements are not represented and get lost.
+#[derive(Debug, Clone, Default, PartialEq, Eq, YaSerialize, YaDeserialize, uniffi::Record)]
+pub struct XmlFragment {
+    /// The path of the parent element
+    pub parent: String,
+    /// The modelled sibling the element follows, e.g. `cbc:IssueDate[1]`, `None` if it is the first child
+    pub preceding: Option<String>,
+    /// The element as XML, declaring the namespaces it uses
+    pub xml: String,
+}
+
+/// The reader configuration of the deserializer, so that text is compared the way the model reads it
+fn parser_config() -> ParserConfig {
+    ParserConfig::new()
+        .trim_whitespace(true)
+        .whitespace_to_characters(true)
+        .cdata_to_characters(true)
+        .ignore_comments(true)
+        .coalesce_characters(true)
+}
+
+/// An element with the range of its events in the document
+struct Node {
+    name: String,
+    start: usize,
+    end: usize,
+    children: Vec<Node>,
+}
+
+/// Read the events and the element tree of an XML document
+fn read_tree(xml: &str) -> Option<(Vec<XmlEvent>, Node)> {
+    let mut events = Vec::new();
+    let mut stack: Vec<Node> = Vec::new();
+    let mut root = None;
+    for event in EventReader::new_with_config(xml.as_bytes(), parser_config()) {
+        let event = event.ok()?;
+        match &event {
+            XmlEvent::StartElement { name, .. } => stack.push(Node {
+                name: qualified_name(name),
+                start: events.len(),
+                end: events.len(),
+                children: Vec::new(),
+            }),
+            XmlEvent::EndElement { .. } => {
+                let mut node = stack.pop()?;
+                node.end = events.len();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => root = Some(node),
+                }
+            }
+            _ => {}
+        }
+        events.push(event);
+    }
+    root.map(|root| (events, root))
+}
+
+/// Collect the elements of an invoice that its model does not keep
+/// # Arguments
+/// * `xml` - The XML content the invoice was parsed from
+/// * `invoice_standard` - The parsed invoice, without unknown elements
+/// # Returns
+/// * `Vec<XmlFragment>` - The elements missing when the invoice is written, in document order
+///
+/// The elements are found by comparing the element tree of the invoice with the tree written from its model.
+pub(crate) fn unknown_elements(xml: &str, invoice_standard: &InvoiceStandard) -> Vec<XmlFragment> {
+    let Ok(modelled) = einvoice_deps_yaserde::ser::to_string(invoice_standard) else {
+        return Vec::new();
+    };
+    let (Some((events, source)), Some((_, modelled))) = (read_tree(xml), read_tree(&modelled))
+    else {
+        return Vec::new();
+    };
+    let mut fragments = Vec::new();
+    if source.name == modelled.name {
+        let path = format!("/{}[1]", modelled.name);
+        collect_unknown_elements(&events, &source, &modelled, &path, &mut fragments);
+    }
+    fragments
+}
+
+fn collect_unknown_elements(
+    events: &[XmlEvent],
+    source: &Node,
+    modelled: &Node,
+    path: &str,
+    fragments: &mut Vec<XmlFragment>,
+) {
+    let mut next = 0;
+    let mut numbers: HashMap<&str, u32> = HashMap::new();
+    let mut preceding = None;
+    for child in &source.children {
+        let matching = modelled.children[next..]
+            .iter()
+            .position(|candidate| candidate.name == child.name);
+        match matching {
+            Some(offset) => {
+                for candidate in &modelled.children[next..=next + offset] {
+                    *numbers.entry(&candidate.name).or_default() += 1;
+                }
+                let candidate = &modelled.children[next + offset];
+                let step = format!("{}[{}]", candidate.name, numbers[candidate.name.as_str()]);
+                let child_path = format!("{}/{}", path, step);
+                collect_unknown_elements(events, child, candidate, &child_path, fragments);
+                preceding = Some(step);
+                next += offset + 1;
+            }
+            None => {
+                if let Some(xml) = write_events(&events[child.start..=child.end]) {
+                    fragments.push(XmlFragment {
+                        parent: path.to_string(),
+                        preceding: preceding.clone(),
+                        xml,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Write events as an XML fragment without declaration
+fn write_events(events: &[XmlEvent]) -> Option<String> {
+    let mut writer = EmitterConfig::new()
+        .write_document_declaration(false)
+        .create_writer(Vec::new());
+    for event in events {
+        if let Some(event) = event.as_writer_event() {
+            writer.write(event).ok()?;
+        }
+    }
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+/// Serialize an invoice model and insert its unknown elements at their original position
+/// # Arguments
+/// * `invoice` - The invoice model
+/// * `fragments` - The unknown elements of the invoice
+/// * `writer` - The serializer to write to
+/// # Errors
+/// * `String` - If the invoice or a fragment cannot be written
+///
+/// An element whose preceding sibling no longer exists is written as the last child of its parent,
+/// an element whose parent no longer exists is dropped.
+pub(crate) fn serialize_with_unknown_elements<T: YaSerialize, W: Write>(
+    invoice: &T,
+    fragments: &[XmlFragment],
+    writer: &mut Serializer<W>,
+) -> Result<(), String> {
+    if fragments.is_empty() {
+        return invoice.serialize(writer);
+    }
+    let xml = einvoice_deps_yaserde::ser::to_string(invoice)?;
+    // The path, child numbers and written child steps of every open element
+    let mut stack: Vec<(String, HashMap<String, u32>, HashSet<String>)> = Vec::new();
+    let mut default_namespace = false;
+    for event in EventReader::new(xml.as_bytes()) {
+        let event = event.map_err(|e| e.to_string())?;
+        match &event {
+            XmlEvent::StartDocument { .. } | XmlEvent::EndDocument => {}
+            XmlEvent::StartElement {
+                name, namespace, ..
+            } => {
+                if stack.is_empty() {
+                    default_namespace = namespace
+                        .get(NS_NO_PREFIX)
+                        .is_some_and(|uri| !uri.is_empty());
+                }
+                let name = qualified_name(name);
+                let (path, step) = match stack.last_mut() {
+                    Some((parent, numbers, _)) => {
+                        let number = numbers.entry(name.clone()).or_default();
+                        *number += 1;
+                        let step = format!("{}[{}]", name, number);
+                        (format!("{}/{}", parent, step), step)
+                    }
+                    None => (format!("/{}[1]", name), String::new()),
+                };
+                if let Some((_, _, written)) = stack.last_mut() {
+                    written.insert(step);
+                }
+                write_event(writer, &event)?;
+                write_fragments(
+                    writer,
+                    default_namespace,
+                    fragments
+                        .iter()
+                        .filter(|f| f.parent == path && f.preceding.is_none()),
+                )?;
+                stack.push((path, HashMap::new(), HashSet::new()));
+            }
+            XmlEvent::EndElement { .. } => {
+                let (path, _, written) = stack.pop().ok_or("Unbalanced end element")?;
+                write_fragments(
+                    writer,
+                    default_namespace,
+                    fragments.iter().filter(|f| {
+                        f.parent == path
+                            && f.preceding
+                                .as_ref()
+                                .is_some_and(|preceding| !written.contains(preceding))
+                    }),
+                )?;
+                write_event(writer, &event)?;
+                if let Some((parent, _, _)) = stack.last() {
+                    let step = path.rsplit('/').next().unwrap_or_default();
+                    write_fragments(
+                        writer,
+                        default_namespace,
+                        fragments.iter().filter(|f| {
+                            &f.parent == parent && f.preceding.as_deref() == Some(step)
+                        }),
+                    )?;
+                }
+            }
+            _ => write_event(writer, &event)?,
+        }
+    }
+    Ok(())
+}
+
+/// Write unknown elements by replaying their events
+///
+/// If the invoice declares a default namespace, it is undeclared for elements that are not in a namespace.
+fn write_fragments<'a, W: Write>(
+    writer: &mut Serializer<W>,
+    default_namespace: bool,
+    fragments: impl Iterator<Item = &'a XmlFragment>,
+) -> Result<(), String> {
+    for fragment in fragments {
+        let mut root = true;
+        for event in EventReader::new_with_config(fragment.xml.as_bytes(), parser_config()) {
+            match event.map_err(|e| e.to_string())? {
+                XmlEvent::StartDocument { .. } => {}
+                XmlEvent::StartElement {
+                    name,
+                    attributes,
+                    namespace,
+                } if root
+                    && default_namespace
+                    && namespace.get(NS_NO_PREFIX).is_none_or(str::is_empty) =>
+                {
+                    let mut attributes = attributes.iter().map(|a| a.borrow()).collect::<Vec<_>>();
+                    attributes.push(Attribute::new(Name::local("xmlns"), ""));
+                    writer
+                        .write(WriterEvent::StartElement {
+                            name: name.borrow(),
+                            attributes: Cow::Owned(attributes),
+                            namespace: Cow::Borrowed(&namespace),
+                        })
+                        .map_err(|e| e.to_string())?;
+                    root = false;
+                }
+                event => {
+                    root &= !matches!(event, XmlEvent::StartElement { .. });
+                    write_event(writer, &event)?
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_event<W: Write>(writer: &mut Serializer<W>, event: &XmlEvent) -> Result<(), String> {
+    match event.as_writer_event() {
+        Some(event) => writer.write(event).map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Serialize an invoice model with its unknown elements as an XML document
+pub(crate) fn to_string_with_unknown_elements<T: YaSerialize>(
+    invoice: &T,
+    fragments: &[XmlFragment],
+) -> Result<String, String> {
+    let mut serializer = Serializer::new_from_writer(Cursor::new(Vec::new()), &Config::default());
+    serialize_with_unknown_elements(invoice, fragments, &mut serializer)?;
+    String::from_utf8(serializer.into_inner().into_inner()).map_err(|e| e.to_string())
+}
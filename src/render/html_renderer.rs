@@ -0,0 +1,2192 @@
+use crate::cii::cii_model::{
+    DateTimeString, DefinedTradeContact, IdType, PostalTradeAddress,
+    RamIncludedSupplyChainTradeLineItem, TradeParty,
+};
+use crate::ubl::ubl_model::{
+    Address, Amount, CacAllowanceCharge, CacDocumentReference, Contact, Identifier, InvoiceLine,
+    Party, SubInvoiceLine, SubSubInvoiceLine, SubSubSubInvoiceLine, TaxCategory,
+};
+use crate::{CrossIndustryInvoice, InvoiceStandard, UblInvoice};
+
+const STYLE: &str = r#"
+body { font-family: Helvetica, Arial, sans-serif; font-size: 10pt; color: #222; margin: 2em; }
+h1 { font-size: 16pt; border-bottom: 2px solid #444; padding-bottom: .3em; }
+h2 { font-size: 12pt; background: #e8e8e8; padding: .3em .5em; margin: 1.5em 0 .5em; }
+h3, h4, h5, h6 { font-size: 10.5pt; margin: 1em 0 .3em; }
+section section { margin-left: 1.5em; border-left: 2px solid #ddd; padding-left: .8em; }
+table { border-collapse: collapse; width: 100%; }
+th, td { text-align: left; vertical-align: top; padding: .15em .5em; border-bottom: 1px solid #eee; }
+th.term { width: 5em; color: #777; font-weight: normal; font-family: monospace; }
+th.label { width: 18em; }
+td { white-space: pre-wrap; word-break: break-word; }
+@media print {
+  body { margin: 0; }
+  h2 { break-after: avoid; }
+  section { break-inside: avoid-page; }
+}
+"#;
+
+/// Render an invoice as a standalone, printable HTML document
+/// # Arguments
+/// * `invoice` - The invoice to render
+/// # Returns
+/// * `String` - The HTML document
+///
+/// Every business group (BG) of EN 16931 present in the invoice is shown with its business terms (BT).
+/// Embedded attachments are linked as data URIs, so the document does not need any network assets.
+pub fn render_html(invoice: &InvoiceStandard) -> String {
+    let (title, sections) = match invoice {
+        InvoiceStandard::UBL(invoice) => (
+            invoice.cbc_id.clone().unwrap_or_default(),
+            ubl_sections(invoice),
+        ),
+        InvoiceStandard::CII(invoice) => (
+            invoice
+                .rsm_exchanged_document
+                .as_ref()
+                .and_then(|document| document.ram_id.as_ref())
+                .and_then(|id| id.id.clone())
+                .unwrap_or_default(),
+            cii_sections(invoice),
+        ),
+    };
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>");
+    html.push_str(&escape(&format!("Invoice {}", title)));
+    html.push_str("</title>\n<style>");
+    html.push_str(STYLE);
+    html.push_str("</style>\n</head>\n<body>\n<h1>");
+    html.push_str(&escape(&format!("Invoice {}", title)));
+    html.push_str("</h1>\n");
+    for section in sections.iter().filter(|section| !section.is_empty()) {
+        section.write(&mut html, 2);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+struct Field {
+    term: &'static str,
+    label: &'static str,
+    value: String,
+}
+
+struct Section {
+    title: String,
+    fields: Vec<Field>,
+    links: Vec<(String, String)>,
+    children: Vec<Section>,
+}
+
+impl Section {
+    fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            fields: vec![],
+            links: vec![],
+            children: vec![],
+        }
+    }
+
+    fn field(&mut self, term: &'static str, label: &'static str, value: Option<String>) {
+        if let Some(value) = value.filter(|value| !value.trim().is_empty()) {
+            self.fields.push(Field { term, label, value });
+        }
+    }
+
+    fn child(&mut self, section: Section) {
+        if !section.is_empty() {
+            self.children.push(section);
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.links.is_empty() && self.children.is_empty()
+    }
+
+    fn write(&self, html: &mut String, level: usize) {
+        let level = level.min(6);
+        html.push_str(&format!(
+            "<section>\n<h{level}>{}</h{level}>\n",
+            escape(&self.title)
+        ));
+        if !self.fields.is_empty() || !self.links.is_empty() {
+            html.push_str("<table>\n");
+            for field in &self.fields {
+                html.push_str(&format!(
+                    "<tr><th class=\"term\">{}</th><th class=\"label\">{}</th><td>{}</td></tr>\n",
+                    field.term,
+                    field.label,
+                    escape(&field.value)
+                ));
+            }
+            for (name, href) in &self.links {
+                html.push_str(&format!(
+                    "<tr><th class=\"term\"></th><th class=\"label\">Download</th><td><a download=\"{name}\" href=\"{href}\">{name}</a></td></tr>\n",
+                    name = escape(name),
+                    href = escape(href)
+                ));
+            }
+            html.push_str("</table>\n");
+        }
+        for child in &self.children {
+            child.write(html, level + 1);
+        }
+        html.push_str("</section>\n");
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn data_uri(mime_code: Option<&str>, content: Option<&str>) -> Option<String> {
+    content.map(|content| {
+        format!(
+            "data:{};base64,{}",
+            mime_code.unwrap_or("application/octet-stream"),
+            content.split_whitespace().collect::<String>()
+        )
+    })
+}
+
+fn with_scheme(value: Option<&String>, scheme: Option<&String>) -> Option<String> {
+    value.map(|value| match scheme {
+        Some(scheme) => format!("{} (scheme: {})", value, scheme),
+        None => value.clone(),
+    })
+}
+
+fn join(values: impl IntoIterator<Item = Option<String>>) -> Option<String> {
+    let values = values.into_iter().flatten().collect::<Vec<_>>();
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.join("\n"))
+    }
+}
+
+fn ubl_identifier(identifier: Option<&Identifier>) -> Option<String> {
+    identifier
+        .and_then(|identifier| with_scheme(identifier.id.as_ref(), identifier.scheme_id.as_ref()))
+}
+
+fn ubl_amount(amount: Option<&Amount>) -> Option<String> {
+    amount.and_then(|amount| {
+        amount.value.map(|value| match &amount.currency_id {
+            Some(currency) => format!("{} {}", value, currency),
+            None => value.to_string(),
+        })
+    })
+}
+
+fn ubl_sections(invoice: &UblInvoice) -> Vec<Section> {
+    let mut sections = Vec::new();
+
+    let mut header = Section::new("Invoice");
+    header.field("BT-1", "Invoice number", invoice.cbc_id.clone());
+    header.field(
+        "BT-2",
+        "Invoice issue date",
+        invoice.cbc_issue_date.as_ref().map(ToString::to_string),
+    );
+    header.field(
+        "BT-3",
+        "Invoice type code",
+        invoice.cbc_invoice_type_code.clone(),
+    );
+    header.field(
+        "BT-5",
+        "Invoice currency code",
+        invoice.cbc_document_currency_code.clone(),
+    );
+    header.field(
+        "BT-6",
+        "VAT accounting currency code",
+        invoice.cbc_tax_currency_code.clone(),
+    );
+    header.field(
+        "BT-7",
+        "Value added tax point date",
+        invoice.cbc_tax_point_date.as_ref().map(ToString::to_string),
+    );
+    header.field(
+        "BT-8",
+        "Value added tax point date code",
+        invoice
+            .cac_invoice_period
+            .as_ref()
+            .and_then(|period| period.cbc_description_code.clone()),
+    );
+    header.field(
+        "BT-9",
+        "Payment due date",
+        invoice.cbc_due_date.as_ref().map(ToString::to_string),
+    );
+    header.field(
+        "BT-10",
+        "Buyer reference",
+        invoice.cbc_buyer_reference.clone(),
+    );
+    header.field(
+        "BT-11",
+        "Project reference",
+        invoice
+            .cac_project_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_id.clone()),
+    );
+    header.field(
+        "BT-12",
+        "Contract reference",
+        invoice
+            .cac_contract_document_reference
+            .as_ref()
+            .and_then(|reference| ubl_identifier(reference.id.as_ref())),
+    );
+    header.field(
+        "BT-13",
+        "Purchase order reference",
+        invoice
+            .cac_order_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_id.clone()),
+    );
+    header.field(
+        "BT-14",
+        "Sales order reference",
+        invoice
+            .cac_order_reference
+            .as_ref()
+            .and_then(|reference| reference.cbc_sales_order_id.clone()),
+    );
+    header.field(
+        "BT-15",
+        "Receiving advice reference",
+        invoice
+            .cac_receipt_document_reference
+            .as_ref()
+            .and_then(|reference| ubl_identifier(reference.id.as_ref())),
+    );
+    header.field(
+        "BT-16",
+        "Despatch advice reference",
+        invoice
+            .cac_despatch_document_reference
+            .as_ref()
+            .and_then(|reference| ubl_identifier(reference.id.as_ref())),
+    );
+    header.field(
+        "BT-17",
+        "Tender or lot reference",
+        invoice
+            .cac_originator_document_reference
+            .as_ref()
+            .and_then(|reference| ubl_identifier(reference.id.as_ref())),
+    );
+    header.field(
+        "BT-19",
+        "Buyer accounting reference",
+        invoice.cbc_accounting_cost.clone(),
+    );
+    header.field(
+        "BT-20",
+        "Payment terms",
+        invoice
+            .cac_payment_terms
+            .as_ref()
+            .and_then(|terms| terms.cbc_note.clone()),
+    );
+    sections.push(header);
+
+    let mut notes = Section::new("BG-1 Invoice note");
+    for note in &invoice.cbc_note {
+        notes.field("BT-22", "Invoice note", Some(note.clone()));
+    }
+    sections.push(notes);
+
+    let mut process = Section::new("BG-2 Process control");
+    process.field(
+        "BT-23",
+        "Business process type",
+        invoice.cbc_profile_id.clone(),
+    );
+    process.field(
+        "BT-24",
+        "Specification identifier",
+        invoice.cbc_customization_id.clone(),
+    );
+    sections.push(process);
+
+    for billing_reference in &invoice.cac_billing_reference {
+        if let Some(reference) = &billing_reference.cac_invoice_document_reference {
+            let mut preceding = Section::new("BG-3 Preceding invoice reference");
+            preceding.field(
+                "BT-25",
+                "Preceding invoice reference",
+                ubl_identifier(reference.id.as_ref()),
+            );
+            preceding.field(
+                "BT-26",
+                "Preceding invoice issue date",
+                reference.cbc_issue_date.as_ref().map(ToString::to_string),
+            );
+            sections.push(preceding);
+        }
+    }
+
+    if let Some(party) = invoice
+        .cac_accounting_supplier_party
+        .as_ref()
+        .and_then(|party| party.cac_party.as_ref())
+    {
+        sections.push(ubl_party(
+            party,
+            "BG-4 Seller",
+            [
+                "BT-27", "BT-28", "BT-29", "BT-30", "BT-31", "BT-32", "BT-33", "BT-34",
+            ],
+            ("BG-5 Seller postal address", ADDRESS_TERMS_SELLER),
+            ("BG-6 Seller contact", ["BT-41", "BT-42", "BT-43"]),
+        ));
+    }
+    if let Some(party) = invoice
+        .cac_accounting_customer_party
+        .as_ref()
+        .and_then(|party| party.cac_party.as_ref())
+    {
+        sections.push(ubl_party(
+            party,
+            "BG-7 Buyer",
+            ["BT-44", "BT-45", "BT-46", "BT-47", "BT-48", "", "", "BT-49"],
+            ("BG-8 Buyer postal address", ADDRESS_TERMS_BUYER),
+            ("BG-9 Buyer contact", ["BT-56", "BT-57", "BT-58"]),
+        ));
+    }
+    if let Some(party) = &invoice.cac_payee_party {
+        let mut payee = Section::new("BG-10 Payee");
+        payee.field(
+            "BT-59",
+            "Payee name",
+            party
+                .cac_party_name
+                .iter()
+                .find_map(|name| name.cbc_name.clone()),
+        );
+        payee.field(
+            "BT-60",
+            "Payee identifier",
+            join(
+                party
+                    .cac_party_identification
+                    .iter()
+                    .map(|identification| ubl_identifier(identification.cbc_id.as_ref())),
+            ),
+        );
+        payee.field(
+            "BT-61",
+            "Payee legal registration identifier",
+            party.cac_party_legal_entity.iter().find_map(|entity| {
+                entity.cbc_company_id.as_ref().and_then(|company_id| {
+                    with_scheme(company_id.text.as_ref(), company_id.scheme_id.as_ref())
+                })
+            }),
+        );
+        sections.push(payee);
+    }
+    if let Some(party) = &invoice.cac_tax_representative_party {
+        let mut representative = Section::new("BG-11 Seller tax representative party");
+        representative.field(
+            "BT-62",
+            "Seller tax representative name",
+            party
+                .cac_party_name
+                .iter()
+                .find_map(|name| name.cbc_name.clone()),
+        );
+        representative.field(
+            "BT-63",
+            "Seller tax representative VAT identifier",
+            party
+                .cac_party_tax_scheme
+                .iter()
+                .find_map(|scheme| scheme.cbc_company_id.clone()),
+        );
+        representative.child(ubl_address(
+            party.cac_postal_address.as_ref(),
+            "BG-12 Seller tax representative postal address",
+            ADDRESS_TERMS_TAX_REPRESENTATIVE,
+        ));
+        sections.push(representative);
+    }
+
+    if let Some(delivery) = &invoice.cac_delivery {
+        let mut section = Section::new("BG-13 Delivery information");
+        section.field(
+            "BT-70",
+            "Deliver to party name",
+            delivery.cac_delivery_party.as_ref().and_then(|party| {
+                party
+                    .cac_party_name
+                    .iter()
+                    .find_map(|name| name.cbc_name.clone())
+            }),
+        );
+        section.field(
+            "BT-71",
+            "Deliver to location identifier",
+            delivery
+                .cac_delivery_location
+                .as_ref()
+                .and_then(|location| ubl_identifier(location.cbc_id.as_ref())),
+        );
+        section.field(
+            "BT-72",
+            "Actual delivery date",
+            delivery
+                .cbc_actual_delivery_date
+                .as_ref()
+                .map(ToString::to_string),
+        );
+        section.child(ubl_address(
+            delivery
+                .cac_delivery_location
+                .as_ref()
+                .and_then(|location| location.cac_address.as_ref()),
+            "BG-15 Deliver to address",
+            ADDRESS_TERMS_DELIVERY,
+        ));
+        sections.push(section);
+    }
+    if let Some(period) = &invoice.cac_invoice_period {
+        let mut section = Section::new("BG-14 Invoicing period");
+        section.field(
+            "BT-73",
+            "Invoicing period start date",
+            period.cbc_start_date.as_ref().map(ToString::to_string),
+        );
+        section.field(
+            "BT-74",
+            "Invoicing period end date",
+            period.cbc_end_date.as_ref().map(ToString::to_string),
+        );
+        sections.push(section);
+    }
+
+    for payment_means in &invoice.cac_payment_means {
+        let mut section = Section::new("BG-16 Payment instructions");
+        section.field(
+            "BT-81",
+            "Payment means type code",
+            payment_means
+                .cbc_payment_means_code
+                .as_ref()
+                .and_then(|code| code.text.clone()),
+        );
+        section.field(
+            "BT-82",
+            "Payment means text",
+            payment_means
+                .cbc_payment_means_code
+                .as_ref()
+                .and_then(|code| code.name.clone()),
+        );
+        section.field(
+            "BT-83",
+            "Remittance information",
+            ubl_identifier(payment_means.cbc_payment_id.as_ref()),
+        );
+        if let Some(account) = &payment_means.cac_payee_financial_account {
+            let mut transfer = Section::new("BG-17 Credit transfer");
+            transfer.field(
+                "BT-84",
+                "Payment account identifier",
+                account.cbc_id.clone(),
+            );
+            transfer.field("BT-85", "Payment account name", account.cbc_name.clone());
+            transfer.field(
+                "BT-86",
+                "Payment service provider identifier",
+                account
+                    .cac_financial_institution_branch
+                    .as_ref()
+                    .and_then(|branch| branch.cbc_id.clone()),
+            );
+            section.child(transfer);
+        }
+        if let Some(card) = &payment_means.cac_card_account {
+            let mut card_section = Section::new("BG-18 Payment card information");
+            card_section.field(
+                "BT-87",
+                "Payment card primary account number",
+                ubl_identifier(card.primary_account_number_id.as_ref()),
+            );
+            card_section.field(
+                "BT-88",
+                "Payment card holder name",
+                card.cbc_holder_name.clone(),
+            );
+            section.child(card_section);
+        }
+        if let Some(mandate) = &payment_means.cac_payment_mandate {
+            let mut debit = Section::new("BG-19 Direct debit");
+            debit.field(
+                "BT-89",
+                "Mandate reference identifier",
+                ubl_identifier(mandate.cbc_id.as_ref()),
+            );
+            debit.field(
+                "BT-91",
+                "Debited account identifier",
+                mandate
+                    .cac_payer_financial_account
+                    .as_ref()
+                    .and_then(|account| account.cbc_id.clone()),
+            );
+            section.child(debit);
+        }
+        sections.push(section);
+    }
+
+    for allowance_charge in &invoice.cac_allowance_charge {
+        sections.push(ubl_allowance_charge(allowance_charge, false));
+    }
+
+    if let Some(total) = &invoice.cac_legal_monetary_total {
+        let mut section = Section::new("BG-22 Document totals");
+        section.field(
+            "BT-106",
+            "Sum of Invoice line net amount",
+            ubl_amount(total.cbc_line_extension_amount.as_ref()),
+        );
+        section.field(
+            "BT-107",
+            "Sum of allowances on document level",
+            ubl_amount(total.cbc_allowance_total_amount.as_ref()),
+        );
+        section.field(
+            "BT-108",
+            "Sum of charges on document level",
+            ubl_amount(total.cbc_charge_total_amount.as_ref()),
+        );
+        section.field(
+            "BT-109",
+            "Invoice total amount without VAT",
+            ubl_amount(total.cbc_tax_exclusive_amount.as_ref()),
+        );
+        for (index, tax_total) in invoice.cac_tax_total.iter().enumerate() {
+            section.field(
+                if index == 0 { "BT-110" } else { "BT-111" },
+                if index == 0 {
+                    "Invoice total VAT amount"
+                } else {
+                    "Invoice total VAT amount in accounting currency"
+                },
+                ubl_amount(tax_total.cbc_tax_amount.as_ref()),
+            );
+        }
+        section.field(
+            "BT-112",
+            "Invoice total amount with VAT",
+            ubl_amount(total.cbc_tax_inclusive_amount.as_ref()),
+        );
+        section.field(
+            "BT-113",
+            "Paid amount",
+            ubl_amount(total.cbc_prepaid_amount.as_ref()),
+        );
+        section.field(
+            "BT-114",
+            "Rounding amount",
+            ubl_amount(total.cbc_payable_rounding_amount.as_ref()),
+        );
+        section.field(
+            "BT-115",
+            "Amount due for payment",
+            ubl_amount(total.payable_amount.as_ref()),
+        );
+        sections.push(section);
+    }
+
+    for subtotal in invoice
+        .cac_tax_total
+        .iter()
+        .flat_map(|tax_total| tax_total.cac_tax_subtotal.iter())
+    {
+        let mut section = Section::new("BG-23 VAT breakdown");
+        section.field(
+            "BT-116",
+            "VAT category taxable amount",
+            ubl_amount(subtotal.cbc_taxable_amount.as_ref()),
+        );
+        section.field(
+            "BT-117",
+            "VAT category tax amount",
+            ubl_amount(subtotal.cbc_tax_amount.as_ref()),
+        );
+        if let Some(category) = &subtotal.cac_tax_category {
+            section.field("BT-118", "VAT category code", category.cbc_id.clone());
+            section.field("BT-119", "VAT category rate", category.cbc_percent.clone());
+            section.field(
+                "BT-120",
+                "VAT exemption reason text",
+                join(category.cbc_tax_exemption_reason.iter().cloned().map(Some)),
+            );
+            section.field(
+                "BT-121",
+                "VAT exemption reason code",
+                category.cbc_tax_exemption_reason_code.clone(),
+            );
+        }
+        sections.push(section);
+    }
+
+    for document in &invoice.cac_additional_document_reference {
+        sections.push(ubl_additional_document(document));
+    }
+
+    for line in &invoice.cac_invoice_line {
+        sections.push(ubl_line(line));
+    }
+    sections
+}
+
+const ADDRESS_TERMS_SELLER: [&str; 7] = [
+    "BT-35", "BT-36", "BT-162", "BT-37", "BT-38", "BT-39", "BT-40",
+];
+const ADDRESS_TERMS_BUYER: [&str; 7] = [
+    "BT-50", "BT-51", "BT-163", "BT-52", "BT-53", "BT-54", "BT-55",
+];
+const ADDRESS_TERMS_TAX_REPRESENTATIVE: [&str; 7] = [
+    "BT-64", "BT-65", "BT-164", "BT-66", "BT-67", "BT-68", "BT-69",
+];
+const ADDRESS_TERMS_DELIVERY: [&str; 7] = [
+    "BT-75", "BT-76", "BT-165", "BT-77", "BT-78", "BT-79", "BT-80",
+];
+
+fn ubl_party(
+    party: &Party,
+    title: &str,
+    terms: [&'static str; 8],
+    address: (&str, [&'static str; 7]),
+    contact: (&str, [&'static str; 3]),
+) -> Section {
+    let mut section = Section::new(title);
+    section.field(
+        terms[0],
+        "Name",
+        party
+            .cac_party_legal_entity
+            .iter()
+            .find_map(|entity| entity.cbc_registration_name.clone()),
+    );
+    section.field(
+        terms[1],
+        "Trading name",
+        party
+            .cac_party_name
+            .iter()
+            .find_map(|name| name.cbc_name.clone()),
+    );
+    section.field(
+        terms[2],
+        "Identifier",
+        join(
+            party
+                .cac_party_identification
+                .iter()
+                .map(|identification| ubl_identifier(identification.cbc_id.as_ref())),
+        ),
+    );
+    section.field(
+        terms[3],
+        "Legal registration identifier",
+        party.cac_party_legal_entity.iter().find_map(|entity| {
+            entity.cbc_company_id.as_ref().and_then(|company_id| {
+                with_scheme(company_id.text.as_ref(), company_id.scheme_id.as_ref())
+            })
+        }),
+    );
+    let is_vat = |scheme: &&crate::ubl::ubl_model::PartyTaxScheme| {
+        scheme
+            .cac_tax_scheme
+            .as_ref()
+            .and_then(|tax_scheme| tax_scheme.id.as_ref())
+            .and_then(|id| id.id.as_deref())
+            == Some("VAT")
+    };
+    section.field(
+        terms[4],
+        "VAT identifier",
+        party
+            .cac_party_tax_scheme
+            .iter()
+            .filter(is_vat)
+            .find_map(|scheme| scheme.cbc_company_id.clone()),
+    );
+    if !terms[5].is_empty() {
+        section.field(
+            terms[5],
+            "Tax registration identifier",
+            party
+                .cac_party_tax_scheme
+                .iter()
+                .filter(|scheme| !is_vat(scheme))
+                .find_map(|scheme| scheme.cbc_company_id.clone()),
+        );
+    }
+    if !terms[6].is_empty() {
+        section.field(
+            terms[6],
+            "Additional legal information",
+            party
+                .cac_party_legal_entity
+                .iter()
+                .find_map(|entity| entity.cbc_company_legal_form.clone()),
+        );
+    }
+    section.field(
+        terms[7],
+        "Electronic address",
+        ubl_identifier(party.cbc_endpoint_id.as_ref()),
+    );
+    section.child(ubl_address(
+        party.cac_postal_address.as_ref(),
+        address.0,
+        address.1,
+    ));
+    section.child(ubl_contact(
+        party.cac_contact.as_ref(),
+        contact.0,
+        contact.1,
+    ));
+    section
+}
+
+fn ubl_address(address: Option<&Address>, title: &str, terms: [&'static str; 7]) -> Section {
+    let mut section = Section::new(title);
+    if let Some(address) = address {
+        section.field(terms[0], "Address line 1", address.cbc_street_name.clone());
+        section.field(
+            terms[1],
+            "Address line 2",
+            address.cbc_additional_street_name.clone(),
+        );
+        section.field(
+            terms[2],
+            "Address line 3",
+            join(
+                address
+                    .cac_address_line
+                    .iter()
+                    .map(|line| line.cbc_line.clone()),
+            ),
+        );
+        section.field(terms[3], "City", address.cbc_city_name.clone());
+        section.field(terms[4], "Post code", address.cbc_postal_zone.clone());
+        section.field(
+            terms[5],
+            "Country subdivision",
+            address.cbc_country_subentity.clone(),
+        );
+        section.field(
+            terms[6],
+            "Country code",
+            address
+                .cac_country
+                .as_ref()
+                .and_then(|country| country.cbc_identification_code.clone()),
+        );
+    }
+    section
+}
+
+fn ubl_contact(contact: Option<&Contact>, title: &str, terms: [&'static str; 3]) -> Section {
+    let mut section = Section::new(title);
+    if let Some(contact) = contact {
+        section.field(terms[0], "Contact point", contact.cbc_name.clone());
+        section.field(
+            terms[1],
+            "Contact telephone number",
+            contact.cbc_telephone.clone(),
+        );
+        section.field(
+            terms[2],
+            "Contact email address",
+            contact.cbc_electronic_mail.clone(),
+        );
+    }
+    section
+}
+
+fn ubl_allowance_charge(allowance_charge: &CacAllowanceCharge, line_level: bool) -> Section {
+    let charge = allowance_charge.charge_indicator.unwrap_or(false);
+    let (title, terms) = match (line_level, charge) {
+        (false, false) => (
+            "BG-20 Document level allowances",
+            [
+                "BT-92", "BT-93", "BT-94", "BT-95", "BT-96", "BT-97", "BT-98",
+            ],
+        ),
+        (false, true) => (
+            "BG-21 Document level charges",
+            [
+                "BT-99", "BT-100", "BT-101", "BT-102", "BT-103", "BT-104", "BT-105",
+            ],
+        ),
+        (true, false) => (
+            "BG-27 Invoice line allowances",
+            ["BT-136", "BT-137", "BT-138", "", "", "BT-139", "BT-140"],
+        ),
+        (true, true) => (
+            "BG-28 Invoice line charges",
+            ["BT-141", "BT-142", "BT-143", "", "", "BT-144", "BT-145"],
+        ),
+    };
+    let mut section = Section::new(title);
+    section.field(
+        terms[0],
+        "Amount",
+        ubl_amount(allowance_charge.amount.as_ref()),
+    );
+    section.field(
+        terms[1],
+        "Base amount",
+        ubl_amount(allowance_charge.cbc_base_amount.as_ref()),
+    );
+    section.field(
+        terms[2],
+        "Percentage",
+        allowance_charge
+            .cbc_multiplier_factor_numeric
+            .map(|factor| factor.to_string()),
+    );
+    if !terms[3].is_empty() {
+        let category = allowance_charge.cac_tax_category.first();
+        section.field(
+            terms[3],
+            "VAT category code",
+            category.and_then(|category| category.cbc_id.clone()),
+        );
+        section.field(
+            terms[4],
+            "VAT rate",
+            category.and_then(|category| category.cbc_percent.clone()),
+        );
+    }
+    section.field(
+        terms[5],
+        "Reason",
+        allowance_charge.cbc_allowance_charge_reason.clone(),
+    );
+    section.field(
+        terms[6],
+        "Reason code",
+        allowance_charge.cbc_allowance_charge_reason_code.clone(),
+    );
+    section
+}
+
+fn ubl_additional_document(document: &CacDocumentReference) -> Section {
+    let mut section = Section::new("BG-24 Additional supporting documents");
+    section.field(
+        "BT-122",
+        "Supporting document reference",
+        ubl_identifier(document.id.as_ref()),
+    );
+    section.field(
+        "BT-18",
+        "Invoiced object identifier",
+        document
+            .cbc_document_type_code
+            .as_deref()
+            .filter(|code| *code == "130")
+            .and_then(|_| ubl_identifier(document.id.as_ref())),
+    );
+    section.field(
+        "BT-123",
+        "Supporting document description",
+        join(document.cbc_document_description.iter().cloned().map(Some)),
+    );
+    if let Some(attachment) = &document.cac_attachment {
+        section.field(
+            "BT-124",
+            "External document location",
+            attachment
+                .cac_external_reference
+                .as_ref()
+                .and_then(|reference| reference.cbc_uri.clone()),
+        );
+        if let Some(object) = &attachment.cbc_embedded_document_binary_object {
+            section.field(
+                "BT-125",
+                "Attached document",
+                join([object.filename.clone(), object.mime_code.clone()]),
+            );
+            if let Some(href) = data_uri(object.mime_code.as_deref(), object.text.as_deref()) {
+                section.links.push((
+                    object
+                        .filename
+                        .clone()
+                        .unwrap_or_else(|| "attachment".to_string()),
+                    href,
+                ));
+            }
+        }
+    }
+    section
+}
+
+fn ubl_tax_category(category: Option<&TaxCategory>) -> Section {
+    let mut section = Section::new("BG-30 Line VAT information");
+    if let Some(category) = category {
+        section.field(
+            "BT-151",
+            "Invoiced item VAT category code",
+            category.cbc_id.clone(),
+        );
+        section.field(
+            "BT-152",
+            "Invoiced item VAT rate",
+            category.cbc_percent.clone(),
+        );
+    }
+    section
+}
+
+/// The UBL invoice line and its sub-lines share the same structure but are distinct types
+macro_rules! ubl_line_section {
+    ($line:expr, $title:expr) => {{
+        let line = $line;
+        let mut section = Section::new(format!(
+            "{} {}",
+            $title,
+            line.cbc_id
+                .as_ref()
+                .and_then(|id| id.id.clone())
+                .unwrap_or_default()
+        ));
+        section.field(
+            "BT-126",
+            "Invoice line identifier",
+            ubl_identifier(line.cbc_id.as_ref()),
+        );
+        section.field("BT-127", "Invoice line note", line.cbc_note.clone());
+        section.field(
+            "BT-128",
+            "Invoice line object identifier",
+            line.cac_document_reference
+                .as_ref()
+                .and_then(|reference| ubl_identifier(reference.id.as_ref())),
+        );
+        section.field(
+            "BT-129",
+            "Invoiced quantity",
+            line.cbc_invoiced_quantity
+                .as_ref()
+                .and_then(|quantity| quantity.text.clone()),
+        );
+        section.field(
+            "BT-130",
+            "Invoiced quantity unit of measure code",
+            line.cbc_invoiced_quantity
+                .as_ref()
+                .and_then(|quantity| quantity.unit_code.clone()),
+        );
+        section.field(
+            "BT-131",
+            "Invoice line net amount",
+            ubl_amount(line.cbc_line_extension_amount.as_ref()),
+        );
+        section.field(
+            "BT-132",
+            "Referenced purchase order line reference",
+            line.cac_order_line_reference
+                .as_ref()
+                .and_then(|reference| reference.cbc_line_id.clone()),
+        );
+        section.field(
+            "BT-133",
+            "Invoice line Buyer accounting reference",
+            line.cbc_accounting_cost.clone(),
+        );
+        if let Some(period) = &line.cac_invoice_period {
+            let mut period_section = Section::new("BG-26 Invoice line period");
+            period_section.field(
+                "BT-134",
+                "Invoice line period start date",
+                period.cbc_start_date.as_ref().map(ToString::to_string),
+            );
+            period_section.field(
+                "BT-135",
+                "Invoice line period end date",
+                period.cbc_end_date.as_ref().map(ToString::to_string),
+            );
+            section.child(period_section);
+        }
+        for allowance_charge in &line.cac_allowance_charge {
+            section.child(ubl_allowance_charge(allowance_charge, true));
+        }
+        if let Some(price) = &line.cac_price {
+            let mut price_section = Section::new("BG-29 Price details");
+            price_section.field(
+                "BT-146",
+                "Item net price",
+                ubl_amount(price.cbc_price_amount.as_ref()),
+            );
+            price_section.field(
+                "BT-147",
+                "Item price discount",
+                price
+                    .cac_allowance_charge
+                    .as_ref()
+                    .and_then(|allowance| ubl_amount(allowance.amount.as_ref())),
+            );
+            price_section.field(
+                "BT-148",
+                "Item gross price",
+                price
+                    .cac_allowance_charge
+                    .as_ref()
+                    .and_then(|allowance| ubl_amount(allowance.cbc_base_amount.as_ref())),
+            );
+            price_section.field(
+                "BT-149",
+                "Item price base quantity",
+                price
+                    .cbc_base_quantity
+                    .as_ref()
+                    .and_then(|quantity| quantity.text.clone()),
+            );
+            price_section.field(
+                "BT-150",
+                "Item price base quantity unit of measure code",
+                price
+                    .cbc_base_quantity
+                    .as_ref()
+                    .and_then(|quantity| quantity.unit_code.clone()),
+            );
+            section.child(price_section);
+        }
+        if let Some(item) = &line.cac_item {
+            section.child(ubl_tax_category(item.cac_classified_tax_category.as_ref()));
+            let mut item_section = Section::new("BG-31 Item information");
+            item_section.field("BT-153", "Item name", item.cbc_name.clone());
+            item_section.field("BT-154", "Item description", item.cbc_description.clone());
+            item_section.field(
+                "BT-155",
+                "Item Seller's identifier",
+                item.cac_sellers_item_identification
+                    .as_ref()
+                    .and_then(|identification| identification.cbc_id.clone()),
+            );
+            item_section.field(
+                "BT-156",
+                "Item Buyer's identifier",
+                item.cac_buyers_item_identification
+                    .as_ref()
+                    .and_then(|identification| identification.cbc_id.clone()),
+            );
+            item_section.field(
+                "BT-157",
+                "Item standard identifier",
+                item.cac_standard_item_identification
+                    .as_ref()
+                    .and_then(|identification| identification.cbc_id.as_ref())
+                    .and_then(|id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref())),
+            );
+            item_section.field(
+                "BT-158",
+                "Item classification identifier",
+                join(
+                    item.cac_commodity_classification
+                        .iter()
+                        .map(|classification| {
+                            classification
+                                .cbc_item_classification_code
+                                .as_ref()
+                                .and_then(|code| {
+                                    with_scheme(code.text.as_ref(), code.list_id.as_ref())
+                                })
+                        }),
+                ),
+            );
+            item_section.field(
+                "BT-159",
+                "Item country of origin",
+                item.cac_origin_country
+                    .as_ref()
+                    .and_then(|country| country.cbc_identification_code.clone()),
+            );
+            for property in &item.cac_additional_item_property {
+                let mut attribute = Section::new("BG-32 Item attributes");
+                attribute.field("BT-160", "Item attribute name", property.cbc_name.clone());
+                attribute.field("BT-161", "Item attribute value", property.cbc_value.clone());
+                item_section.child(attribute);
+            }
+            section.child(item_section);
+        }
+        section
+    }};
+}
+
+fn ubl_line(line: &InvoiceLine) -> Section {
+    let mut section = ubl_line_section!(line, "BG-25 Invoice line");
+    for sub_line in &line.cac_sub_invoice_line {
+        section.child(ubl_sub_line(sub_line));
+    }
+    section
+}
+
+fn ubl_sub_line(line: &SubInvoiceLine) -> Section {
+    let mut section = ubl_line_section!(line, "Sub invoice line");
+    for sub_line in &line.cac_sub_invoice_line {
+        section.child(ubl_sub_sub_line(sub_line));
+    }
+    section
+}
+
+fn ubl_sub_sub_line(line: &SubSubInvoiceLine) -> Section {
+    let mut section = ubl_line_section!(line, "Sub invoice line");
+    for sub_line in &line.cac_sub_invoice_line {
+        section.child(ubl_sub_sub_sub_line(sub_line));
+    }
+    section
+}
+
+fn ubl_sub_sub_sub_line(line: &SubSubSubInvoiceLine) -> Section {
+    ubl_line_section!(line, "Sub invoice line")
+}
+
+fn cii_id(id: Option<&IdType>) -> Option<String> {
+    id.and_then(|id| with_scheme(id.id.as_ref(), id.scheme_id.as_ref()))
+}
+
+fn cii_date(date: Option<&DateTimeString>) -> Option<String> {
+    date.map(|date| {
+        let text = date.text.trim();
+        if date.format == "102" && text.len() == 8 {
+            format!("{}-{}-{}", &text[0..4], &text[4..6], &text[6..8])
+        } else {
+            text.to_string()
+        }
+    })
+}
+
+fn cii_amount(amount: Option<&String>, currency: Option<&String>) -> Option<String> {
+    amount.map(|amount| match currency {
+        Some(currency) => format!("{} {}", amount, currency),
+        None => amount.clone(),
+    })
+}
+
+fn cii_sections(invoice: &CrossIndustryInvoice) -> Vec<Section> {
+    let mut sections = Vec::new();
+    let document = invoice.rsm_exchanged_document.as_ref();
+    let transaction = invoice.rsm_supply_chain_trade_transaction.as_ref();
+    let agreement = transaction.and_then(|t| t.ram_applicable_header_trade_agreement.as_ref());
+    let delivery = transaction.and_then(|t| t.ram_applicable_header_trade_delivery.as_ref());
+    let settlement = transaction.and_then(|t| t.ram_applicable_header_trade_settlement.as_ref());
+    let currency = settlement.and_then(|s| s.ram_invoice_currency_code.as_ref());
+
+    let mut header = Section::new("Invoice");
+    header.field(
+        "BT-1",
+        "Invoice number",
+        document
+            .and_then(|d| d.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+    );
+    header.field(
+        "BT-2",
+        "Invoice issue date",
+        cii_date(
+            document
+                .and_then(|d| d.ram_issue_date_time.as_ref())
+                .and_then(|d| d.udt_date_time_string.as_ref()),
+        ),
+    );
+    header.field(
+        "BT-3",
+        "Invoice type code",
+        document.and_then(|d| d.ram_type_code.clone()),
+    );
+    header.field("BT-5", "Invoice currency code", currency.cloned());
+    header.field(
+        "BT-6",
+        "VAT accounting currency code",
+        settlement.and_then(|s| s.ram_tax_currency_code.clone()),
+    );
+    header.field(
+        "BT-8",
+        "Value added tax point date code",
+        settlement
+            .and_then(|s| s.ram_applicable_trade_tax.first())
+            .and_then(|tax| tax.ram_due_date_type_code.clone()),
+    );
+    header.field(
+        "BT-9",
+        "Payment due date",
+        cii_date(
+            settlement
+                .and_then(|s| s.ram_specified_trade_payment_terms.as_ref())
+                .and_then(|t| t.ram_due_date_date_time.as_ref())
+                .and_then(|d| d.udt_date_time_string.as_ref()),
+        ),
+    );
+    header.field(
+        "BT-10",
+        "Buyer reference",
+        agreement.and_then(|a| a.ram_buyer_reference.clone()),
+    );
+    header.field(
+        "BT-11",
+        "Project reference",
+        agreement
+            .and_then(|a| a.ram_specified_procuring_project.as_ref())
+            .and_then(|project| join([cii_id(project.ram_id.as_ref()), project.ram_name.clone()])),
+    );
+    header.field(
+        "BT-12",
+        "Contract reference",
+        agreement
+            .and_then(|a| a.ram_contract_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    header.field(
+        "BT-13",
+        "Purchase order reference",
+        agreement
+            .and_then(|a| a.ram_buyer_order_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    header.field(
+        "BT-14",
+        "Sales order reference",
+        agreement
+            .and_then(|a| a.ram_seller_order_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    header.field(
+        "BT-15",
+        "Receiving advice reference",
+        delivery
+            .and_then(|d| d.ram_receiving_advice_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    header.field(
+        "BT-16",
+        "Despatch advice reference",
+        delivery
+            .and_then(|d| d.ram_despatch_advice_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    header.field(
+        "BT-19",
+        "Buyer accounting reference",
+        settlement
+            .and_then(|s| s.ram_receivable_specified_trade_accounting_account.as_ref())
+            .and_then(|account| cii_id(account.ram_id.as_ref())),
+    );
+    header.field(
+        "BT-20",
+        "Payment terms",
+        settlement
+            .and_then(|s| s.ram_specified_trade_payment_terms.as_ref())
+            .and_then(|t| t.ram_description.clone()),
+    );
+    sections.push(header);
+
+    let mut notes = Section::new("BG-1 Invoice note");
+    for note in document
+        .map(|d| d.ram_included_note.as_slice())
+        .unwrap_or_default()
+    {
+        notes.field(
+            "BT-21",
+            "Invoice note subject code",
+            note.ram_subject_code.clone(),
+        );
+        notes.field("BT-22", "Invoice note", note.ram_content.clone());
+    }
+    sections.push(notes);
+
+    let mut process = Section::new("BG-2 Process control");
+    let context = invoice.rsm_exchanged_document_context.as_ref();
+    process.field(
+        "BT-23",
+        "Business process type",
+        context
+            .and_then(|c| {
+                c.ram_business_process_specified_document_context_parameter
+                    .as_ref()
+            })
+            .and_then(|p| p.ram_id.as_ref())
+            .and_then(|id| id.id.clone()),
+    );
+    process.field(
+        "BT-24",
+        "Specification identifier",
+        context
+            .and_then(|c| {
+                c.ram_guideline_specified_document_context_parameter
+                    .as_ref()
+            })
+            .and_then(|p| p.id.as_ref())
+            .and_then(|id| id.id.clone()),
+    );
+    sections.push(process);
+
+    if let Some(reference) = settlement.and_then(|s| s.ram_invoice_referenced_document.as_ref()) {
+        let mut preceding = Section::new("BG-3 Preceding invoice reference");
+        preceding.field(
+            "BT-25",
+            "Preceding invoice reference",
+            reference.ram_issuer_assigned_id.clone(),
+        );
+        preceding.field(
+            "BT-26",
+            "Preceding invoice issue date",
+            cii_date(
+                reference
+                    .ram_formatted_issue_date_time
+                    .as_ref()
+                    .and_then(|d| d.qdt_date_time_string.as_ref()),
+            ),
+        );
+        sections.push(preceding);
+    }
+
+    if let Some(party) = agreement.and_then(|a| a.ram_seller_trade_party.as_ref()) {
+        sections.push(cii_party(
+            party,
+            "BG-4 Seller",
+            [
+                "BT-27", "BT-28", "BT-29", "BT-30", "BT-31", "BT-32", "BT-33", "BT-34",
+            ],
+            ("BG-5 Seller postal address", ADDRESS_TERMS_SELLER),
+            ("BG-6 Seller contact", ["BT-41", "BT-42", "BT-43"]),
+        ));
+    }
+    if let Some(party) = agreement.and_then(|a| a.ram_buyer_trade_party.as_ref()) {
+        sections.push(cii_party(
+            party,
+            "BG-7 Buyer",
+            ["BT-44", "BT-45", "BT-46", "BT-47", "BT-48", "", "", "BT-49"],
+            ("BG-8 Buyer postal address", ADDRESS_TERMS_BUYER),
+            ("BG-9 Buyer contact", ["BT-56", "BT-57", "BT-58"]),
+        ));
+    }
+    if let Some(party) = settlement.and_then(|s| s.ram_payee_trade_party.as_ref()) {
+        let mut payee = Section::new("BG-10 Payee");
+        payee.field("BT-59", "Payee name", party.ram_name.clone());
+        payee.field(
+            "BT-60",
+            "Payee identifier",
+            join(party.ram_id.iter().cloned().map(Some)),
+        );
+        payee.field(
+            "BT-61",
+            "Payee legal registration identifier",
+            party
+                .ram_specified_legal_organization
+                .as_ref()
+                .and_then(|organization| organization.ram_id.as_ref())
+                .and_then(|id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref())),
+        );
+        sections.push(payee);
+    }
+    if let Some(party) =
+        agreement.and_then(|a| a.ram_seller_tax_representative_trade_party.as_ref())
+    {
+        let mut representative = Section::new("BG-11 Seller tax representative party");
+        representative.field(
+            "BT-62",
+            "Seller tax representative name",
+            party.ram_name.clone(),
+        );
+        representative.field(
+            "BT-63",
+            "Seller tax representative VAT identifier",
+            party
+                .ram_specified_tax_registration
+                .iter()
+                .find_map(|registration| registration.ram_id.as_ref())
+                .and_then(|id| id.text.clone()),
+        );
+        representative.child(cii_address(
+            party.ram_postal_trade_address.as_ref(),
+            "BG-12 Seller tax representative postal address",
+            ADDRESS_TERMS_TAX_REPRESENTATIVE,
+        ));
+        sections.push(representative);
+    }
+
+    if let Some(delivery) = delivery {
+        let mut section = Section::new("BG-13 Delivery information");
+        let ship_to = delivery.ram_ship_to_trade_party.as_ref();
+        section.field(
+            "BT-70",
+            "Deliver to party name",
+            ship_to.and_then(|party| party.ram_name.clone()),
+        );
+        section.field(
+            "BT-71",
+            "Deliver to location identifier",
+            ship_to.and_then(|party| {
+                join(
+                    party
+                        .ram_id
+                        .iter()
+                        .cloned()
+                        .map(Some)
+                        .chain(std::iter::once(party.ram_global_id.as_ref().and_then(
+                            |id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref()),
+                        ))),
+                )
+            }),
+        );
+        section.field(
+            "BT-72",
+            "Actual delivery date",
+            cii_date(
+                delivery
+                    .ram_actual_delivery_supply_chain_event
+                    .as_ref()
+                    .and_then(|event| event.ram_occurrence_date_time.as_ref())
+                    .and_then(|date| date.udt_date_time_string.as_ref()),
+            ),
+        );
+        section.child(cii_address(
+            ship_to.and_then(|party| party.ram_postal_trade_address.as_ref()),
+            "BG-15 Deliver to address",
+            ADDRESS_TERMS_DELIVERY,
+        ));
+        sections.push(section);
+    }
+    if let Some(period) = settlement.and_then(|s| s.ram_billing_specified_period.as_ref()) {
+        let mut section = Section::new("BG-14 Invoicing period");
+        section.field(
+            "BT-73",
+            "Invoicing period start date",
+            cii_date(
+                period
+                    .ram_start_date_time
+                    .as_ref()
+                    .and_then(|d| d.udt_date_time_string.as_ref()),
+            ),
+        );
+        section.field(
+            "BT-74",
+            "Invoicing period end date",
+            cii_date(
+                period
+                    .ram_end_date_time
+                    .as_ref()
+                    .and_then(|d| d.udt_date_time_string.as_ref()),
+            ),
+        );
+        sections.push(section);
+    }
+
+    if let Some(settlement) = settlement {
+        for payment_means in &settlement.ram_specified_trade_settlement_payment_means {
+            let mut section = Section::new("BG-16 Payment instructions");
+            section.field(
+                "BT-81",
+                "Payment means type code",
+                payment_means.ram_type_code.clone(),
+            );
+            section.field(
+                "BT-82",
+                "Payment means text",
+                payment_means.ram_information.clone(),
+            );
+            section.field(
+                "BT-83",
+                "Remittance information",
+                settlement.ram_payment_reference.clone(),
+            );
+            if let Some(account) = &payment_means.ram_payee_party_creditor_financial_account {
+                let mut transfer = Section::new("BG-17 Credit transfer");
+                transfer.field(
+                    "BT-84",
+                    "Payment account identifier",
+                    account
+                        .ram_ibanid
+                        .clone()
+                        .or(account.ram_proprietary_id.clone()),
+                );
+                transfer.field(
+                    "BT-85",
+                    "Payment account name",
+                    account.ram_account_name.clone(),
+                );
+                transfer.field(
+                    "BT-86",
+                    "Payment service provider identifier",
+                    payment_means
+                        .ram_payee_specified_creditor_financial_institution
+                        .as_ref()
+                        .and_then(|institution| institution.ram_bicid.clone()),
+                );
+                section.child(transfer);
+            }
+            if let Some(card) = &payment_means.ram_applicable_trade_settlement_financial_card {
+                let mut card_section = Section::new("BG-18 Payment card information");
+                card_section.field(
+                    "BT-87",
+                    "Payment card primary account number",
+                    card.ram_id.clone(),
+                );
+                card_section.field(
+                    "BT-88",
+                    "Payment card holder name",
+                    card.ram_cardholder_name.clone(),
+                );
+                section.child(card_section);
+            }
+            if let Some(account) = &payment_means.ram_payer_party_debitor_financial_account {
+                let mut debit = Section::new("BG-19 Direct debit");
+                debit.field(
+                    "BT-89",
+                    "Mandate reference identifier",
+                    settlement
+                        .ram_specified_trade_payment_terms
+                        .as_ref()
+                        .and_then(|terms| terms.ram_direct_debit_mandate_id.clone()),
+                );
+                debit.field(
+                    "BT-90",
+                    "Bank assigned creditor identifier",
+                    settlement.ram_creditor_reference_id.clone(),
+                );
+                debit.field(
+                    "BT-91",
+                    "Debited account identifier",
+                    account.ram_ibanid.clone(),
+                );
+                section.child(debit);
+            }
+            sections.push(section);
+        }
+
+        for allowance_charge in &settlement.ram_specified_trade_allowance_charge {
+            let charge = allowance_charge
+                .ram_charge_indicator
+                .as_ref()
+                .and_then(|indicator| indicator.udt_indicator.as_deref())
+                == Some("true");
+            let (title, terms) = if charge {
+                (
+                    "BG-21 Document level charges",
+                    [
+                        "BT-99", "BT-100", "BT-101", "BT-102", "BT-103", "BT-104", "BT-105",
+                    ],
+                )
+            } else {
+                (
+                    "BG-20 Document level allowances",
+                    [
+                        "BT-92", "BT-93", "BT-94", "BT-95", "BT-96", "BT-97", "BT-98",
+                    ],
+                )
+            };
+            let mut section = Section::new(title);
+            section.field(
+                terms[0],
+                "Amount",
+                cii_amount(allowance_charge.ram_actual_amount.as_ref(), currency),
+            );
+            section.field(
+                terms[1],
+                "Base amount",
+                cii_amount(allowance_charge.ram_basis_amount.as_ref(), currency),
+            );
+            section.field(
+                terms[2],
+                "Percentage",
+                allowance_charge.ram_calculation_percent.clone(),
+            );
+            section.field(
+                terms[3],
+                "VAT category code",
+                allowance_charge
+                    .ram_category_trade_tax
+                    .as_ref()
+                    .and_then(|tax| tax.ram_category_code.clone()),
+            );
+            section.field(
+                terms[4],
+                "VAT rate",
+                allowance_charge
+                    .ram_category_trade_tax
+                    .as_ref()
+                    .and_then(|tax| tax.ram_rate_applicable_percent.clone()),
+            );
+            section.field(terms[5], "Reason", allowance_charge.ram_reason.clone());
+            section.field(
+                terms[6],
+                "Reason code",
+                allowance_charge.ram_reason_code.clone(),
+            );
+            sections.push(section);
+        }
+
+        if let Some(summation) =
+            &settlement.ram_specified_trade_settlement_header_monetary_summation
+        {
+            let mut section = Section::new("BG-22 Document totals");
+            section.field(
+                "BT-106",
+                "Sum of Invoice line net amount",
+                cii_amount(summation.ram_line_total_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-107",
+                "Sum of allowances on document level",
+                cii_amount(summation.ram_allowance_total_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-108",
+                "Sum of charges on document level",
+                cii_amount(summation.ram_charge_total_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-109",
+                "Invoice total amount without VAT",
+                cii_amount(summation.ram_tax_basis_total_amount.as_ref(), currency),
+            );
+            for tax_total in &summation.ram_tax_total_amount {
+                let accounting_currency =
+                    tax_total.currency_id.as_ref() != currency && tax_total.currency_id.is_some();
+                section.field(
+                    if accounting_currency {
+                        "BT-111"
+                    } else {
+                        "BT-110"
+                    },
+                    if accounting_currency {
+                        "Invoice total VAT amount in accounting currency"
+                    } else {
+                        "Invoice total VAT amount"
+                    },
+                    cii_amount(tax_total.text.as_ref(), tax_total.currency_id.as_ref()),
+                );
+            }
+            section.field(
+                "BT-112",
+                "Invoice total amount with VAT",
+                cii_amount(summation.ram_grand_total_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-113",
+                "Paid amount",
+                cii_amount(summation.ram_total_prepaid_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-114",
+                "Rounding amount",
+                cii_amount(summation.ram_rounding_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-115",
+                "Amount due for payment",
+                cii_amount(summation.ram_due_payable_amount.as_ref(), currency),
+            );
+            sections.push(section);
+        }
+
+        for tax in &settlement.ram_applicable_trade_tax {
+            let mut section = Section::new("BG-23 VAT breakdown");
+            section.field(
+                "BT-116",
+                "VAT category taxable amount",
+                cii_amount(tax.ram_basis_amount.as_ref(), currency),
+            );
+            section.field(
+                "BT-117",
+                "VAT category tax amount",
+                cii_amount(tax.ram_calculated_amount.as_ref(), currency),
+            );
+            section.field("BT-118", "VAT category code", tax.ram_category_code.clone());
+            section.field(
+                "BT-119",
+                "VAT category rate",
+                tax.ram_rate_applicable_percent.clone(),
+            );
+            section.field(
+                "BT-120",
+                "VAT exemption reason text",
+                tax.ram_exemption_reason.clone(),
+            );
+            section.field(
+                "BT-121",
+                "VAT exemption reason code",
+                tax.ram_exemption_reason_code.clone(),
+            );
+            sections.push(section);
+        }
+    }
+
+    for document in agreement
+        .map(|a| a.ram_additional_referenced_document.as_slice())
+        .unwrap_or_default()
+    {
+        let invoiced_object = document.ram_type_code.as_deref() == Some("130");
+        let mut section = Section::new(if invoiced_object {
+            "Invoiced object identifier"
+        } else {
+            "BG-24 Additional supporting documents"
+        });
+        section.field(
+            if invoiced_object { "BT-18" } else { "BT-122" },
+            if invoiced_object {
+                "Invoiced object identifier"
+            } else {
+                "Supporting document reference"
+            },
+            cii_id(document.ram_issuer_assigned_id.as_ref()),
+        );
+        section.field(
+            "BT-123",
+            "Supporting document description",
+            document.ram_name.clone(),
+        );
+        section.field(
+            "BT-124",
+            "External document location",
+            document.ram_uriid.clone(),
+        );
+        if let Some(object) = &document.ram_attachment_binary_object {
+            section.field(
+                "BT-125",
+                "Attached document",
+                join([object.filename.clone(), object.mime_code.clone()]),
+            );
+            if let Some(href) = data_uri(object.mime_code.as_deref(), object.text.as_deref()) {
+                section.links.push((
+                    object
+                        .filename
+                        .clone()
+                        .unwrap_or_else(|| "attachment".to_string()),
+                    href,
+                ));
+            }
+        }
+        sections.push(section);
+    }
+
+    for line in transaction
+        .map(|t| t.ram_included_supply_chain_trade_line_item.as_slice())
+        .unwrap_or_default()
+    {
+        sections.push(cii_line(line, currency));
+    }
+    sections
+}
+
+fn cii_party(
+    party: &TradeParty,
+    title: &str,
+    terms: [&'static str; 8],
+    address: (&str, [&'static str; 7]),
+    contact: (&str, [&'static str; 3]),
+) -> Section {
+    let mut section = Section::new(title);
+    section.field(terms[0], "Name", party.ram_name.clone());
+    let organization = party.ram_specified_legal_organization.as_ref();
+    section.field(
+        terms[1],
+        "Trading name",
+        organization.and_then(|organization| organization.ram_trading_business_name.clone()),
+    );
+    section.field(
+        terms[2],
+        "Identifier",
+        join(
+            party
+                .ram_id
+                .iter()
+                .cloned()
+                .map(Some)
+                .chain(std::iter::once(party.ram_global_id.as_ref().and_then(
+                    |id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref()),
+                ))),
+        ),
+    );
+    section.field(
+        terms[3],
+        "Legal registration identifier",
+        organization
+            .and_then(|organization| organization.ram_id.as_ref())
+            .and_then(|id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref())),
+    );
+    let registration = |scheme: &str| {
+        party
+            .ram_specified_tax_registration
+            .iter()
+            .filter_map(|registration| registration.ram_id.as_ref())
+            .find(|id| id.scheme_id.as_deref() == Some(scheme))
+            .and_then(|id| id.text.clone())
+    };
+    section.field(terms[4], "VAT identifier", registration("VA"));
+    if !terms[5].is_empty() {
+        section.field(terms[5], "Tax registration identifier", registration("FC"));
+    }
+    if !terms[6].is_empty() {
+        section.field(
+            terms[6],
+            "Additional legal information",
+            party.ram_description.clone(),
+        );
+    }
+    section.field(
+        terms[7],
+        "Electronic address",
+        party
+            .ram_uriuniversal_communication
+            .as_ref()
+            .and_then(|communication| communication.ram_uriid.as_ref())
+            .and_then(|id| with_scheme(id.text.as_ref(), id.scheme_id.as_ref())),
+    );
+    section.child(cii_address(
+        party.ram_postal_trade_address.as_ref(),
+        address.0,
+        address.1,
+    ));
+    section.child(cii_contact(
+        party.ram_defined_trade_contact.as_ref(),
+        contact.0,
+        contact.1,
+    ));
+    section
+}
+
+fn cii_address(
+    address: Option<&PostalTradeAddress>,
+    title: &str,
+    terms: [&'static str; 7],
+) -> Section {
+    let mut section = Section::new(title);
+    if let Some(address) = address {
+        section.field(terms[0], "Address line 1", address.ram_line_one.clone());
+        section.field(terms[1], "Address line 2", address.ram_line_two.clone());
+        section.field(terms[2], "Address line 3", address.ram_line_three.clone());
+        section.field(terms[3], "City", address.ram_city_name.clone());
+        section.field(terms[4], "Post code", address.ram_postcode_code.clone());
+        section.field(
+            terms[5],
+            "Country subdivision",
+            address.ram_country_sub_division_name.clone(),
+        );
+        section.field(terms[6], "Country code", address.ram_country_id.clone());
+    }
+    section
+}
+
+fn cii_contact(
+    contact: Option<&DefinedTradeContact>,
+    title: &str,
+    terms: [&'static str; 3],
+) -> Section {
+    let mut section = Section::new(title);
+    if let Some(contact) = contact {
+        section.field(
+            terms[0],
+            "Contact point",
+            join([
+                contact.ram_person_name.clone(),
+                contact.ram_department_name.clone(),
+            ]),
+        );
+        section.field(
+            terms[1],
+            "Contact telephone number",
+            contact
+                .ram_telephone_universal_communication
+                .as_ref()
+                .and_then(|telephone| telephone.ram_complete_number.clone()),
+        );
+        section.field(
+            terms[2],
+            "Contact email address",
+            contact
+                .ram_email_uriuniversal_communication
+                .as_ref()
+                .and_then(|email| email.ram_uriid.clone()),
+        );
+    }
+    section
+}
+
+fn cii_line(line: &RamIncludedSupplyChainTradeLineItem, currency: Option<&String>) -> Section {
+    let line_document = line.ram_associated_document_line_document.as_ref();
+    let line_id = line_document.and_then(|d| d.ram_line_id.clone());
+    let mut section = Section::new(format!(
+        "BG-25 Invoice line {}",
+        line_id.clone().unwrap_or_default()
+    ));
+    section.field("BT-126", "Invoice line identifier", line_id);
+    section.field(
+        "BT-127",
+        "Invoice line note",
+        line_document
+            .and_then(|d| d.ram_included_note.as_ref())
+            .and_then(|note| note.ram_content.clone()),
+    );
+    let settlement = line.ram_specified_line_trade_settlement.as_ref();
+    section.field(
+        "BT-128",
+        "Invoice line object identifier",
+        settlement
+            .and_then(|s| s.ram_additional_referenced_document.as_ref())
+            .and_then(|d| d.ram_issuer_assigned_id.clone()),
+    );
+    let quantity = line
+        .ram_specified_line_trade_delivery
+        .as_ref()
+        .and_then(|d| d.ram_billed_quantity.as_ref());
+    section.field(
+        "BT-129",
+        "Invoiced quantity",
+        quantity.and_then(|q| q.text.clone()),
+    );
+    section.field(
+        "BT-130",
+        "Invoiced quantity unit of measure code",
+        quantity.and_then(|q| q.unit_code.clone()),
+    );
+    section.field(
+        "BT-131",
+        "Invoice line net amount",
+        cii_amount(
+            settlement
+                .and_then(|s| {
+                    s.ram_specified_trade_settlement_line_monetary_summation
+                        .as_ref()
+                })
+                .and_then(|s| s.ram_line_total_amount.as_ref()),
+            currency,
+        ),
+    );
+    let agreement = line.ram_specified_line_trade_agreement.as_ref();
+    section.field(
+        "BT-132",
+        "Referenced purchase order line reference",
+        agreement
+            .and_then(|a| a.ram_buyer_order_referenced_document.as_ref())
+            .and_then(|d| d.ram_line_id.clone()),
+    );
+    section.field(
+        "BT-133",
+        "Invoice line Buyer accounting reference",
+        settlement
+            .and_then(|s| s.ram_receivable_specified_trade_accounting_account.as_ref())
+            .and_then(|account| cii_id(account.ram_id.as_ref())),
+    );
+
+    if let Some(period) = settlement.and_then(|s| s.ram_billing_specified_period.as_ref()) {
+        let mut period_section = Section::new("BG-26 Invoice line period");
+        period_section.field(
+            "BT-134",
+            "Invoice line period start date",
+            cii_date(
+                period
+                    .ram_start_date_time
+                    .as_ref()
+                    .and_then(|d| d.udt_date_time_string.as_ref()),
+            ),
+        );
+        period_section.field(
+            "BT-135",
+            "Invoice line period end date",
+            cii_date(
+                period
+                    .ram_end_date_time
+                    .as_ref()
+                    .and_then(|d| d.udt_date_time_string.as_ref()),
+            ),
+        );
+        section.child(period_section);
+    }
+    for allowance_charge in settlement
+        .map(|s| s.ram_specified_trade_allowance_charge.as_slice())
+        .unwrap_or_default()
+    {
+        let charge = allowance_charge
+            .ram_charge_indicator
+            .as_ref()
+            .and_then(|indicator| indicator.udt_indicator.as_deref())
+            == Some("true");
+        let (title, terms) = if charge {
+            (
+                "BG-28 Invoice line charges",
+                ["BT-141", "BT-142", "BT-143", "BT-144", "BT-145"],
+            )
+        } else {
+            (
+                "BG-27 Invoice line allowances",
+                ["BT-136", "BT-137", "BT-138", "BT-139", "BT-140"],
+            )
+        };
+        let mut allowance_section = Section::new(title);
+        allowance_section.field(
+            terms[0],
+            "Amount",
+            cii_amount(allowance_charge.ram_actual_amount.as_ref(), currency),
+        );
+        allowance_section.field(
+            terms[1],
+            "Base amount",
+            cii_amount(allowance_charge.ram_basis_amount.as_ref(), currency),
+        );
+        allowance_section.field(
+            terms[2],
+            "Percentage",
+            allowance_charge.ram_calculation_percent.clone(),
+        );
+        allowance_section.field(terms[3], "Reason", allowance_charge.ram_reason.clone());
+        allowance_section.field(
+            terms[4],
+            "Reason code",
+            allowance_charge.ram_reason_code.clone(),
+        );
+        section.child(allowance_section);
+    }
+
+    if let Some(agreement) = agreement {
+        let mut price = Section::new("BG-29 Price details");
+        let net_price = agreement.ram_net_price_product_trade_price.as_ref();
+        let gross_price = agreement.ram_gross_price_product_trade_price.as_ref();
+        price.field(
+            "BT-146",
+            "Item net price",
+            cii_amount(
+                net_price.and_then(|p| p.ram_charge_amount.as_ref()),
+                currency,
+            ),
+        );
+        price.field(
+            "BT-147",
+            "Item price discount",
+            cii_amount(
+                gross_price
+                    .and_then(|p| p.ram_applied_trade_allowance_charge.as_ref())
+                    .and_then(|a| a.ram_actual_amount.as_ref()),
+                currency,
+            ),
+        );
+        price.field(
+            "BT-148",
+            "Item gross price",
+            cii_amount(
+                gross_price.and_then(|p| p.ram_charge_amount.as_ref()),
+                currency,
+            ),
+        );
+        let basis_quantity = net_price.and_then(|p| p.ram_basis_quantity.as_ref());
+        price.field(
+            "BT-149",
+            "Item price base quantity",
+            basis_quantity.and_then(|q| q.text.clone()),
+        );
+        price.field(
+            "BT-150",
+            "Item price base quantity unit of measure code",
+            basis_quantity.and_then(|q| q.unit_code.clone()),
+        );
+        section.child(price);
+    }
+
+    if let Some(tax) = settlement.and_then(|s| s.ram_applicable_trade_tax.as_ref()) {
+        let mut vat = Section::new("BG-30 Line VAT information");
+        vat.field(
+            "BT-151",
+            "Invoiced item VAT category code",
+            tax.ram_category_code.clone(),
+        );
+        vat.field(
+            "BT-152",
+            "Invoiced item VAT rate",
+            tax.ram_rate_applicable_percent.clone(),
+        );
+        section.child(vat);
+    }
+
+    if let Some(product) = &line.ram_specified_trade_product {
+        let mut item = Section::new("BG-31 Item information");
+        item.field("BT-153", "Item name", product.ram_name.clone());
+        item.field(
+            "BT-154",
+            "Item description",
+            product.ram_description.clone(),
+        );
+        item.field(
+            "BT-155",
+            "Item Seller's identifier",
+            cii_id(product.ram_seller_assigned_id.as_ref()),
+        );
+        item.field(
+            "BT-156",
+            "Item Buyer's identifier",
+            cii_id(product.ram_buyer_assigned_id.as_ref()),
+        );
+        item.field(
+            "BT-157",
+            "Item standard identifier",
+            cii_id(product.ram_global_id.as_ref()),
+        );
+        item.field(
+            "BT-158",
+            "Item classification identifier",
+            join(
+                product
+                    .ram_designated_product_classification
+                    .iter()
+                    .map(|classification| {
+                        classification
+                            .ram_class_code
+                            .as_ref()
+                            .and_then(|code| with_scheme(code.text.as_ref(), code.list_id.as_ref()))
+                    }),
+            ),
+        );
+        item.field(
+            "BT-159",
+            "Item country of origin",
+            product
+                .ram_origin_trade_country
+                .as_ref()
+                .and_then(|country| cii_id(country.ram_id.as_ref())),
+        );
+        for characteristic in &product.ram_applicable_product_characteristic {
+            let mut attribute = Section::new("BG-32 Item attributes");
+            attribute.field(
+                "BT-160",
+                "Item attribute name",
+                characteristic.ram_description.clone(),
+            );
+            attribute.field(
+                "BT-161",
+                "Item attribute value",
+                characteristic.ram_value.clone(),
+            );
+            item.child(attribute);
+        }
+        section.child(item);
+    }
+    section
+}
@@ -0,0 +1,206 @@
+use crate::detect::format_detector::qualified_name;
+use crate::rules::rule_catalogue::catalogue_entry;
+use crate::{InvoiceSyntax, ValidationError};
+use einvoice_deps_yaserde::__xml::common::{Position, TextPosition};
+use einvoice_deps_yaserde::__xml::reader::{EventReader, XmlEvent};
+use std::collections::HashMap;
+
+/// The position of an element in the XML source of an invoice
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct SourceLocation {
+    /// An XPath selecting the element, repeated siblings are numbered, e.g. `/ubl:Invoice/cac:InvoiceLine[2]`
+    pub path: String,
+    /// The line of the start tag, counting from 1
+    pub line: u64,
+    /// The column of the start tag, counting from 1
+    pub column: u64,
+}
+
+/// An element of an XML document with the qualified names of its ancestors and itself
+pub(crate) struct SourceElement {
+    pub(crate) names: Vec<String>,
+    pub(crate) location: SourceLocation,
+}
+
+/// Locate every element of an XML document in document order
+///
+/// A document that is not well-formed yields the elements started before the error.
+pub(crate) fn source_elements(xml: &str) -> Vec<SourceElement> {
+    let mut reader = EventReader::new(xml.as_bytes());
+    // The parent, name, sibling number and start position of every element
+    let mut elements: Vec<(Option<usize>, String, u32, u64, u64)> = Vec::new();
+    let mut siblings: HashMap<(Option<usize>, String), u32> = HashMap::new();
+    let mut stack = Vec::new();
+    loop {
+        match reader.next() {
+            Ok(XmlEvent::StartElement { name, .. }) => {
+                let position = reader.position();
+                let name = qualified_name(&name);
+                let parent = stack.last().copied();
+                let number = siblings.entry((parent, name.clone())).or_default();
+                *number += 1;
+                stack.push(elements.len());
+                elements.push((parent, name, *number, position.row, position.column));
+            }
+            Ok(XmlEvent::EndElement { .. }) => {
+                stack.pop();
+            }
+            Ok(XmlEvent::EndDocument) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+
+    let mut located: Vec<SourceElement> = Vec::with_capacity(elements.len());
+    for (parent, name, number, row, column) in &elements {
+        let (mut names, mut path) = match parent {
+            Some(parent) => (
+                located[*parent].names.clone(),
+                located[*parent].location.path.clone(),
+            ),
+            None => (Vec::new(), String::new()),
+        };
+        path.push('/');
+        path.push_str(name);
+        if siblings[&(*parent, name.clone())] > 1 {
+            path.push_str(&format!("[{}]", number));
+        }
+        names.push(name.clone());
+        located.push(SourceElement {
+            names,
+            location: SourceLocation {
+                path,
+                line: row + 1,
+                column: column + 1,
+            },
+        });
+    }
+    located
+}
+
+/// Split a Schematron context into its alternatives, ignoring `|` inside predicates
+pub(crate) fn context_alternatives(context: &str) -> Vec<&str> {
+    let mut alternatives = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (index, c) in context.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            '|' if depth == 0 => {
+                alternatives.push(context[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    alternatives.push(context[start..].trim());
+    alternatives
+}
+
+/// Whether an element may be a node the context of a rule applies to
+///
+/// Predicates are not evaluated, so a context matches every element its steps match.
+fn matches_context(names: &[String], context: &str) -> bool {
+    context_alternatives(context).iter().any(|alternative| {
+        let mut steps = String::new();
+        let mut depth = 0;
+        for c in alternative.chars() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth -= 1,
+                c if depth == 0 => steps.push(c),
+                _ => {}
+            }
+        }
+        let anchored = steps.starts_with('/') && !steps.starts_with("//");
+        let steps = steps
+            .split('/')
+            .map(str::trim)
+            .filter(|step| !step.is_empty())
+            .collect::<Vec<_>>();
+        if steps.is_empty() || names.len() < steps.len() || (anchored && names.len() != steps.len())
+        {
+            return false;
+        }
+        names[names.len() - steps.len()..]
+            .iter()
+            .zip(&steps)
+            .all(|(name, step)| match step.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => name == step,
+            })
+    })
+}
+
+/// Point the business rule violations of an invoice to the element they were found at
+/// # Arguments
+/// * `xml` - The XML content of the invoice
+/// * `syntax` - The syntax of the invoice
+/// * `violations` - The violations, those already located are left as they are
+///
+/// The rules check the parsed invoice, so a violation is located by the element its rule reports,
+/// or else by the Schematron context of its rule.
+/// Violations without an element whose context matches several elements, e.g. one of several invoice lines,
+/// and violations of custom rules without an element are not located.
+pub(crate) fn locate_violations(
+    xml: &str,
+    syntax: InvoiceSyntax,
+    violations: &mut [ValidationError],
+) {
+    let mut elements = None;
+    for violation in violations.iter_mut() {
+        let violation = match violation {
+            ValidationError::Fatal(violation) | ValidationError::Warning(violation)
+                if violation.location.is_none() =>
+            {
+                violation
+            }
+            _ => continue,
+        };
+        let elements = elements.get_or_insert_with(|| source_elements(xml));
+        let located = match &violation.element {
+            Some(path) => elements
+                .iter()
+                .find(|element| is_element(&element.location.path, path)),
+            None => {
+                let Some(entry) = catalogue_entry(syntax, &violation.rule_id) else {
+                    continue;
+                };
+                let mut matching = elements
+                    .iter()
+                    .filter(|element| matches_context(&element.names, entry.context));
+                match (matching.next(), matching.next()) {
+                    (Some(element), None) => Some(element),
+                    _ => None,
+                }
+            }
+        };
+        violation.location = located.map(|element| element.location.clone());
+    }
+}
+
+/// Whether the path of a source element selects the element a rule reported
+///
+/// The reported path starts below the root element and may number a sibling `[1]` even if it is the only one.
+fn is_element(location_path: &str, element: &str) -> bool {
+    let below_root = location_path
+        .trim_start_matches('/')
+        .split_once('/')
+        .map_or("", |(_, path)| path);
+    below_root.replace("[1]", "") == element.trim_start_matches('/').replace("[1]", "")
+}
+
+/// The position of the first well-formedness error of an XML document
+///
+/// The invoice deserializer reports errors without a position, so a failed parse is read again to locate them.
+/// Errors in the content of a well-formed document, e.g. an unknown root element, have no position.
+pub(crate) fn syntax_error_position(xml: &str) -> Option<TextPosition> {
+    let mut reader = EventReader::new(xml.as_bytes());
+    loop {
+        match reader.next() {
+            Ok(XmlEvent::EndDocument) => return None,
+            Ok(_) => {}
+            Err(e) => return Some(e.position()),
+        }
+    }
+}
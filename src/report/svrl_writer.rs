@@ -0,0 +1,114 @@
+use crate::report::source_location::context_alternatives;
+use crate::rules::rule_catalogue::catalogue_entry;
+use crate::{BusinessRuleViolation, InvoiceSyntax, ValidationError, ValidationReport};
+use einvoice_deps_yaserde::__xml::writer::{EmitterConfig, EventWriter, Result, XmlEvent};
+
+const SVRL_NAMESPACE: &str = "http://purl.oclc.org/dsdl/svrl";
+
+/// Serialise a validation report as Schematron Validation Report Language (SVRL)
+/// # Arguments
+/// * `report` - The validation report
+/// # Returns
+/// * `String` - The SVRL document with a `svrl:failed-assert` for every violation
+///
+/// Fatal, metadata and PDF violations are flagged `fatal`, warnings are flagged `warning`.
+/// The `test` of a business rule violation is the Schematron test documented for the rule.
+/// The `location` is the path of the offending element if the violation was located in the XML source,
+/// otherwise the Schematron context of the rule, which names every node the rule applies to.
+/// Metadata and PDF violations have no Schematron rule and are located at the document root.
+#[uniffi::export]
+pub fn validation_report_to_svrl(report: &ValidationReport) -> String {
+    let mut writer = EmitterConfig::new()
+        .perform_indent(true)
+        .create_writer(Vec::new());
+    write_report(&mut writer, report).expect("writing to a vector cannot fail");
+    String::from_utf8(writer.into_inner()).expect("xml-rs writes UTF-8")
+}
+
+fn write_report(writer: &mut EventWriter<Vec<u8>>, report: &ValidationReport) -> Result<()> {
+    writer.write(
+        XmlEvent::start_element("svrl:schematron-output")
+            .ns("svrl", SVRL_NAMESPACE)
+            .attr("title", "EN 16931 business rules"),
+    )?;
+    let mut active_pattern = XmlEvent::start_element("svrl:active-pattern");
+    if let Some(file_name) = &report.file_name {
+        active_pattern = active_pattern.attr("document", file_name);
+    }
+    writer.write(active_pattern)?;
+    writer.write(XmlEvent::end_element())?;
+    for violation in &report.violations {
+        match violation {
+            ValidationError::Fatal(violation) => {
+                write_rule_violation(writer, report.syntax, "fatal", violation)?
+            }
+            ValidationError::Warning(violation) => {
+                write_rule_violation(writer, report.syntax, "warning", violation)?
+            }
+            ValidationError::MetadataMismatch(violation)
+            | ValidationError::PdfViolation(violation) => {
+                write_failed_assert(writer, "fatal", violation, None)?
+            }
+            ValidationError::ConcurrencyError(text) => {
+                write_failed_assert(writer, "fatal", &BusinessRuleViolation::new("", text), None)?
+            }
+        }
+    }
+    writer.write(XmlEvent::end_element())
+}
+
+/// Write a business rule violation as the rule that fired and its failed assertion
+fn write_rule_violation(
+    writer: &mut EventWriter<Vec<u8>>,
+    syntax: InvoiceSyntax,
+    flag: &str,
+    violation: &BusinessRuleViolation,
+) -> Result<()> {
+    let assertion =
+        catalogue_entry(syntax, &violation.rule_id).map(|entry| (entry.context, entry.test));
+    if let Some((context, _)) = assertion {
+        writer.write(XmlEvent::start_element("svrl:fired-rule").attr("context", context))?;
+        writer.write(XmlEvent::end_element())?;
+    }
+    write_failed_assert(writer, flag, violation, assertion)
+}
+
+fn write_failed_assert(
+    writer: &mut EventWriter<Vec<u8>>,
+    flag: &str,
+    violation: &BusinessRuleViolation,
+    assertion: Option<(&str, &str)>,
+) -> Result<()> {
+    let location = match (&violation.location, assertion) {
+        (Some(location), _) => location.path.clone(),
+        (None, Some((context, _))) => location(context),
+        (None, None) => "/".to_string(),
+    };
+    let mut failed_assert = XmlEvent::start_element("svrl:failed-assert")
+        .attr("id", &violation.rule_id)
+        .attr("flag", flag)
+        .attr("location", &location);
+    if let Some((_, test)) = assertion {
+        failed_assert = failed_assert.attr("test", test);
+    }
+    writer.write(failed_assert)?;
+    writer.write(XmlEvent::start_element("svrl:text"))?;
+    writer.write(XmlEvent::characters(&violation.rule_text))?;
+    writer.write(XmlEvent::end_element())?;
+    writer.write(XmlEvent::end_element())
+}
+
+/// An XPath locating the nodes a rule context matches, relative contexts match anywhere in the document
+fn location(context: &str) -> String {
+    context_alternatives(context)
+        .iter()
+        .map(|path| {
+            if path.starts_with('/') {
+                path.to_string()
+            } else {
+                format!("//{}", path)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
@@ -0,0 +1,142 @@
+use crate::{InvoiceStandard, InvoiceSyntax, ValidationConfig, ValidationError};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use std::sync::Arc;
+
+/// A business rule checked in addition to the EN 16931 rules, e.g. an in-house rule of a receiver
+///
+/// Rules are checked for UBL and CII invoices alike, use `InvoiceStandard::syntax` to tell them apart.
+/// Add them to `ValidationConfig::custom_rules` with `ValidationConfig::with_rule`,
+/// their violations can be suppressed or re-graded like those of the built-in rules.
+pub trait Rule: Send + Sync {
+    /// The id reported with violations, e.g. `ACME-01`
+    fn rule_id(&self) -> &str;
+
+    /// Check an invoice
+    /// # Arguments
+    /// * `invoice` - The parsed invoice
+    /// # Returns
+    /// * `Result<(), ValidationError>` - The violation if the invoice does not satisfy the rule
+    fn check(&self, invoice: &InvoiceStandard) -> Result<(), ValidationError>;
+}
+
+/// A rule checked by a closure, which can capture its own configuration
+pub struct FnRule<F> {
+    rule_id: String,
+    check: F,
+}
+
+impl<F> FnRule<F>
+where
+    F: Fn(&InvoiceStandard) -> Result<(), ValidationError> + Send + Sync,
+{
+    /// Create a rule from a closure
+    /// # Arguments
+    /// * `rule_id` - The id reported with violations
+    /// * `check` - Returns the violation if the invoice does not satisfy the rule
+    pub fn new(rule_id: &str, check: F) -> Self {
+        FnRule {
+            rule_id: rule_id.to_string(),
+            check,
+        }
+    }
+}
+
+impl<F> Rule for FnRule<F>
+where
+    F: Fn(&InvoiceStandard) -> Result<(), ValidationError> + Send + Sync,
+{
+    fn rule_id(&self) -> &str {
+        &self.rule_id
+    }
+
+    fn check(&self, invoice: &InvoiceStandard) -> Result<(), ValidationError> {
+        (self.check)(invoice)
+    }
+}
+
+/// The invoice checked by the custom rules of a validation
+///
+/// The invoice is copied once per validation and shared by all rules.
+#[derive(uniffi::Object)]
+pub struct CheckedInvoice {
+    invoice: InvoiceStandard,
+}
+
+#[uniffi::export]
+impl CheckedInvoice {
+    /// The parsed invoice, copied on every call
+    pub fn invoice(&self) -> InvoiceStandard {
+        self.invoice.clone()
+    }
+
+    /// The syntax of the invoice
+    pub fn syntax(&self) -> InvoiceSyntax {
+        self.invoice.syntax()
+    }
+
+    /// The invoice number (BT-1)
+    pub fn invoice_number(&self) -> Option<String> {
+        self.invoice.invoice_number().map(str::to_string)
+    }
+}
+
+/// A business rule, implemented in Rust with `Rule` or in a language using the bindings, e.g. Kotlin or Java
+///
+/// An exception thrown by `check` is not caught and aborts the validation.
+#[uniffi::export(with_foreign)]
+pub trait ForeignRule: Send + Sync {
+    /// The id reported with violations
+    fn rule_id(&self) -> String;
+
+    /// Check an invoice, returning the violation if the invoice does not satisfy the rule
+    fn check(&self, invoice: Arc<CheckedInvoice>) -> Option<ValidationError>;
+}
+
+/// A `Rule` checking the invoice by reference
+struct RustRule<R>(R);
+
+impl<R: Rule> ForeignRule for RustRule<R> {
+    fn rule_id(&self) -> String {
+        self.0.rule_id().to_string()
+    }
+
+    fn check(&self, invoice: Arc<CheckedInvoice>) -> Option<ValidationError> {
+        self.0.check(&invoice.invoice).err()
+    }
+}
+
+impl ValidationConfig {
+    /// Check a rule in addition to the built-in ones
+    /// # Arguments
+    /// * `rule` - The rule, replacing a custom rule with the same id
+    /// # Returns
+    /// * `ValidationConfig` - The configuration with the rule
+    pub fn with_rule(mut self, rule: impl Rule + 'static) -> Self {
+        self.custom_rules
+            .retain(|custom| custom.rule_id() != rule.rule_id());
+        self.custom_rules.push(Arc::new(RustRule(rule)));
+        self
+    }
+}
+
+/// Check an invoice against the custom rules enabled by a configuration
+pub(crate) fn validate_custom_rules(
+    invoice: &InvoiceStandard,
+    config: &ValidationConfig,
+) -> Vec<ValidationError> {
+    let rules = config
+        .custom_rules
+        .iter()
+        .filter(|rule| config.is_enabled(&rule.rule_id()))
+        .collect::<Vec<_>>();
+    if rules.is_empty() {
+        return Vec::new();
+    }
+    let invoice = Arc::new(CheckedInvoice {
+        invoice: invoice.clone(),
+    });
+    rules
+        .par_iter()
+        .filter_map(|rule| rule.check(invoice.clone()))
+        .collect()
+}
@@ -0,0 +1,147 @@
+use crate::ubl::ubl_business_rule_validator::rule_in_profile;
+use crate::{InvoiceSyntax, ZugferdProfile};
+
+// Generated by build.rs from the rule functions of the UBL and CII business rule validators
+include!(concat!(env!("OUT_DIR"), "/rule_catalogue.rs"));
+
+/// The severity a business rule reports violations with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, uniffi::Enum)]
+pub enum RuleSeverity {
+    /// Reported as `ValidationError::Fatal`, the invoice is rejected
+    Fatal,
+    /// Reported as `ValidationError::Warning`
+    Warning,
+}
+
+/// A business rule as checked for one syntax
+///
+/// Rules checked for UBL and CII have an entry for each syntax, their context, test and message differ.
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct RuleDefinition {
+    /// The id of the rule, e.g. `BR-CO-25`
+    pub rule_id: String,
+    /// The syntax the rule is checked for
+    pub syntax: InvoiceSyntax,
+    pub severity: RuleSeverity,
+    /// The message reported for violations, without the `[rule id]-` prefix
+    pub message: String,
+    /// The Schematron context the rule applies to
+    pub context: String,
+    /// The Schematron test the context has to pass
+    pub test: String,
+    /// The business terms and groups the message refers to, e.g. `BT-24` or `BG-25`
+    pub business_terms: Vec<String>,
+    /// The ZUGFeRD / Factur-X profiles that check the rule
+    ///
+    /// EN 16931 checks every UBL rule, the other profiles the UBL rules with the same id as their CII rules.
+    pub profiles: Vec<ZugferdProfile>,
+    /// Whether violations of the rule are detected, rules that are not implemented yet always pass
+    pub implemented: bool,
+}
+
+/// A catalogue row as generated by the build script
+pub(crate) struct CatalogueEntry {
+    pub(crate) rule_id: &'static str,
+    pub(crate) severity: RuleSeverity,
+    pub(crate) message: &'static str,
+    pub(crate) context: &'static str,
+    pub(crate) test: &'static str,
+    pub(crate) business_terms: &'static [&'static str],
+    pub(crate) profiles: &'static [ZugferdProfile],
+    pub(crate) implemented: bool,
+}
+
+impl CatalogueEntry {
+    fn definition(&self, syntax: InvoiceSyntax) -> RuleDefinition {
+        RuleDefinition {
+            rule_id: self.rule_id.to_string(),
+            syntax,
+            severity: self.severity,
+            message: self.message.to_string(),
+            context: self.context.to_string(),
+            test: self.test.to_string(),
+            business_terms: self.business_terms.iter().map(|t| t.to_string()).collect(),
+            profiles: match syntax {
+                InvoiceSyntax::Ubl => [
+                    ZugferdProfile::Minimum,
+                    ZugferdProfile::BasicWl,
+                    ZugferdProfile::Basic,
+                    ZugferdProfile::En16931,
+                    ZugferdProfile::Extended,
+                ]
+                .into_iter()
+                .filter(|profile| rule_in_profile(self.rule_id, *profile))
+                .collect(),
+                InvoiceSyntax::Cii => self.profiles.to_vec(),
+            },
+            implemented: self.implemented,
+        }
+    }
+}
+
+fn entries(syntax: InvoiceSyntax) -> &'static [CatalogueEntry] {
+    match syntax {
+        InvoiceSyntax::Ubl => UBL_RULES,
+        InvoiceSyntax::Cii => CII_RULES,
+    }
+}
+
+/// The catalogue row of a rule
+pub(crate) fn catalogue_entry(
+    syntax: InvoiceSyntax,
+    rule_id: &str,
+) -> Option<&'static CatalogueEntry> {
+    entries(syntax)
+        .iter()
+        .find(|entry| entry.rule_id == rule_id)
+}
+
+/// List every business rule of the UBL and CII validators
+/// # Returns
+/// * `Vec<RuleDefinition>` - The UBL rules followed by the CII rules, in the order they are checked
+#[uniffi::export]
+pub fn rule_catalogue() -> Vec<RuleDefinition> {
+    [InvoiceSyntax::Ubl, InvoiceSyntax::Cii]
+        .into_iter()
+        .flat_map(|syntax| {
+            entries(syntax)
+                .iter()
+                .map(move |entry| entry.definition(syntax))
+        })
+        .collect()
+}
+
+/// Look up a business rule
+/// # Arguments
+/// * `rule_id` - The id of the rule, e.g. `BR-CO-25`, case-insensitive
+/// # Returns
+/// * `Vec<RuleDefinition>` - The definition for every syntax the rule is checked for, empty for an unknown rule
+#[uniffi::export]
+pub fn find_rule(rule_id: &str) -> Vec<RuleDefinition> {
+    let rule_id = rule_id.trim().to_uppercase();
+    [InvoiceSyntax::Ubl, InvoiceSyntax::Cii]
+        .into_iter()
+        .filter_map(|syntax| {
+            catalogue_entry(syntax, &rule_id).map(|entry| entry.definition(syntax))
+        })
+        .collect()
+}
+
+/// List the business rules checked for an invoice syntax and profile
+/// # Arguments
+/// * `syntax` - The syntax of the invoice
+/// * `profile` - The profile the invoice declares, all rules of the syntax if `None`
+/// # Returns
+/// * `Vec<RuleDefinition>` - The rules in the order they are checked
+#[uniffi::export]
+pub fn rules_for(syntax: InvoiceSyntax, profile: Option<ZugferdProfile>) -> Vec<RuleDefinition> {
+    entries(syntax)
+        .iter()
+        .filter(|entry| match (syntax, profile) {
+            (InvoiceSyntax::Ubl, Some(profile)) => rule_in_profile(entry.rule_id, profile),
+            (InvoiceSyntax::Cii, Some(profile)) => entry.profiles.contains(&profile),
+            (_, None) => true,
+        })
+        .map(|entry| entry.definition(syntax))
+        .collect()
+}
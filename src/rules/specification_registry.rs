@@ -0,0 +1,249 @@
+use crate::{InvoiceSyntax, ValidationConfig, ZugferdProfile};
+
+/// The prefix of the identifiers of CIUS, which restrict EN 16931 without adding to it
+const COMPLIANT_PREFIX: &str = "urn:cen.eu:en16931:2017#compliant#";
+
+/// The version of the Factur-X XMP extension schema, declared by Factur-X 1.0 up to 1.07 and ZUGFeRD 2.x alike
+const FACTUR_X_VERSION: &str = "1.0";
+
+/// A specification identifier (BT-24) and the rules an invoice declaring it is validated with
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct Specification {
+    /// The identifier as declared in `cbc:CustomizationID` or the CII guideline ID,
+    /// e.g. `urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0`
+    pub identifier: String,
+    /// A human readable name, e.g. `XRechnung 3.0`
+    pub name: String,
+    /// The profile selecting the business rules checked, for UBL invoices the UBL rules with the same ids
+    /// as the CII rules of the profile
+    pub profile: ZugferdProfile,
+    /// The syntax the identifier is used with, `None` if it is used with both
+    pub syntax: Option<InvoiceSyntax>,
+    /// The `fx:ConformanceLevel` a hybrid PDF declares in its XMP metadata, e.g. `EN 16931` or `XRECHNUNG`
+    pub conformance_level: String,
+    /// The `fx:Version` of the Factur-X XMP extension schema a hybrid PDF declares, e.g. `1.0`
+    pub facturx_version: String,
+}
+
+/// A built-in registry row
+struct KnownSpecification {
+    identifier: &'static str,
+    name: &'static str,
+    profile: ZugferdProfile,
+    syntax: Option<InvoiceSyntax>,
+    conformance_level: &'static str,
+}
+
+impl KnownSpecification {
+    fn matches(&self, identifier: &str, syntax: InvoiceSyntax) -> bool {
+        self.identifier == identifier && self.syntax.is_none_or(|s| s == syntax)
+    }
+
+    fn specification(&self) -> Specification {
+        Specification {
+            identifier: self.identifier.to_string(),
+            name: self.name.to_string(),
+            profile: self.profile,
+            syntax: self.syntax,
+            conformance_level: self.conformance_level.to_string(),
+            facturx_version: FACTUR_X_VERSION.to_string(),
+        }
+    }
+}
+
+/// The specifications known without registration
+///
+/// Factur-X 1.0 up to 1.07 and ZUGFeRD 2.1 up to 2.3 share the `urn:factur-x.eu:1p0` identifiers,
+/// ZUGFeRD 2.3 declares its EN 16931 and XRECHNUNG profiles with the EN 16931 and XRechnung identifiers.
+/// ZUGFeRD 1.0 COMFORT is the predecessor of the EN 16931 profile.
+const KNOWN_SPECIFICATIONS: &[KnownSpecification] = &[
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017",
+        name: "EN 16931",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "EN 16931",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0",
+        name: "XRechnung 3.0",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "XRECHNUNG",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:xeinkauf.de:kosit:xrechnung_3.0#conformant#urn:xeinkauf.de:kosit:extension:xrechnung_3.0",
+        name: "XRechnung 3.0 Extension",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "XRECHNUNG",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:xoev-de:kosit:standard:xrechnung_2.3",
+        name: "XRechnung 2.3",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "XRECHNUNG",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:xoev-de:kosit:standard:xrechnung_2.3#conformant#urn:xoev-de:kosit:extension:xrechnung_2.3",
+        name: "XRechnung 2.3 Extension",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "XRECHNUNG",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0",
+        name: "Peppol BIS Billing 3.0",
+        profile: ZugferdProfile::En16931,
+        syntax: None,
+        conformance_level: "EN 16931",
+    },
+    KnownSpecification {
+        identifier: "urn:factur-x.eu:1p0:minimum",
+        name: "Factur-X / ZUGFeRD MINIMUM",
+        profile: ZugferdProfile::Minimum,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "MINIMUM",
+    },
+    KnownSpecification {
+        identifier: "urn:factur-x.eu:1p0:basicwl",
+        name: "Factur-X / ZUGFeRD BASIC WL",
+        profile: ZugferdProfile::BasicWl,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "BASIC WL",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:factur-x.eu:1p0:basic",
+        name: "Factur-X / ZUGFeRD BASIC",
+        profile: ZugferdProfile::Basic,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "BASIC",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#conformant#urn:factur-x.eu:1p0:extended",
+        name: "Factur-X / ZUGFeRD EXTENDED",
+        profile: ZugferdProfile::Extended,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "EXTENDED",
+    },
+    KnownSpecification {
+        identifier: "urn:zugferd.de:2p0:minimum",
+        name: "ZUGFeRD 2.0 MINIMUM",
+        profile: ZugferdProfile::Minimum,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "MINIMUM",
+    },
+    KnownSpecification {
+        identifier: "urn:zugferd.de:2p0:basicwl",
+        name: "ZUGFeRD 2.0 BASIC WL",
+        profile: ZugferdProfile::BasicWl,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "BASIC WL",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#compliant#urn:zugferd.de:2p0:basic",
+        name: "ZUGFeRD 2.0 BASIC",
+        profile: ZugferdProfile::Basic,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "BASIC",
+    },
+    KnownSpecification {
+        identifier: "urn:cen.eu:en16931:2017#conformant#urn:zugferd.de:2p0:extended",
+        name: "ZUGFeRD 2.0 EXTENDED",
+        profile: ZugferdProfile::Extended,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "EXTENDED",
+    },
+    KnownSpecification {
+        identifier: "urn:ferd:CrossIndustryDocument:invoice:1p0:basic",
+        name: "ZUGFeRD 1.0 BASIC",
+        profile: ZugferdProfile::Basic,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "BASIC",
+    },
+    KnownSpecification {
+        identifier: "urn:ferd:CrossIndustryDocument:invoice:1p0:comfort",
+        name: "ZUGFeRD 1.0 COMFORT",
+        profile: ZugferdProfile::En16931,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "COMFORT",
+    },
+    KnownSpecification {
+        identifier: "urn:ferd:CrossIndustryDocument:invoice:1p0:extended",
+        name: "ZUGFeRD 1.0 EXTENDED",
+        profile: ZugferdProfile::Extended,
+        syntax: Some(InvoiceSyntax::Cii),
+        conformance_level: "EXTENDED",
+    },
+];
+
+/// All built-in specifications
+#[uniffi::export]
+pub fn known_specifications() -> Vec<Specification> {
+    KNOWN_SPECIFICATIONS
+        .iter()
+        .map(KnownSpecification::specification)
+        .collect()
+}
+
+/// Find the built-in specification an invoice declares
+/// # Arguments
+/// * `identifier` - The specification identifier (BT-24)
+/// * `syntax` - The syntax of the invoice
+/// # Returns
+/// * `Option<Specification>` - The built-in specification for the identifier and syntax,
+///   else an EN 16931 specification if the identifier declares a CIUS with the `#compliant#` prefix
+///
+/// Use `ValidationConfig::find_specification` to include the specifications of a configuration.
+#[uniffi::export]
+pub fn find_specification(identifier: &str, syntax: InvoiceSyntax) -> Option<Specification> {
+    ValidationConfig::default().find_specification(identifier, syntax)
+}
+
+impl ValidationConfig {
+    /// Find the specification an invoice declares
+    /// # Arguments
+    /// * `identifier` - The specification identifier (BT-24)
+    /// * `syntax` - The syntax of the invoice
+    /// # Returns
+    /// * `Option<Specification>` - The specification of the configuration or else the built-in one for the identifier and syntax,
+    ///   else an EN 16931 specification if the identifier declares a CIUS with the `#compliant#` prefix
+    ///
+    /// A specification of the configuration hides a built-in one with the same identifier for both syntaxes.
+    pub fn find_specification(
+        &self,
+        identifier: &str,
+        syntax: InvoiceSyntax,
+    ) -> Option<Specification> {
+        let identifier = identifier.trim();
+        let configured = self
+            .specifications
+            .iter()
+            .filter(|specification| specification.identifier == identifier)
+            .collect::<Vec<_>>();
+        let found = if configured.is_empty() {
+            KNOWN_SPECIFICATIONS
+                .iter()
+                .find(|known| known.matches(identifier, syntax))
+                .map(KnownSpecification::specification)
+        } else {
+            configured
+                .into_iter()
+                .find(|specification| specification.syntax.is_none_or(|s| s == syntax))
+                .cloned()
+        };
+        found.or_else(|| {
+            identifier
+                .starts_with(COMPLIANT_PREFIX)
+                .then(|| Specification {
+                    identifier: identifier.to_string(),
+                    name: "EN 16931 CIUS".to_string(),
+                    profile: ZugferdProfile::En16931,
+                    syntax: None,
+                    conformance_level: ZugferdProfile::En16931.conformance_level().to_string(),
+                    facturx_version: FACTUR_X_VERSION.to_string(),
+                })
+        })
+    }
+}
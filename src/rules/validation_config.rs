@@ -0,0 +1,121 @@
+use crate::rules::rule_catalogue::RuleSeverity;
+use crate::{ForeignRule, Specification, ValidationError};
+use std::fmt;
+use std::sync::Arc;
+
+/// Report violations of a rule with another severity
+#[derive(Debug, Clone, PartialEq, Eq, uniffi::Record)]
+pub struct SeverityOverride {
+    /// The id of the rule, e.g. `BR-CO-25`
+    pub rule_id: String,
+    pub severity: RuleSeverity,
+}
+
+/// Which business rules are checked and how their violations are reported
+///
+/// The default checks every built-in rule with its own severity.
+/// Rule ids and families are compared case-insensitively.
+#[derive(Clone, Default, uniffi::Record)]
+pub struct ValidationConfig {
+    /// Rules that are not reported
+    pub suppressed_rules: Vec<String>,
+    /// Rules reported with another severity than their own
+    pub severity_overrides: Vec<SeverityOverride>,
+    /// Only report rules of these families, e.g. `BR-CO` or `BR-CL`, all rules if empty
+    ///
+    /// The family of a rule is its id without the trailing number, `BR-CO-25` belongs to `BR-CO` and `BR-01` to `BR`.
+    pub rule_families: Vec<String>,
+    /// Rules checked in addition to the built-in ones, see `ValidationConfig::with_rule`
+    pub custom_rules: Vec<Arc<dyn ForeignRule>>,
+    /// Specification identifiers (BT-24) known in addition to the built-in ones, taking precedence over them
+    ///
+    /// See `ValidationConfig::find_specification`.
+    pub specifications: Vec<Specification>,
+}
+
+impl fmt::Debug for ValidationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationConfig")
+            .field("suppressed_rules", &self.suppressed_rules)
+            .field("severity_overrides", &self.severity_overrides)
+            .field("rule_families", &self.rule_families)
+            .field(
+                "custom_rules",
+                &self
+                    .custom_rules
+                    .iter()
+                    .map(|rule| rule.rule_id())
+                    .collect::<Vec<_>>(),
+            )
+            .field("specifications", &self.specifications)
+            .finish()
+    }
+}
+
+impl ValidationConfig {
+    /// Whether violations of a rule are reported
+    /// # Arguments
+    /// * `rule_id` - The id of the rule
+    /// # Returns
+    /// * `bool` - `false` if the rule is suppressed or outside the selected families
+    pub fn is_enabled(&self, rule_id: &str) -> bool {
+        let suppressed = self
+            .suppressed_rules
+            .iter()
+            .any(|suppressed| suppressed.trim().eq_ignore_ascii_case(rule_id));
+        let family = rule_family(rule_id);
+        let selected = self.rule_families.is_empty()
+            || self
+                .rule_families
+                .iter()
+                .any(|selected| selected.trim().eq_ignore_ascii_case(family));
+        !suppressed && selected
+    }
+
+    /// Drop the violations of disabled rules and change the severity of overridden ones
+    ///
+    /// The validators skip disabled rules, this drops violations reported under another rule id.
+    /// Only `ValidationError::Fatal` and `ValidationError::Warning` are affected,
+    /// metadata and PDF container violations are always reported as they are.
+    pub(crate) fn apply(&self, violations: Vec<ValidationError>) -> Vec<ValidationError> {
+        violations
+            .into_iter()
+            .filter_map(|violation| {
+                let (violation, severity) = match violation {
+                    ValidationError::Fatal(violation) => (violation, RuleSeverity::Fatal),
+                    ValidationError::Warning(violation) => (violation, RuleSeverity::Warning),
+                    violation => return Some(violation),
+                };
+                if !self.is_enabled(&violation.rule_id) {
+                    return None;
+                }
+                Some(
+                    match self.severity(&violation.rule_id).unwrap_or(severity) {
+                        RuleSeverity::Fatal => ValidationError::Fatal(violation),
+                        RuleSeverity::Warning => ValidationError::Warning(violation),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn severity(&self, rule_id: &str) -> Option<RuleSeverity> {
+        self.severity_overrides
+            .iter()
+            .find(|severity_override| {
+                severity_override
+                    .rule_id
+                    .trim()
+                    .eq_ignore_ascii_case(rule_id)
+            })
+            .map(|severity_override| severity_override.severity)
+    }
+}
+
+/// The id of a rule without its trailing number
+fn rule_family(rule_id: &str) -> &str {
+    rule_id
+        .rsplit_once('-')
+        .filter(|(_, number)| number.chars().all(|c| c.is_ascii_digit()))
+        .map_or(rule_id, |(family, _)| family)
+}
@@ -1,14 +1,28 @@
-use crate::{BusinessRuleViolation, UblInvoice, ValidationError};
+use crate::attachment::attachment_reader::ALLOWED_MIME_CODES;
+use crate::cii::cii_business_rule_validator::{
+    BASIC_BUSINESS_RULE_IDS, BASIC_WL_BUSINESS_RULE_IDS, EXTENDED_BUSINESS_RULE_IDS,
+    MINIMUM_BUSINESS_RULE_IDS,
+};
+use crate::{BusinessRuleViolation, UblInvoice, ValidationConfig, ValidationError, ZugferdProfile};
 use rayon::iter::IntoParallelRefIterator;
-use rayon::iter::ParallelIterator;
+use rayon::iter::{IndexedParallelIterator, ParallelIterator};
 use std::sync::Arc;
 
-pub fn validate_invoice(invoice: Arc<&UblInvoice>) -> Result<(), Vec<ValidationError>> {
+pub fn validate_invoice(
+    zugferd_profile: ZugferdProfile,
+    invoice: Arc<&UblInvoice>,
+    config: &ValidationConfig,
+) -> Result<(), Vec<ValidationError>> {
     let results: Vec<_> = BUSINESS_RULES
         .par_iter()
-        .map(|rule| rule(&invoice))
+        .zip(BUSINESS_RULE_IDS)
+        .filter(|(_, rule_id)| {
+            rule_in_profile(rule_id, zugferd_profile) && config.is_enabled(rule_id)
+        })
+        .map(|(rule, _)| rule(&invoice))
         .filter_map(Result::err) // Collect only errors
         .collect();
+    let results = config.apply(results);
 
     if results.is_empty() {
         Ok(())
@@ -17,7 +31,23 @@ pub fn validate_invoice(invoice: Arc<&UblInvoice>) -> Result<(), Vec<ValidationE
     }
 }
 
+/// Whether a UBL rule is checked for a profile
+///
+/// EN 16931 checks every rule, the other profiles the rules with the same id as their CII rules.
+pub(crate) fn rule_in_profile(rule_id: &str, zugferd_profile: ZugferdProfile) -> bool {
+    match zugferd_profile {
+        ZugferdProfile::Minimum => MINIMUM_BUSINESS_RULE_IDS.contains(&rule_id),
+        ZugferdProfile::BasicWl => BASIC_WL_BUSINESS_RULE_IDS.contains(&rule_id),
+        ZugferdProfile::Basic => BASIC_BUSINESS_RULE_IDS.contains(&rule_id),
+        ZugferdProfile::En16931 => true,
+        ZugferdProfile::Extended => EXTENDED_BUSINESS_RULE_IDS.contains(&rule_id),
+    }
+}
+
 type BusinessRule = fn(&UblInvoice) -> Result<(), ValidationError>;
+
+include!(concat!(env!("OUT_DIR"), "/ubl_rule_ids.rs"));
+
 pub const BUSINESS_RULES: &[BusinessRule] = &[
     validate_br_52,
     validate_br_co_25,
@@ -1003,15 +1033,18 @@ pub const BUSINESS_RULES: &[BusinessRule] = &[
 // Context: cac:AdditionalDocumentReference
 // Test: normalize-space(cbc:ID) != ''
 fn validate_br_52(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice
+    if let Some(index) = invoice
         .cac_additional_document_reference
         .iter()
-        .any(|doc| doc.id.is_none())
+        .position(|doc| doc.id.is_none())
     {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-52",
-            "[BR-52]-Each Additional supporting document (BG-24) shall contain a Supporting document reference (BT-122).",
-        )));
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-52",
+                "[BR-52]-Each Additional supporting document (BG-24) shall contain a Supporting document reference (BT-122).",
+            )
+            .with_element(format!("cac:AdditionalDocumentReference[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -1068,7 +1101,7 @@ fn validate_br_11(invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: cac:PaymentMeans/cac:CardAccount/cbc:PrimaryAccountNumberID
 // Test: string-length(normalize-space(.))<=10
 fn validate_br_51(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_payment_means.iter().any(|payment_means| {
+    if let Some(index) = invoice.cac_payment_means.iter().position(|payment_means| {
         payment_means.cac_card_account.iter().any(|card_account| {
             card_account
                 .primary_account_number_id
@@ -1076,10 +1109,16 @@ fn validate_br_51(invoice: &UblInvoice) -> Result<(), ValidationError> {
                 .any(|identifier| identifier.id.iter().any(|id| id.len() > 10))
         })
     }) {
-        return Err(ValidationError::Warning(BusinessRuleViolation::new(
-            "BR-51",
-            "[BR-51]-In accordance with card payments security standards an invoice should never include a full card primary account number (BT-87). At the moment PCI Security Standards Council has defined that the first 6 digits and last 4 digits are the maximum number of digits to be shown.",
-        )));
+        return Err(ValidationError::Warning(
+            BusinessRuleViolation::new(
+                "BR-51",
+                "[BR-51]-In accordance with card payments security standards an invoice should never include a full card primary account number (BT-87). At the moment PCI Security Standards Council has defined that the first 6 digits and last 4 digits are the maximum number of digits to be shown.",
+            )
+            .with_element(format!(
+                "cac:PaymentMeans[{}]/cac:CardAccount/cbc:PrimaryAccountNumberID",
+                index + 1
+            )),
+        ));
     }
     Ok(())
 }
@@ -1098,10 +1137,13 @@ fn validate_br_57(invoice: &UblInvoice) -> Result<(), ValidationError> {
                     .any(|address| address.cac_country.is_none())
             })
     }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-57",
-            "[BR-57]-Each Deliver to address (BG-15) shall contain a Deliver to country code (BT-80).",
-        )));
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-57",
+                "[BR-57]-Each Deliver to address (BG-15) shall contain a Deliver to country code (BT-80).",
+            )
+            .with_element("cac:Delivery/cac:DeliveryLocation/cac:Address".to_string()),
+        ));
     }
     Ok(())
 }
@@ -1109,17 +1151,24 @@ fn validate_br_57(invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: /ubl:Invoice/cac:AllowanceCharge[cbc:ChargeIndicator = false()] | /cn:CreditNote/cac:AllowanceCharge[cbc:ChargeIndicator = false()]
 // Test: exists(cbc:Amount)
 fn validate_br_31(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_allowance_charge.iter().any(|allowance_charge| {
-        allowance_charge
-            .charge_indicator
-            .map(|charge| !charge)
-            .unwrap_or(false)
-            && allowance_charge.amount.is_none()
-    }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-31",
-            "[BR-31]-Each Document level allowance (BG-20) shall have a Document level allowance amount (BT-92).",
-        )));
+    if let Some(index) = invoice
+        .cac_allowance_charge
+        .iter()
+        .position(|allowance_charge| {
+            allowance_charge
+                .charge_indicator
+                .map(|charge| !charge)
+                .unwrap_or(false)
+                && allowance_charge.amount.is_none()
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-31",
+                "[BR-31]-Each Document level allowance (BG-20) shall have a Document level allowance amount (BT-92).",
+            )
+            .with_element(format!("cac:AllowanceCharge[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -1127,27 +1176,34 @@ fn validate_br_31(invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: /ubl:Invoice/cac:AllowanceCharge[cbc:ChargeIndicator = false()] | /cn:CreditNote/cac:AllowanceCharge[cbc:ChargeIndicator = false()]
 // Test: exists(cac:TaxCategory[cac:TaxScheme/normalize-space(upper-case(cbc:ID))='VAT']/cbc:ID)
 fn validate_br_32(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_allowance_charge.iter().any(|allowance_charge| {
-        allowance_charge
-            .charge_indicator
-            .map(|charge| !charge)
-            .unwrap_or(false)
-            && allowance_charge
-                .cac_tax_category
-                .iter()
-                .all(|tax_category| {
-                    tax_category.cac_tax_scheme.iter().any(|tax_scheme| {
-                        tax_scheme
-                            .id
-                            .iter()
-                            .any(|identifier| identifier.id.iter().any(|id| id != "VAT"))
+    if let Some(index) = invoice
+        .cac_allowance_charge
+        .iter()
+        .position(|allowance_charge| {
+            allowance_charge
+                .charge_indicator
+                .map(|charge| !charge)
+                .unwrap_or(false)
+                && allowance_charge
+                    .cac_tax_category
+                    .iter()
+                    .all(|tax_category| {
+                        tax_category.cac_tax_scheme.iter().any(|tax_scheme| {
+                            tax_scheme
+                                .id
+                                .iter()
+                                .any(|identifier| identifier.id.iter().any(|id| id != "VAT"))
+                        })
                     })
-                })
-    }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-32",
-            "[BR-32]-Each Document level allowance (BG-20) shall have a Document level allowance VAT category code (BT-95).",
-        )));
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-32",
+                "[BR-32]-Each Document level allowance (BG-20) shall have a Document level allowance VAT category code (BT-95).",
+            )
+            .with_element(format!("cac:AllowanceCharge[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -1155,18 +1211,25 @@ fn validate_br_32(invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: /ubl:Invoice/cac:AllowanceCharge[cbc:ChargeIndicator = false()] | /cn:CreditNote/cac:AllowanceCharge[cbc:ChargeIndicator = false()]
 // Test: exists(cbc:AllowanceChargeReason) or exists(cbc:AllowanceChargeReasonCode)
 fn validate_br_33(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_allowance_charge.iter().any(|allowance_charge| {
-        allowance_charge
-            .charge_indicator
-            .map(|charge_indicator| !charge_indicator)
-            .unwrap_or(false)
-            && allowance_charge.cbc_allowance_charge_reason.is_none()
-            && allowance_charge.cbc_allowance_charge_reason_code.is_none()
-    }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-33",
-            "[BR-33]-Each Document level allowance (BG-20) shall have a Document level allowance reason (BT-97) or a Document level allowance reason code (BT-98).",
-        )));
+    if let Some(index) = invoice
+        .cac_allowance_charge
+        .iter()
+        .position(|allowance_charge| {
+            allowance_charge
+                .charge_indicator
+                .map(|charge_indicator| !charge_indicator)
+                .unwrap_or(false)
+                && allowance_charge.cbc_allowance_charge_reason.is_none()
+                && allowance_charge.cbc_allowance_charge_reason_code.is_none()
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-33",
+                "[BR-33]-Each Document level allowance (BG-20) shall have a Document level allowance reason (BT-97) or a Document level allowance reason code (BT-98).",
+            )
+            .with_element(format!("cac:AllowanceCharge[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -1186,18 +1249,25 @@ fn validate_br_co_05(_invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: /ubl:Invoice/cac:AllowanceCharge[cbc:ChargeIndicator = false()] | /cn:CreditNote/cac:AllowanceCharge[cbc:ChargeIndicator = false()]
 // Test: exists(cbc:AllowanceChargeReason) or exists(cbc:AllowanceChargeReasonCode)
 fn validate_br_co_21(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_allowance_charge.iter().any(|allowance_charge| {
-        allowance_charge
-            .charge_indicator
-            .map(|charge_indicator| !charge_indicator)
-            .unwrap_or(false)
-            && allowance_charge.cbc_allowance_charge_reason.is_none()
-            && allowance_charge.cbc_allowance_charge_reason_code.is_none()
-    }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-CO-21",
-            "[BR-CO-21]-Each Document level allowance (BG-20) shall contain a Document level allowance reason (BT-97) or a Document level allowance reason code (BT-98), or both.",
-        )));
+    if let Some(index) = invoice
+        .cac_allowance_charge
+        .iter()
+        .position(|allowance_charge| {
+            allowance_charge
+                .charge_indicator
+                .map(|charge_indicator| !charge_indicator)
+                .unwrap_or(false)
+                && allowance_charge.cbc_allowance_charge_reason.is_none()
+                && allowance_charge.cbc_allowance_charge_reason_code.is_none()
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-CO-21",
+                "[BR-CO-21]-Each Document level allowance (BG-20) shall contain a Document level allowance reason (BT-97) or a Document level allowance reason code (BT-98), or both.",
+            )
+            .with_element(format!("cac:AllowanceCharge[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -1205,22 +1275,29 @@ fn validate_br_co_21(invoice: &UblInvoice) -> Result<(), ValidationError> {
 // Context: /ubl:Invoice/cac:AllowanceCharge[cbc:ChargeIndicator = false()] | /cn:CreditNote/cac:AllowanceCharge[cbc:ChargeIndicator = false()]
 // Test: string-length(substring-after(cbc:Amount,'.'))<=2
 fn validate_br_dec_01(invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if invoice.cac_allowance_charge.iter().any(|allowance_charge| {
-        allowance_charge
-            .charge_indicator
-            .iter()
-            .any(|charge_indicator| {
-                !charge_indicator
-                    && allowance_charge
-                        .amount
-                        .iter()
-                        .any(|amount| amount.value.iter().any(|value| value.scale() > 2))
-            })
-    }) {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-DEC-01",
-            "[BR-DEC-01]-The allowed maximum number of decimals for the Document level allowance amount (BT-92) is 2.",
-        )));
+    if let Some(index) = invoice
+        .cac_allowance_charge
+        .iter()
+        .position(|allowance_charge| {
+            allowance_charge
+                .charge_indicator
+                .iter()
+                .any(|charge_indicator| {
+                    !charge_indicator
+                        && allowance_charge
+                            .amount
+                            .iter()
+                            .any(|amount| amount.value.iter().any(|value| value.scale() > 2))
+                })
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-DEC-01",
+                "[BR-DEC-01]-The allowed maximum number of decimals for the Document level allowance amount (BT-92) is 2.",
+            )
+            .with_element(format!("cac:AllowanceCharge[{}]", index + 1)),
+        ));
     }
     Ok(())
 }
@@ -12802,12 +12879,29 @@ fn validate_br_cl_23(_invoice: &UblInvoice) -> Result<(), ValidationError> {
 
 // Context: cbc:EmbeddedDocumentBinaryObject[@mimeCode]
 // Test: ((@mimeCode = 'application/pdf' or @mimeCode = 'image/png' or @mimeCode = 'image/jpeg' or @mimeCode = 'text/csv' or @mimeCode = 'application/vnd.openxmlformats-officedocument.spreadsheetml.sheet' or @mimeCode = 'application/vnd.oasis.opendocument.spreadsheet'))
-fn validate_br_cl_24(_invoice: &UblInvoice) -> Result<(), ValidationError> {
-    if false {
-        return Err(ValidationError::Fatal(BusinessRuleViolation::new(
-            "BR-CL-24",
-            "[BR-CL-24]-For Mime code in attribute use MIMEMediaType.",
-        )));
+fn validate_br_cl_24(invoice: &UblInvoice) -> Result<(), ValidationError> {
+    if let Some(index) = invoice
+        .cac_additional_document_reference
+        .iter()
+        .position(|document| {
+            document
+                .cac_attachment
+                .iter()
+                .filter_map(|attachment| attachment.cbc_embedded_document_binary_object.as_ref())
+                .filter_map(|object| object.mime_code.as_deref())
+                .any(|mime_code| !ALLOWED_MIME_CODES.contains(&mime_code))
+        })
+    {
+        return Err(ValidationError::Fatal(
+            BusinessRuleViolation::new(
+                "BR-CL-24",
+                "[BR-CL-24]-For Mime code in attribute use MIMEMediaType.",
+            )
+            .with_element(format!(
+                "cac:AdditionalDocumentReference[{}]/cac:Attachment/cbc:EmbeddedDocumentBinaryObject",
+                index + 1
+            )),
+        ));
     }
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crate::UniffiCustomTypeConverter;
+use crate::XmlFragment;
 use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 use einvoice_deps_yaserde::__xml::attribute::OwnedAttribute;
 use einvoice_deps_yaserde::__xml::name::OwnedName;
@@ -15,7 +16,7 @@ use std::io::Read;
 use std::str::FromStr;
 use std::time::SystemTime;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Iso8601Date(NaiveDate);
 uniffi::custom_type!(Iso8601Date, SystemTime);
 impl UniffiCustomTypeConverter for Iso8601Date {
@@ -33,6 +34,25 @@ impl UniffiCustomTypeConverter for Iso8601Date {
     }
 }
 
+impl Iso8601Date {
+    /// The calendar date
+    pub fn date(&self) -> NaiveDate {
+        self.0
+    }
+}
+
+impl From<NaiveDate> for Iso8601Date {
+    fn from(date: NaiveDate) -> Self {
+        Iso8601Date(date)
+    }
+}
+
+impl std::fmt::Display for Iso8601Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m-%d"))
+    }
+}
+
 impl YaSerialize for Iso8601Date {
     fn serialize<W: std::io::Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
         let start_event_name = writer
@@ -75,7 +95,7 @@ impl YaDeserialize for Iso8601Date {
     }
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 default_namespace = "",
 prefix = "",
@@ -156,9 +176,14 @@ pub struct UblInvoice {
     pub cac_legal_monetary_total: Option<LegalMonetaryTotal>,
     #[yaserde(rename = "InvoiceLine", prefix = "cac")]
     pub cac_invoice_line: Vec<InvoiceLine>,
+    /// Elements of the invoice that are not part of the model, written back at their original position
+    ///
+    /// Only filled by `parse_invoice_lossless`.
+    #[yaserde(rename = "UnknownElements", skip_serializing = true)]
+    pub unknown_elements: Vec<XmlFragment>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -182,7 +207,7 @@ pub struct Payment {
     pub cbc_instruction_id: Option<Identifier>,
 }
 
-#[derive(uniffi::Record, Debug)]
+#[derive(uniffi::Record, Debug, Clone)]
 pub struct MoneyDecimal {
     pub as_integer: i64,
     pub scale: u32,
@@ -208,7 +233,7 @@ impl UniffiCustomTypeConverter for Decimal {
         }
     }
 }
-#[derive(uniffi::Record, Debug)]
+#[derive(uniffi::Record, Debug, Clone, Default)]
 pub struct Amount {
     pub value: Option<Decimal>,
     pub currency_id: Option<String>,
@@ -287,7 +312,7 @@ impl YaDeserialize for Amount {
     }
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -313,7 +338,7 @@ pub struct Period {
     pub cbc_description: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -341,7 +366,7 @@ pub struct Identifier {
     pub scheme_uri: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -387,7 +412,7 @@ pub struct Party {
     pub cac_financial_account: Option<FinancialAccount>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -433,7 +458,7 @@ pub struct CacPerson {
     pub cac_residence_address: Option<Address>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -461,7 +486,7 @@ pub struct CacPowerOfAttorney {
     pub cac_mandate_document_reference: Vec<CacDocumentReference>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -487,7 +512,7 @@ pub struct PartyTaxScheme {
     pub cac_tax_scheme: Option<TaxScheme>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -523,7 +548,7 @@ pub struct Location {
     pub cac_location_coordinate: Vec<LocationCoordinate>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -585,7 +610,7 @@ pub struct Address {
     pub cac_location_coordinate: Vec<LocationCoordinate>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -613,7 +638,7 @@ pub struct LocationCoordinate {
     pub cbc_altitude_measure: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -629,7 +654,7 @@ pub struct CacCountry {
     pub cbc_name: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -643,7 +668,7 @@ pub struct CacAddressLine {
     pub cbc_line: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -657,7 +682,7 @@ pub struct PartyIdentification {
     pub cbc_id: Option<Identifier>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "cbc" = "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2",
@@ -670,7 +695,7 @@ pub struct Language {
     pub cbc_name: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "cbc" = "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2",
@@ -681,7 +706,7 @@ pub struct PartyName {
     pub cbc_name: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -699,7 +724,7 @@ pub struct CacOrderReference {
     pub cbc_sales_order_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -715,7 +740,7 @@ pub struct CacBillingReference {
     pub cac_invoice_document_reference: Option<CacDocumentReference>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -733,7 +758,7 @@ pub struct CacAttachment {
     pub cac_external_reference: Option<CacExternalReference>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -751,7 +776,7 @@ pub struct CbcEmbeddedDocumentBinaryObject {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -767,7 +792,7 @@ pub struct CacExternalReference {
     pub cbc_uri: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -783,7 +808,7 @@ pub struct CacProjectReference {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -799,7 +824,7 @@ pub struct CacAccountingSupplierParty {
     pub cac_party: Option<Party>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -815,7 +840,7 @@ pub struct CacAccountingSupplierPartyCacPartyCacPostalAddressCacCountry {
     pub cbc_identification_code: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -833,7 +858,7 @@ pub struct CacAccountingSupplierPartyCacPartyCacPartyTaxScheme {
     pub cac_tax_scheme: Option<CacAccountingSupplierPartyCacPartyCacPartyTaxSchemeCacTaxScheme>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -849,7 +874,7 @@ pub struct CacAccountingSupplierPartyCacPartyCacPartyTaxSchemeCacTaxScheme {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -869,7 +894,7 @@ pub struct PartyLegalEntity {
     pub cbc_company_legal_form: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -885,7 +910,7 @@ pub struct CompanyId {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -905,7 +930,7 @@ pub struct Contact {
     pub cbc_electronic_mail: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -921,7 +946,7 @@ pub struct CacAccountingCustomerParty {
     pub cac_party: Option<Party>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -937,7 +962,7 @@ pub struct InvoiceCacTaxRepresentativePartyCacPartyTaxSchemeCacTaxScheme {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -957,7 +982,7 @@ pub struct CacDelivery {
     pub cac_delivery_party: Option<Party>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -975,7 +1000,7 @@ pub struct CacDeliveryLocation {
     pub cac_address: Option<CacAddress>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1003,7 +1028,7 @@ pub struct CacAddress {
     pub cac_country: Option<CacCountry>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1019,7 +1044,7 @@ pub struct CacDeliveryParty {
     pub cac_party_name: Option<CacDeliveryCacDeliveryPartyCacPartyName>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1035,7 +1060,7 @@ pub struct CacDeliveryCacDeliveryPartyCacPartyName {
     pub cbc_name: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1069,7 +1094,7 @@ pub struct PaymentMeans {
     pub cac_payment_mandate: Option<PaymentMandate>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1101,7 +1126,7 @@ pub struct PaymentMandate {
     pub cac_clause: Vec<Clause>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1117,7 +1142,7 @@ pub struct Clause {
     pub cbc_content: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1151,7 +1176,7 @@ pub struct CardAccount {
     pub cbc_holder_name: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1167,7 +1192,7 @@ pub struct CbcPaymentMeansCode {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1187,7 +1212,7 @@ pub struct FinancialAccount {
     pub cac_financial_institution_branch: Option<CacFinancialInstitutionBranch>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1203,7 +1228,7 @@ pub struct CacFinancialInstitutionBranch {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1227,7 +1252,7 @@ pub struct CacPaymentTerms {
     pub cac_validity_period: Option<Period>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1255,7 +1280,7 @@ pub struct ExchangeRate {
     pub cbc_date: Option<Iso8601Date>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1297,7 +1322,7 @@ pub struct CacAllowanceCharge {
     pub cac_payment_means: Vec<PaymentMeans>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1315,7 +1340,7 @@ pub struct CacTaxTotal {
     pub cac_tax_subtotal: Vec<CacTaxSubtotal>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1335,7 +1360,7 @@ pub struct CacTaxSubtotal {
     pub cac_tax_category: Option<TaxCategory>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1366,7 +1391,7 @@ pub struct TaxCategory {
     #[yaserde(rename = "TaxScheme", prefix = "cac")]
     pub cac_tax_scheme: Option<TaxScheme>,
 }
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1388,7 +1413,7 @@ pub struct TaxScheme {
     pub cac_jurisdiction_region_address: Vec<Address>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1412,7 +1437,7 @@ pub struct CacTaxSubtotalCacTaxCategory {
     pub cac_tax_scheme: Option<CacTaxTotalCacTaxSubtotalCacTaxCategoryCacTaxScheme>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1428,7 +1453,7 @@ pub struct CacTaxTotalCacTaxSubtotalCacTaxCategoryCacTaxScheme {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1458,7 +1483,7 @@ pub struct LegalMonetaryTotal {
     pub payable_amount: Option<Amount>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1494,7 +1519,7 @@ pub struct InvoiceLine {
     pub cac_sub_invoice_line: Vec<SubInvoiceLine>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1530,7 +1555,7 @@ pub struct SubInvoiceLine {
     pub cac_sub_invoice_line: Vec<SubSubInvoiceLine>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1566,7 +1591,7 @@ pub struct SubSubInvoiceLine {
     pub cac_sub_invoice_line: Vec<SubSubSubInvoiceLine>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1600,7 +1625,7 @@ pub struct SubSubSubInvoiceLine {
     pub cac_price: Option<CacPrice>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1616,7 +1641,7 @@ pub struct CbcInvoicedQuantity {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1648,7 +1673,7 @@ pub struct CacItem {
     pub cac_additional_item_property: Vec<CacAdditionalItemProperty>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1668,7 +1693,7 @@ pub struct CacClassifiedTaxCategory {
     pub cac_tax_scheme: Option<CacInvoiceLineCacItemCacClassifiedTaxCategoryCacTaxScheme>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1684,7 +1709,7 @@ pub struct CacInvoiceLineCacItemCacClassifiedTaxCategoryCacTaxScheme {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1702,7 +1727,7 @@ pub struct CacAdditionalItemProperty {
     pub cbc_value: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1718,7 +1743,7 @@ pub struct CacSellersItemIdentification {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1734,7 +1759,7 @@ pub struct CacOriginCountry {
     pub cbc_identification_code: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1750,7 +1775,7 @@ pub struct CacStandardItemIdentification {
     pub cbc_id: Option<InvoiceCacInvoiceLineCacItemCacStandardItemIdentificationCbcId>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1766,7 +1791,7 @@ pub struct InvoiceCacInvoiceLineCacItemCacStandardItemIdentificationCbcId {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1782,7 +1807,7 @@ pub struct CacBuyersItemIdentification {
     pub cbc_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1798,7 +1823,7 @@ pub struct CacCommodityClassification {
     pub cbc_item_classification_code: Option<CbcItemClassificationCode>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1816,7 +1841,7 @@ pub struct CbcItemClassificationCode {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1836,7 +1861,7 @@ pub struct CacPrice {
     pub cac_allowance_charge: Option<CacAllowanceCharge>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1852,7 +1877,7 @@ pub struct CbcBaseQuantity {
     pub text: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1868,7 +1893,7 @@ pub struct CacOrderLineReference {
     pub cbc_line_id: Option<String>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -1902,7 +1927,7 @@ pub struct CacDocumentReference {
     pub cac_result_of_verification: Option<CacResultOfVerification>,
 }
 
-#[derive(YaSerialize, YaDeserialize, uniffi::Record)]
+#[derive(Clone, Default, YaSerialize, YaDeserialize, uniffi::Record)]
 #[yaserde(
 namespaces = {
     "" = "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use std::process::{Command, Output};
+
+    const VALID: &str = "tests/inputs/ubl/01.01a-INVOICE_ubl.xml";
+    const INVALID: &str =
+        "tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml";
+
+    fn einvoice(args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_einvoice"))
+            .args(args)
+            .output()
+            .expect("Failed to run einvoice")
+    }
+
+    fn stdout(output: &Output) -> String {
+        String::from_utf8(output.stdout.clone()).unwrap()
+    }
+
+    #[test]
+    fn exit_codes_work() {
+        assert_eq!(einvoice(&["validate", VALID]).status.code(), Some(0));
+        assert_eq!(einvoice(&["validate", INVALID]).status.code(), Some(1));
+        assert_eq!(
+            einvoice(&["validate", "tests/inputs/missing.xml"])
+                .status
+                .code(),
+            Some(2)
+        );
+        // The most severe outcome of all files is reported
+        assert_eq!(
+            einvoice(&["validate", VALID, INVALID, "tests/inputs/missing.xml"])
+                .status
+                .code(),
+            Some(2)
+        );
+        assert_eq!(
+            einvoice(&["validate", "tests/inputs/ubl/01.0*a-INVOICE_ubl.xml"])
+                .status
+                .code(),
+            Some(0)
+        );
+        // Usage errors, e.g. no files given, are reported by the argument parser
+        assert_eq!(einvoice(&["validate"]).status.code(), Some(2));
+    }
+
+    #[test]
+    fn human_output_works() {
+        let output = stdout(&einvoice(&["validate", VALID, INVALID]));
+        assert!(output.contains(&format!("{}: valid", VALID)));
+        assert!(output.contains(&format!("{}: ", INVALID)));
+        assert!(output.contains("violation(s)"));
+        assert!(output.contains("BR-01"));
+
+        let output = stdout(&einvoice(&["info", VALID]));
+        assert!(output.contains("Syntax: UBL"));
+    }
+
+    #[test]
+    fn json_output_works() {
+        let output = stdout(&einvoice(&["validate", VALID, INVALID, "--format", "json"]));
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let files = json.as_array().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0]["file"], VALID);
+        assert_eq!(files[0]["valid"], true);
+        assert_eq!(files[0]["violations"].as_array().unwrap().len(), 0);
+        assert_eq!(files[1]["file"], INVALID);
+        assert!(files[1]["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|violation| violation["rule_id"] == "BR-01"));
+
+        let output = stdout(&einvoice(&["info", VALID, "--format", "json"]));
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json[0]["syntax"], "UBL");
+    }
+
+    #[test]
+    fn conversion_to_standard_output_works() {
+        let output = einvoice(&["convert", VALID, "--to", "cii", "--output", "-"]);
+        assert_eq!(output.status.code(), Some(0));
+        assert!(stdout(&output).contains("CrossIndustryInvoice"));
+    }
+}
@@ -0,0 +1,242 @@
+#[cfg(test)]
+mod tests {
+    use einvoice::{convert_invoice, InvoiceSyntax};
+    use rstest::rstest;
+    use xmltree::{Element, XMLNode};
+
+    const CII_AGREEMENT: &str = "SupplyChainTradeTransaction/ApplicableHeaderTradeAgreement";
+    const CII_DELIVERY: &str = "SupplyChainTradeTransaction/ApplicableHeaderTradeDelivery";
+    const CII_SETTLEMENT: &str = "SupplyChainTradeTransaction/ApplicableHeaderTradeSettlement";
+    const CII_LINE: &str = "SupplyChainTradeTransaction/IncludedSupplyChainTradeLineItem";
+
+    /// One business term of each business term group (BG) of EN 16931, by its path in UBL and CII
+    ///
+    /// Each term is converted from the UBL and from the CII variant of a test suite invoice,
+    /// the converted invoice has to carry the value of the source invoice.
+    #[rstest]
+    #[case::bg_1(
+        "01.01_comprehensive_test",
+        "Note[2]",
+        "ExchangedDocument/IncludedNote[2]/Content"
+    )]
+    #[case::bg_2(
+        "01.01_comprehensive_test",
+        "CustomizationID",
+        "ExchangedDocumentContext/GuidelineSpecifiedDocumentContextParameter/ID"
+    )]
+    #[case::bg_3(
+        "01.01_comprehensive_test",
+        "BillingReference/InvoiceDocumentReference/ID",
+        &format!("{CII_SETTLEMENT}/InvoiceReferencedDocument/IssuerAssignedID")
+    )]
+    #[case::bg_4(
+        "01.01_comprehensive_test",
+        "AccountingSupplierParty/Party/PartyLegalEntity/RegistrationName",
+        &format!("{CII_AGREEMENT}/SellerTradeParty/Name")
+    )]
+    #[case::bg_5(
+        "01.01_comprehensive_test",
+        "AccountingSupplierParty/Party/PostalAddress/CityName",
+        &format!("{CII_AGREEMENT}/SellerTradeParty/PostalTradeAddress/CityName")
+    )]
+    #[case::bg_6(
+        "01.01_comprehensive_test",
+        "AccountingSupplierParty/Party/Contact/ElectronicMail",
+        &format!("{CII_AGREEMENT}/SellerTradeParty/DefinedTradeContact/EmailURIUniversalCommunication/URIID")
+    )]
+    #[case::bg_7(
+        "01.01_comprehensive_test",
+        "AccountingCustomerParty/Party/PartyLegalEntity/CompanyID",
+        &format!("{CII_AGREEMENT}/BuyerTradeParty/SpecifiedLegalOrganization/ID")
+    )]
+    #[case::bg_8(
+        "01.01_comprehensive_test",
+        "AccountingCustomerParty/Party/PostalAddress/PostalZone",
+        &format!("{CII_AGREEMENT}/BuyerTradeParty/PostalTradeAddress/PostcodeCode")
+    )]
+    #[case::bg_9(
+        "01.01_comprehensive_test",
+        "AccountingCustomerParty/Party/Contact/Telephone",
+        &format!("{CII_AGREEMENT}/BuyerTradeParty/DefinedTradeContact/TelephoneUniversalCommunication/CompleteNumber")
+    )]
+    #[case::bg_10(
+        "01.01_comprehensive_test",
+        "PayeeParty/PartyName/Name",
+        &format!("{CII_SETTLEMENT}/PayeeTradeParty/Name")
+    )]
+    #[case::bg_11(
+        "01.01_comprehensive_test",
+        "TaxRepresentativeParty/PartyTaxScheme/CompanyID",
+        &format!("{CII_AGREEMENT}/SellerTaxRepresentativeTradeParty/SpecifiedTaxRegistration/ID")
+    )]
+    #[case::bg_12(
+        "01.01_comprehensive_test",
+        "TaxRepresentativeParty/PostalAddress/CityName",
+        &format!("{CII_AGREEMENT}/SellerTaxRepresentativeTradeParty/PostalTradeAddress/CityName")
+    )]
+    #[case::bg_13(
+        "01.01_comprehensive_test",
+        "Delivery/DeliveryParty/PartyName/Name",
+        &format!("{CII_DELIVERY}/ShipToTradeParty/Name")
+    )]
+    #[case::bg_14(
+        "01.01_comprehensive_test",
+        "InvoicePeriod/StartDate",
+        &format!("{CII_SETTLEMENT}/BillingSpecifiedPeriod/StartDateTime/DateTimeString")
+    )]
+    #[case::bg_15(
+        "01.01_comprehensive_test",
+        "Delivery/DeliveryLocation/Address/StreetName",
+        &format!("{CII_DELIVERY}/ShipToTradeParty/PostalTradeAddress/LineOne")
+    )]
+    #[case::bg_16(
+        "01.01_comprehensive_test",
+        "PaymentMeans/PaymentMeansCode",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeSettlementPaymentMeans/TypeCode")
+    )]
+    #[case::bg_17(
+        "01.01_comprehensive_test",
+        "PaymentMeans/PayeeFinancialAccount/ID",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeSettlementPaymentMeans/PayeePartyCreditorFinancialAccount/IBANID")
+    )]
+    #[case::bg_18(
+        "01.03_comprehensive_test",
+        "PaymentMeans/CardAccount/PrimaryAccountNumberID",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeSettlementPaymentMeans/ApplicableTradeSettlementFinancialCard/ID")
+    )]
+    #[case::bg_19(
+        "01.04_comprehensive_test",
+        "PaymentMeans/PaymentMandate/ID",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradePaymentTerms/DirectDebitMandateID")
+    )]
+    #[case::bg_20(
+        "01.01_comprehensive_test",
+        "AllowanceCharge[4]/AllowanceChargeReason",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeAllowanceCharge[4]/Reason")
+    )]
+    #[case::bg_21(
+        "01.01_comprehensive_test",
+        "AllowanceCharge[2]/AllowanceChargeReason",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeAllowanceCharge[2]/Reason")
+    )]
+    #[case::bg_22(
+        "01.01_comprehensive_test",
+        "LegalMonetaryTotal/PayableAmount",
+        &format!("{CII_SETTLEMENT}/SpecifiedTradeSettlementHeaderMonetarySummation/DuePayableAmount")
+    )]
+    #[case::bg_23(
+        "01.01_comprehensive_test",
+        "TaxTotal/TaxSubtotal[2]/TaxCategory/TaxExemptionReasonCode",
+        &format!("{CII_SETTLEMENT}/ApplicableTradeTax[2]/ExemptionReasonCode")
+    )]
+    #[case::bg_24(
+        "01.01_comprehensive_test",
+        "AdditionalDocumentReference[2]/Attachment/EmbeddedDocumentBinaryObject/@filename",
+        &format!("{CII_AGREEMENT}/AdditionalReferencedDocument[2]/AttachmentBinaryObject/@filename")
+    )]
+    #[case::bg_25(
+        "01.01_comprehensive_test",
+        "InvoiceLine[2]/LineExtensionAmount",
+        &format!("{CII_LINE}[2]/SpecifiedLineTradeSettlement/SpecifiedTradeSettlementLineMonetarySummation/LineTotalAmount")
+    )]
+    #[case::bg_26(
+        "01.01_comprehensive_test",
+        "InvoiceLine/InvoicePeriod/EndDate",
+        &format!("{CII_LINE}/SpecifiedLineTradeSettlement/BillingSpecifiedPeriod/EndDateTime/DateTimeString")
+    )]
+    #[case::bg_27(
+        "01.01_comprehensive_test",
+        "InvoiceLine/AllowanceCharge[4]/AllowanceChargeReason",
+        &format!("{CII_LINE}/SpecifiedLineTradeSettlement/SpecifiedTradeAllowanceCharge[4]/Reason")
+    )]
+    #[case::bg_28(
+        "01.01_comprehensive_test",
+        "InvoiceLine/AllowanceCharge[2]/AllowanceChargeReason",
+        &format!("{CII_LINE}/SpecifiedLineTradeSettlement/SpecifiedTradeAllowanceCharge[2]/Reason")
+    )]
+    #[case::bg_29(
+        "01.01_comprehensive_test",
+        "InvoiceLine[2]/Price/PriceAmount",
+        &format!("{CII_LINE}[2]/SpecifiedLineTradeAgreement/NetPriceProductTradePrice/ChargeAmount")
+    )]
+    #[case::bg_30(
+        "01.01_comprehensive_test",
+        "InvoiceLine/Item/ClassifiedTaxCategory/ID",
+        &format!("{CII_LINE}/SpecifiedLineTradeSettlement/ApplicableTradeTax/CategoryCode")
+    )]
+    #[case::bg_31(
+        "01.01_comprehensive_test",
+        "InvoiceLine/Item/SellersItemIdentification/ID",
+        &format!("{CII_LINE}/SpecifiedTradeProduct/SellerAssignedID")
+    )]
+    #[case::bg_32(
+        "01.01_comprehensive_test",
+        "InvoiceLine/Item/AdditionalItemProperty[2]/Value",
+        &format!("{CII_LINE}/SpecifiedTradeProduct/ApplicableProductCharacteristic[2]/Value")
+    )]
+    fn business_term_groups_are_converted(
+        #[case] invoice: &str,
+        #[case] ubl_path: &str,
+        #[case] cii_path: &str,
+    ) {
+        let ubl = read_input(&format!("ubl/{}_ubl.xml", invoice));
+        let converted = convert_invoice(&ubl, InvoiceSyntax::Cii).unwrap();
+        let expected = value(&ubl, ubl_path);
+        assert!(
+            expected.is_some(),
+            "{} is missing in the UBL invoice",
+            ubl_path
+        );
+        assert_eq!(value(&converted, cii_path), expected, "UBL to CII");
+
+        let cii = read_input(&format!("cii/{}_uncefact.xml", invoice));
+        let converted = convert_invoice(&cii, InvoiceSyntax::Ubl).unwrap();
+        let expected = value(&cii, cii_path);
+        assert!(
+            expected.is_some(),
+            "{} is missing in the CII invoice",
+            cii_path
+        );
+        assert_eq!(value(&converted, ubl_path), expected, "CII to UBL");
+    }
+
+    fn read_input(name: &str) -> String {
+        std::fs::read_to_string(format!("tests/inputs/{}", name)).expect("Cannot read file")
+    }
+
+    /// The value at a path of local names below the root element, e.g. `InvoiceLine[2]/Item/Name` or `Attachment/@filename`
+    ///
+    /// Values are compared across syntaxes: the subject code UBL writes in front of a note (`#AAC#`)
+    /// and the dashes of UBL dates are left out.
+    fn value(xml: &str, path: &str) -> Option<String> {
+        let mut element = Element::parse(xml.as_bytes()).expect("Failed to parse XML");
+        let mut steps = path.split('/').peekable();
+        while let Some(step) = steps.next() {
+            if let Some(attribute) = step.strip_prefix('@') {
+                return element.attributes.get(attribute).cloned();
+            }
+            let (name, index) = match step.split_once('[') {
+                Some((name, index)) => (name, index.trim_end_matches(']').parse().unwrap()),
+                None => (step, 1),
+            };
+            element = element
+                .children
+                .into_iter()
+                .filter_map(|child| match child {
+                    XMLNode::Element(child) if child.name == name => Some(child),
+                    _ => None,
+                })
+                .nth(index - 1)?;
+            if steps.peek().is_none() {
+                let text = element.get_text()?.trim().to_string();
+                let text = match text.strip_prefix('#').and_then(|note| note.split_once('#')) {
+                    Some((_, content)) => content.to_string(),
+                    None => text,
+                };
+                let is_date = text.len() == 10 && text.as_bytes()[4] == b'-';
+                return Some(if is_date { text.replace('-', "") } else { text });
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpStream;
+    use std::process::{Child, Command, Stdio};
+
+    const VALID: &str = "tests/inputs/ubl/01.01a-INVOICE_ubl.xml";
+    const INVALID: &str =
+        "tests/inputs/ubl/invalid/no_customizationid_01.01_comprehensive_test_ubl.xml";
+    const PDF: &str = "tests/inputs/zugferd/EXTENDED_Projektabschlussrechnung.pdf";
+
+    /// A server listening on a port of localhost chosen by the operating system, stopped when dropped
+    struct TestServer {
+        child: Child,
+        address: String,
+    }
+
+    impl TestServer {
+        fn start(args: &[&str]) -> Self {
+            let mut child = Command::new(env!("CARGO_BIN_EXE_einvoice-server"))
+                .args(["--host", "127.0.0.1", "--port", "0", "--threads", "2"])
+                .args(args)
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to start einvoice-server");
+            let mut line = String::new();
+            BufReader::new(child.stderr.take().unwrap())
+                .read_line(&mut line)
+                .unwrap();
+            let address = line
+                .strip_prefix("Listening on http://")
+                .and_then(|rest| rest.split_whitespace().next())
+                .unwrap_or_else(|| panic!("Unexpected output: {}", line))
+                .to_string();
+            TestServer { child, address }
+        }
+
+        /// Send a request and return the status, headers and body of the response
+        fn request(&self, method: &str, target: &str, body: &[u8]) -> (u16, String, Vec<u8>) {
+            let mut stream = TcpStream::connect(&self.address).unwrap();
+            write!(
+                stream,
+                "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                method,
+                target,
+                self.address,
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(body).unwrap();
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).unwrap();
+            let split = response
+                .windows(4)
+                .position(|window| window == b"\r\n\r\n")
+                .expect("Response without header end");
+            let head = String::from_utf8_lossy(&response[..split]).to_string();
+            let status = head
+                .split_whitespace()
+                .nth(1)
+                .and_then(|status| status.parse().ok())
+                .expect("Response without status");
+            (status, head, response[split + 4..].to_vec())
+        }
+
+        fn post(&self, target: &str, file: &str) -> (u16, String, Vec<u8>) {
+            self.request("POST", target, &std::fs::read(file).unwrap())
+        }
+    }
+
+    impl Drop for TestServer {
+        fn drop(&mut self) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+
+    fn json(body: &[u8]) -> serde_json::Value {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    #[test]
+    fn validation_works() {
+        let server = TestServer::start(&[]);
+        let (status, head, body) = server.post("/validate", VALID);
+        assert_eq!(status, 200);
+        assert!(head.contains("application/json"));
+        assert_eq!(json(&body)["valid"], true);
+
+        let (status, _, body) = server.post("/validate", INVALID);
+        assert_eq!(status, 406);
+        assert!(json(&body)["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|violation| violation["rule_id"] == "BR-01"));
+
+        let (status, head, body) = server.post("/validate?format=svrl", INVALID);
+        assert_eq!(status, 406);
+        assert!(head.contains("application/xml"));
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("svrl:failed-assert"));
+
+        let (status, _, body) = server.request("POST", "/validate", b"<Unknown/>");
+        assert_eq!(status, 422);
+        assert!(json(&body)["error"].is_string());
+
+        let (status, _, body) = server.post("/validate", PDF);
+        assert_eq!(status, 200);
+        assert_eq!(json(&body)["embedded_file"], "factur-x.xml");
+    }
+
+    #[test]
+    fn conversion_extraction_and_rendering_work() {
+        let server = TestServer::start(&[]);
+        let (status, _, body) = server.post("/convert?to=cii", VALID);
+        assert_eq!(status, 200);
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("CrossIndustryInvoice"));
+        assert_eq!(server.post("/convert?to=pdf", VALID).0, 400);
+
+        let (status, head, body) = server.post("/extract", PDF);
+        assert_eq!(status, 200);
+        assert!(head.contains("Content-Disposition: inline; filename="));
+        assert!(String::from_utf8(body)
+            .unwrap()
+            .contains("CrossIndustryInvoice"));
+        assert_eq!(server.post("/extract", VALID).0, 422);
+
+        let (status, head, body) = server.post("/render", VALID);
+        assert_eq!(status, 200);
+        assert!(head.contains("text/html"));
+        assert!(String::from_utf8(body).unwrap().contains("<html"));
+    }
+
+    #[test]
+    fn requests_are_checked() {
+        let server = TestServer::start(&["--max-body-size", "100"]);
+        assert_eq!(server.post("/validate", VALID).0, 413);
+        assert_eq!(server.request("POST", "/validate", b"").0, 400);
+        assert_eq!(server.request("GET", "/validate", b"").0, 405);
+        assert_eq!(server.request("GET", "/unknown", b"").0, 404);
+    }
+
+    #[test]
+    fn monitoring_works() {
+        let server = TestServer::start(&[]);
+        let (status, _, body) = server.request("GET", "/health", b"");
+        assert_eq!(status, 200);
+        assert_eq!(json(&body)["status"], "UP");
+
+        server.post("/validate", VALID);
+        server.post("/validate", INVALID);
+        server.request("GET", "/unknown", b"");
+        let (status, _, body) = server.request("GET", "/metrics", b"");
+        assert_eq!(status, 200);
+        let metrics = String::from_utf8(body).unwrap();
+        assert!(metrics.contains("einvoice_validations_total{result=\"valid\"} 1"));
+        assert!(metrics.contains("einvoice_validations_total{result=\"invalid\"} 1"));
+        assert!(metrics.contains("einvoice_requests_total{endpoint=\"validate\",status=\"406\"} 1"));
+        assert!(metrics.contains("einvoice_requests_total{endpoint=\"unknown\",status=\"404\"} 1"));
+    }
+}